@@ -1,8 +1,9 @@
 use crate::{
     middleware::AuthenticationMiddleware,
     uses::{
-        get_nonce, gql_playground, health_check, indexer_status, query_graph,
-        register_indexer_assets, remove_indexer, verify_signature,
+        export_indexer, get_nonce, gql_playground, health_check, indexer_logs,
+        indexer_status, plan_schema_migration, query_graph, register_indexer_assets,
+        remove_indexer, restore_indexer, snapshot_indexer, verify_signature,
     },
 };
 
@@ -12,10 +13,10 @@ use crate::middleware::MetricsMiddleware;
 use async_std::sync::{Arc, RwLock};
 use axum::{
     error_handling::HandleErrorLayer,
-    extract::{Extension, Json},
+    extract::{DefaultBodyLimit, Extension, Json},
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::{delete, get, post},
+    routing::{get, post},
     Router,
 };
 use fuel_indexer_database::{IndexerConnectionPool, IndexerDatabaseError};
@@ -23,6 +24,7 @@ use fuel_indexer_graphql::graphql::GraphqlError;
 use fuel_indexer_lib::{config::IndexerConfig, defaults, utils::ServiceRequest};
 use fuel_indexer_schema::db::{manager::SchemaManager, IndexerSchemaDbError};
 use hyper::Method;
+use parquet::errors::ParquetError;
 use serde_json::json;
 use std::{
     net::SocketAddr,
@@ -99,6 +101,10 @@ pub enum ApiError {
     HexError(#[from] hex::FromHexError),
     #[error("BoxError: {0:?}")]
     BoxError(#[from] axum::BoxError),
+    #[error("Parquet error: {0:?}")]
+    Parquet(#[from] ParquetError),
+    #[error("IO error: {0:?}")]
+    Io(#[from] std::io::Error),
 }
 
 impl Default for ApiError {
@@ -127,6 +133,9 @@ impl IntoResponse for ApiError {
             Self::Http(HttpError::Conflict(e)) => {
                 (StatusCode::CONFLICT, format!("Conflict: {e}"))
             }
+            Self::Http(HttpError::BadRequest) => {
+                (StatusCode::BAD_REQUEST, "Bad request.".to_string())
+            }
             Self::Http(HttpError::Unauthorized) => {
                 (StatusCode::UNAUTHORIZED, "Unauthorized.".to_string())
             }
@@ -145,6 +154,12 @@ impl IntoResponse for ApiError {
                 (StatusCode::BAD_REQUEST, format!("Crypto error: {e}."))
             }
             Self::Graphql(e) => (StatusCode::BAD_REQUEST, format!("GraphQL error: {e}.")),
+            Self::SchemaError(IndexerSchemaDbError::IncompatibleSchemaMigration(e)) => {
+                (StatusCode::CONFLICT, format!("Schema error: {e}"))
+            }
+            Self::SchemaError(IndexerSchemaDbError::IncompatibleSnapshot(e)) => {
+                (StatusCode::BAD_REQUEST, format!("Snapshot error: {e}"))
+            }
             Self::SchemaError(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Schema error: {e}."),
@@ -182,6 +197,7 @@ impl GraphQlApi {
         let sm = SchemaManager::new(pool.clone());
         let schema_manager = Arc::new(RwLock::new(sm));
         let max_body_size = config.graphql_api.max_body_size;
+        let max_snapshot_size = config.graphql_api.max_snapshot_size;
         let start_time = Arc::new(Instant::now());
         let log_level = Level::from_str(config.log_level.as_ref()).unwrap();
 
@@ -217,18 +233,34 @@ impl GraphQlApi {
         let graph_routes = graph_routes.layer(MetricsMiddleware::default());
 
         let indexer_routes = Router::new()
-            .route("/:namespace/:identifier", post(register_indexer_assets))
-            .layer(AuthenticationMiddleware::from(&config))
-            .layer(Extension(tx.clone()))
-            .layer(Extension(schema_manager.clone()))
-            .layer(Extension(pool.clone()))
-            .layer(Extension(config.clone()))
-            .route("/:namespace/:identifier", delete(remove_indexer))
+            .route(
+                "/:namespace/:identifier",
+                post(register_indexer_assets).delete(remove_indexer),
+            )
+            .route("/:namespace/:identifier/logs", get(indexer_logs))
+            .route("/:namespace/:identifier/export", get(export_indexer))
+            .route("/:namespace/:identifier/snapshot", get(snapshot_indexer))
+            .route(
+                "/:namespace/:identifier/migration",
+                post(plan_schema_migration),
+            )
+            .layer(RequestBodyLimitLayer::new(max_body_size));
+
+        // Snapshots are much larger than other request bodies, so the restore
+        // route gets its own limit rather than the one applied above.
+        let restore_route = Router::new()
+            .route("/:namespace/:identifier/restore", post(restore_indexer))
+            .layer(DefaultBodyLimit::max(max_snapshot_size))
+            .layer(RequestBodyLimitLayer::new(max_snapshot_size));
+
+        // Authentication and extensions are layered once, over every indexer route.
+        let indexer_routes = indexer_routes
+            .merge(restore_route)
             .layer(AuthenticationMiddleware::from(&config))
             .layer(Extension(tx))
+            .layer(Extension(schema_manager.clone()))
             .layer(Extension(pool.clone()))
-            .layer(Extension(config.clone()))
-            .layer(RequestBodyLimitLayer::new(max_body_size));
+            .layer(Extension(config.clone()));
 
         #[cfg(feature = "metrics")]
         let indexer_routes = indexer_routes.layer(MetricsMiddleware::default());
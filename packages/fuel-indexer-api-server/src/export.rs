@@ -0,0 +1,897 @@
+use crate::{
+    api::{ApiError, ApiResult, HttpError},
+    models::{ExportFormat, ExportRequest},
+};
+use arrow_array::{
+    ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema};
+use bech32::{ToBase32, Variant};
+use fuel_indexer_database::{
+    queries,
+    types::{ColumnType, Persistence, SqlNamed},
+    DbType, IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_graphql::{
+    arguments::{parse_argument_into_param, ParamType},
+    graphql::GraphqlError,
+};
+use fuel_indexer_lib::{
+    defaults,
+    graphql::types::{FromBlockCol, IdCol, ToBlockCol},
+};
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
+use serde_json::{Map, Value};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{Seek, SeekFrom, Write},
+    sync::Arc,
+};
+
+/// Offset between TAI64 labels and UNIX timestamps.
+const TAI64_UNIX_OFFSET: i64 = (1 << 62) + 10;
+
+/// Human-readable part of Bech32 encoded addresses.
+const BECH32_HRP: &str = "fuel";
+
+/// How the values of a column are decoded when exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportKind {
+    Boolean,
+    Integer,
+    Unsigned,
+    Hex,
+    Bech32,
+    Tai64,
+    Json,
+    Text,
+}
+
+impl ExportKind {
+    /// Return how the values of a column of the given type are decoded.
+    fn from_coltype(coltype: ColumnType) -> Self {
+        match coltype {
+            ColumnType::Boolean => ExportKind::Boolean,
+            ColumnType::BlockHeight
+            | ColumnType::Int1
+            | ColumnType::Int4
+            | ColumnType::Int8
+            | ColumnType::Timestamp
+            | ColumnType::UInt1
+            | ColumnType::UInt4 => ExportKind::Integer,
+            ColumnType::ForeignKey | ColumnType::ID | ColumnType::UInt8 => {
+                ExportKind::Unsigned
+            }
+            ColumnType::Address | ColumnType::ContractId | ColumnType::Identity => {
+                ExportKind::Bech32
+            }
+            ColumnType::AssetId
+            | ColumnType::Blob
+            | ColumnType::BlockId
+            | ColumnType::Bytes32
+            | ColumnType::Bytes4
+            | ColumnType::Bytes64
+            | ColumnType::Bytes8
+            | ColumnType::HexString
+            | ColumnType::MessageId
+            | ColumnType::Nonce
+            | ColumnType::Salt
+            | ColumnType::Signature
+            | ColumnType::TxId => ExportKind::Hex,
+            ColumnType::Tai64Timestamp => ExportKind::Tai64,
+            ColumnType::Array | ColumnType::Json | ColumnType::Virtual => {
+                ExportKind::Json
+            }
+            _ => ExportKind::Text,
+        }
+    }
+
+    /// Arrow type of the Parquet column holding values of this kind.
+    fn data_type(&self) -> DataType {
+        match self {
+            ExportKind::Boolean => DataType::Boolean,
+            ExportKind::Integer | ExportKind::Tai64 => DataType::Int64,
+            ExportKind::Unsigned => DataType::UInt64,
+            _ => DataType::Utf8,
+        }
+    }
+}
+
+/// Represents a decoded value of an exported record.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ExportValue {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Unsigned(u64),
+    Text(String),
+    Json(Value),
+}
+
+impl ExportValue {
+    /// Return the value as it's written to a CSV file, or to a text column of a
+    /// Parquet file.
+    fn to_text(&self) -> Option<String> {
+        match self {
+            ExportValue::Null => None,
+            ExportValue::Boolean(v) => Some(v.to_string()),
+            ExportValue::Integer(v) => Some(v.to_string()),
+            ExportValue::Unsigned(v) => Some(v.to_string()),
+            ExportValue::Text(v) => Some(v.clone()),
+            ExportValue::Json(v) => Some(v.to_string()),
+        }
+    }
+
+    /// Return the value as it's written to a JSON Lines file.
+    fn to_json(&self) -> Value {
+        match self {
+            ExportValue::Null => Value::Null,
+            ExportValue::Boolean(v) => Value::from(*v),
+            ExportValue::Integer(v) => Value::from(*v),
+            ExportValue::Unsigned(v) => Value::from(*v),
+            ExportValue::Text(v) => Value::from(v.as_str()),
+            ExportValue::Json(v) => v.clone(),
+        }
+    }
+}
+
+/// Prefix a hex-encoded value with `0x`.
+fn prefix_hex(value: &str) -> String {
+    format!("0x{value}")
+}
+
+/// Return the Bech32 encoding of a hex-encoded address, or the address prefixed with
+/// `0x` if it isn't valid hex.
+fn bech32_address(value: &str) -> String {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| {
+            bech32::encode(BECH32_HRP, bytes.to_base32(), Variant::Bech32m).ok()
+        })
+        .unwrap_or_else(|| prefix_hex(value))
+}
+
+/// Decode an item of a list whose items are hex-encoded.
+fn decode_item(kind: ExportKind, item: Value) -> Value {
+    match (kind, item) {
+        (ExportKind::Hex, Value::String(s)) => Value::String(prefix_hex(&s)),
+        (ExportKind::Bech32, Value::String(s)) => Value::String(bech32_address(&s)),
+        (_, item) => item,
+    }
+}
+
+/// A column of an exported table.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportColumn {
+    /// Name of the column.
+    name: String,
+
+    /// How the values of the column are decoded.
+    kind: ExportKind,
+
+    /// How the items of the column are decoded, if it's a list.
+    item_kind: Option<ExportKind>,
+
+    /// Type of the column.
+    coltype: ColumnType,
+}
+
+impl ExportColumn {
+    /// Return the SQL expression selecting the value of this column as text.
+    fn select(&self, table: &str, db_type: &DbType) -> String {
+        let column = format!("{table}.{}", self.name);
+        match (db_type, self.coltype) {
+            (DbType::Postgres, ColumnType::Array) => {
+                format!("array_to_json({column})::text")
+            }
+            (DbType::Postgres, _) => format!("{column}::text"),
+            (DbType::Sqlite, _) => format!("CAST({column} AS TEXT)"),
+        }
+    }
+
+    /// Decode a value of this column, as read from the database.
+    fn decode(&self, value: Option<String>) -> ExportValue {
+        let Some(value) = value else {
+            return ExportValue::Null;
+        };
+
+        match self.kind {
+            ExportKind::Boolean => {
+                ExportValue::Boolean(matches!(value.as_str(), "true" | "t" | "1"))
+            }
+            ExportKind::Integer => value
+                .parse()
+                .map(ExportValue::Integer)
+                .unwrap_or(ExportValue::Text(value)),
+            ExportKind::Unsigned => value
+                .parse()
+                .map(ExportValue::Unsigned)
+                .unwrap_or(ExportValue::Text(value)),
+            ExportKind::Hex => ExportValue::Text(prefix_hex(&value)),
+            ExportKind::Bech32 => ExportValue::Text(bech32_address(&value)),
+            ExportKind::Tai64 => u64::from_str_radix(&value, 16)
+                .map(|label| ExportValue::Integer(label as i64 - TAI64_UNIX_OFFSET))
+                .unwrap_or(ExportValue::Text(value)),
+            ExportKind::Json => match serde_json::from_str::<Value>(&value) {
+                // Lists of hex-encoded items are decoded item by item.
+                Ok(Value::Array(items)) => match self.item_kind {
+                    Some(kind) => ExportValue::Json(Value::Array(
+                        items
+                            .into_iter()
+                            .map(|item| decode_item(kind, item))
+                            .collect(),
+                    )),
+                    None => ExportValue::Json(Value::Array(items)),
+                },
+                Ok(json) => ExportValue::Json(json),
+                Err(_) => ExportValue::Text(value),
+            },
+            ExportKind::Text => ExportValue::Text(value),
+        }
+    }
+}
+
+/// The records of an entity to be exported, read from its table, or its history
+/// table if a block range is given.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportTable {
+    /// Name of the entity.
+    entity: String,
+
+    /// Fully qualified name of the table the records are read from.
+    table: String,
+
+    /// Columns of the table that are exported.
+    columns: Vec<ExportColumn>,
+
+    /// Columns by which records are ordered, and paginated.
+    keys: Vec<String>,
+
+    /// Conditions that the exported records must match.
+    conditions: Vec<String>,
+
+    /// The type of database the table is in.
+    db_type: DbType,
+}
+
+impl ExportTable {
+    /// Return the exports of the entities requested, in the order in which they're
+    /// written.
+    pub fn from_request(
+        schema: &IndexerSchema,
+        request: &ExportRequest,
+    ) -> ApiResult<Vec<Self>> {
+        let mut entities = schema
+            .parsed()
+            .non_enum_typdefs()
+            .into_iter()
+            .map(|(name, _)| name.to_string())
+            .filter(|name| !schema.parsed().is_virtual_typedef(name))
+            .collect::<Vec<String>>();
+        entities.sort();
+
+        match &request.entity {
+            Some(entity) => {
+                if !entities.contains(entity) {
+                    return Err(ApiError::Http(HttpError::NotFound(format!(
+                        "Entity '{entity}' does not exist"
+                    ))));
+                }
+                Ok(vec![Self::new(schema, entity, request)?])
+            }
+            None => entities
+                .iter()
+                .filter(|entity| {
+                    (request.from_block.is_none() && request.to_block.is_none())
+                        || schema.parsed().is_historical_typedef(entity)
+                })
+                .map(|entity| Self::new(schema, entity, request))
+                .collect(),
+        }
+    }
+
+    /// Create the export of a single entity.
+    pub fn new(
+        schema: &IndexerSchema,
+        entity: &str,
+        request: &ExportRequest,
+    ) -> ApiResult<Self> {
+        let table = schema
+            .tables()
+            .iter()
+            .find(|t| {
+                t.sql_name() == entity.to_lowercase()
+                    && t.persistence() == Persistence::Scalar
+            })
+            .ok_or_else(|| {
+                ApiError::Http(HttpError::NotFound(format!(
+                    "Entity '{entity}' does not exist"
+                )))
+            })?;
+        let db_type = schema.db_type().clone();
+
+        let is_ranged = request.from_block.is_some() || request.to_block.is_some();
+        if is_ranged && !table.is_historical() {
+            return Err(ApiError::Graphql(GraphqlError::NotHistorical(
+                entity.to_string(),
+            )));
+        }
+
+        let (name, columns, keys) = if is_ranged {
+            (
+                table.history_qualified_name(),
+                table.history_columns(),
+                vec![
+                    IdCol::to_lowercase_string(),
+                    FromBlockCol::to_lowercase_string(),
+                ],
+            )
+        } else {
+            (
+                table.qualified_name(),
+                table.columns().clone(),
+                vec![IdCol::to_lowercase_string()],
+            )
+        };
+
+        let columns = columns
+            .into_iter()
+            .filter(|c| c.coltype != ColumnType::Object)
+            .map(|c| ExportColumn {
+                kind: ExportKind::from_coltype(c.coltype),
+                item_kind: c.array_coltype.map(ExportKind::from_coltype),
+                name: c.name,
+                coltype: c.coltype,
+            })
+            .collect::<Vec<ExportColumn>>();
+
+        let mut conditions = Vec::new();
+        if let Some(filter) = &request.filter {
+            let value = async_graphql::Value::from_json(serde_json::from_str(filter)?)?
+                .into_value();
+            match parse_argument_into_param(
+                Some(&entity.to_string()),
+                "filter",
+                value,
+                schema,
+            )? {
+                ParamType::Filter(filter) => {
+                    conditions.push(filter.to_sql(name.clone(), &db_type))
+                }
+                _ => return Err(ApiError::Http(HttpError::BadRequest)),
+            }
+        }
+
+        // A version is in the block range if it was valid at any block in it.
+        let (from_block, to_block) = (
+            FromBlockCol::to_lowercase_str(),
+            ToBlockCol::to_lowercase_str(),
+        );
+        if let Some(height) = request.to_block {
+            conditions.push(format!("{name}.{from_block} <= {height}"));
+        }
+        if let Some(height) = request.from_block {
+            conditions.push(format!(
+                "({name}.{to_block} IS NULL OR {name}.{to_block} > {height})"
+            ));
+        }
+
+        Ok(Self {
+            entity: entity.to_string(),
+            table: name,
+            columns,
+            keys,
+            conditions,
+            db_type,
+        })
+    }
+
+    /// Return the query reading the page of at most `limit` records that follows
+    /// the record with the given keys.
+    ///
+    /// Each record is read as a JSON array of the text of its column values.
+    pub fn page_query(&self, after: Option<&[String]>, limit: usize) -> String {
+        let table = &self.table;
+        let values = self
+            .columns
+            .iter()
+            .map(|c| c.select(table, &self.db_type))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let keys = self
+            .keys
+            .iter()
+            .map(|k| format!("{table}.{k}"))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let mut conditions = self.conditions.clone();
+        if let Some(after) = after {
+            conditions.push(format!("({keys}) > ({})", self.key_literals(after)));
+        }
+        let conditions = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+
+        let array = match self.db_type {
+            DbType::Postgres => "json_build_array",
+            DbType::Sqlite => "json_array",
+        };
+
+        format!(
+            "SELECT {array}({values}) FROM {table}{conditions} ORDER BY {keys} LIMIT {limit}"
+        )
+    }
+
+    /// Return the SQL literals of the keys of a record.
+    fn key_literals(&self, keys: &[String]) -> String {
+        // SQLite stores IDs as text, so they're compared as such.
+        keys.iter()
+            .zip(self.keys.iter())
+            .map(|(value, key)| match self.db_type {
+                DbType::Sqlite if *key == IdCol::to_lowercase_str() => {
+                    format!("'{value}'")
+                }
+                _ => value.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Return the keys of a record, as read from the database.
+    pub fn keys_of(&self, record: &[Option<String>]) -> Vec<String> {
+        self.keys
+            .iter()
+            .filter_map(|k| self.columns.iter().position(|c| c.name == *k))
+            .map(|i| record[i].clone().unwrap_or_default())
+            .collect()
+    }
+
+    /// Decode a record, as read from the database.
+    pub fn decode(&self, record: Vec<Option<String>>) -> Vec<ExportValue> {
+        self.columns
+            .iter()
+            .zip(record)
+            .map(|(c, v)| c.decode(v))
+            .collect()
+    }
+
+    /// Return the header line of a CSV export of this table.
+    pub fn csv_header(&self) -> String {
+        csv_line(self.columns.iter().map(|c| Some(c.name.clone())))
+    }
+
+    /// Return the line of a CSV export holding a record.
+    pub fn csv_record(&self, record: &[ExportValue]) -> String {
+        csv_line(record.iter().map(|v| v.to_text()))
+    }
+
+    /// Return the line of a JSON Lines export holding a record.
+    ///
+    /// Records of exports of every entity are tagged with the name of their entity.
+    pub fn json_record(&self, record: &[ExportValue], tagged: bool) -> String {
+        let mut object = Map::new();
+        if tagged {
+            object.insert("__typename".to_string(), Value::from(self.entity.as_str()));
+        }
+        for (column, value) in self.columns.iter().zip(record) {
+            object.insert(column.name.clone(), value.to_json());
+        }
+        format!("{}\n", Value::Object(object))
+    }
+
+    /// Return the Arrow schema of a Parquet file holding records of this table.
+    fn parquet_schema(&self) -> Arc<Schema> {
+        Arc::new(Schema::new(
+            self.columns
+                .iter()
+                .map(|c| Field::new(&c.name, c.kind.data_type(), true))
+                .collect::<Vec<Field>>(),
+        ))
+    }
+
+    /// Return a writer of a Parquet file holding records of this table.
+    pub fn parquet_writer<W: Write + Send>(&self, out: W) -> ApiResult<ArrowWriter<W>> {
+        Ok(ArrowWriter::try_new(out, self.parquet_schema(), None)?)
+    }
+
+    /// Write a page of records to a Parquet file, as a row group of its own.
+    pub fn write_parquet_page<W: Write + Send>(
+        &self,
+        writer: &mut ArrowWriter<W>,
+        page: &[Vec<ExportValue>],
+    ) -> ApiResult<()> {
+        let arrays = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| parquet_array(c.kind, page.iter().map(|r| &r[i])))
+            .collect::<Vec<ArrayRef>>();
+        let batch = RecordBatch::try_new(self.parquet_schema(), arrays)
+            .map_err(ParquetError::from)?;
+        writer.write(&batch)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Return the Arrow array holding the values of a column.
+fn parquet_array<'a>(
+    kind: ExportKind,
+    values: impl Iterator<Item = &'a ExportValue>,
+) -> ArrayRef {
+    match kind.data_type() {
+        DataType::Boolean => Arc::new(BooleanArray::from(
+            values
+                .map(|v| match v {
+                    ExportValue::Boolean(v) => Some(*v),
+                    _ => None,
+                })
+                .collect::<Vec<Option<bool>>>(),
+        )),
+        DataType::Int64 => Arc::new(Int64Array::from(
+            values
+                .map(|v| match v {
+                    ExportValue::Integer(v) => Some(*v),
+                    _ => None,
+                })
+                .collect::<Vec<Option<i64>>>(),
+        )),
+        DataType::UInt64 => Arc::new(UInt64Array::from(
+            values
+                .map(|v| match v {
+                    ExportValue::Unsigned(v) => Some(*v),
+                    _ => None,
+                })
+                .collect::<Vec<Option<u64>>>(),
+        )),
+        _ => Arc::new(StringArray::from(
+            values.map(|v| v.to_text()).collect::<Vec<Option<String>>>(),
+        )),
+    }
+}
+
+/// Return a line of a CSV file holding the given fields.
+fn csv_line(fields: impl Iterator<Item = Option<String>>) -> String {
+    let line = fields
+        .map(|field| match field {
+            Some(f) if f.contains([',', '"', '\n', '\r']) => {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            }
+            Some(f) => f,
+            None => String::new(),
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    format!("{line}\n")
+}
+
+/// Return the records of a page read by `ExportTable::page_query`.
+pub(crate) fn page_records(page: Value) -> Vec<Vec<Option<String>>> {
+    match page {
+        Value::Array(records) => records
+            .into_iter()
+            .map(|record| match record {
+                Value::Array(values) => values
+                    .into_iter()
+                    .map(|v| match v {
+                        Value::Null => None,
+                        Value::String(s) => Some(s),
+                        v => Some(v.to_string()),
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Reader of the records of an export.
+///
+/// Every page is read in the same transaction, so that the export is a consistent
+/// snapshot of the indexer's data even while the indexer writes new records.
+pub(crate) struct ExportReader {
+    /// Connection the records are read from, until the export is finished.
+    conn: Option<IndexerConnection>,
+}
+
+impl ExportReader {
+    /// Start the transaction in which the records of an export are read.
+    pub async fn new(pool: &IndexerConnectionPool) -> sqlx::Result<Self> {
+        let mut conn = pool.acquire().await?;
+        match conn {
+            IndexerConnection::Postgres(_) => {
+                queries::execute_query(
+                    &mut conn,
+                    "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY".to_string(),
+                )
+                .await?;
+            }
+            IndexerConnection::Sqlite(_) => {
+                queries::start_transaction(&mut conn).await?;
+            }
+        }
+
+        Ok(Self { conn: Some(conn) })
+    }
+
+    /// Read the page of records of a table that follows the record with the given
+    /// keys.
+    async fn read_page(
+        &mut self,
+        table: &ExportTable,
+        after: Option<&[String]>,
+    ) -> sqlx::Result<Vec<Vec<Option<String>>>> {
+        let conn = self.conn.as_mut().ok_or(sqlx::Error::PoolClosed)?;
+        let query = table.page_query(after, defaults::EXPORT_BATCH_SIZE);
+        let page = queries::run_query(conn, query).await?;
+        Ok(page_records(page))
+    }
+
+    /// End the transaction of the export, returning its connection to the pool.
+    async fn finish(&mut self) -> sqlx::Result<()> {
+        if let Some(mut conn) = self.conn.take() {
+            queries::commit_transaction(&mut conn).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ExportReader {
+    fn drop(&mut self) {
+        // An export can be dropped midway, e.g. when its client disconnects. Its
+        // transaction can't be ended here, so the connection is closed rather than
+        // returned to the pool with the transaction still open.
+        match self.conn.take() {
+            Some(IndexerConnection::Postgres(c)) => drop(c.detach()),
+            Some(IndexerConnection::Sqlite(c)) => drop(c.detach()),
+            None => {}
+        }
+    }
+}
+
+/// Return a Parquet file holding every record of a table, positioned at its start.
+///
+/// Since the metadata of a Parquet file is written after its records, the file is
+/// written in full before being sent. It's written to a temporary file a page of
+/// records at a time, each page being a row group of its own, so that only one page
+/// is held in memory.
+pub(crate) async fn parquet_export(
+    mut reader: ExportReader,
+    table: &ExportTable,
+) -> ApiResult<File> {
+    let mut file = tempfile::tempfile()?;
+    let mut writer = table.parquet_writer(file.try_clone()?)?;
+
+    let mut after = None;
+    loop {
+        let records = reader.read_page(table, after.as_deref()).await?;
+        let is_last = records.len() < defaults::EXPORT_BATCH_SIZE;
+        after = records.last().map(|r| table.keys_of(r));
+        let page = records
+            .into_iter()
+            .map(|r| table.decode(r))
+            .collect::<Vec<Vec<ExportValue>>>();
+        table.write_parquet_page(&mut writer, &page)?;
+        if is_last {
+            break;
+        }
+    }
+    writer.close()?;
+    reader.finish().await?;
+
+    file.seek(SeekFrom::Start(0))?;
+    Ok(file)
+}
+
+/// Position of a CSV or JSON Lines export streamed a page of records at a time.
+pub(crate) struct ExportCursor {
+    /// Reader of the records.
+    reader: ExportReader,
+
+    /// Tables that remain to be exported, the first being the one being exported.
+    tables: VecDeque<ExportTable>,
+
+    /// Keys of the last record exported from the first table, if any.
+    after: Option<Vec<String>>,
+
+    /// File format of the export.
+    format: ExportFormat,
+
+    /// Whether records are tagged with the name of their entity.
+    tagged: bool,
+}
+
+impl ExportCursor {
+    /// Create a cursor positioned at the start of an export of the given tables.
+    pub fn new(
+        reader: ExportReader,
+        tables: Vec<ExportTable>,
+        format: ExportFormat,
+        tagged: bool,
+    ) -> Self {
+        Self {
+            reader,
+            tables: tables.into(),
+            after: None,
+            format,
+            tagged,
+        }
+    }
+
+    /// Return the next chunk of the export, along with the cursor positioned after
+    /// it, or `None` once every table has been exported.
+    pub async fn next_chunk(mut self) -> Option<(sqlx::Result<String>, Self)> {
+        loop {
+            let Some(table) = self.tables.front().cloned() else {
+                return match self.reader.finish().await {
+                    Ok(()) => None,
+                    Err(e) => Some((Err(e), self)),
+                };
+            };
+
+            let mut chunk = String::new();
+            if self.after.is_none() && self.format == ExportFormat::Csv {
+                chunk.push_str(&table.csv_header());
+            }
+
+            let records = match self.reader.read_page(&table, self.after.as_deref()).await
+            {
+                Ok(records) => records,
+                Err(e) => {
+                    // The stream ends after the error is sent.
+                    self.tables.clear();
+                    return Some((Err(e), self));
+                }
+            };
+
+            if records.len() < defaults::EXPORT_BATCH_SIZE {
+                self.tables.pop_front();
+                self.after = None;
+            } else {
+                self.after = records.last().map(|r| table.keys_of(r));
+            }
+
+            for record in records {
+                let values = table.decode(record);
+                let line = match self.format {
+                    ExportFormat::Csv => table.csv_record(&values),
+                    _ => table.json_record(&values, self.tagged),
+                };
+                chunk.push_str(&line);
+            }
+
+            if !chunk.is_empty() {
+                return Some((Ok(chunk), self));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bech32::FromBase32;
+
+    fn column(coltype: ColumnType, item_coltype: Option<ColumnType>) -> ExportColumn {
+        ExportColumn {
+            name: "column".to_string(),
+            kind: ExportKind::from_coltype(coltype),
+            item_kind: item_coltype.map(ExportKind::from_coltype),
+            coltype,
+        }
+    }
+
+    #[test]
+    fn test_export_columns_decode_values_readably() {
+        let decode =
+            |coltype, value: &str| column(coltype, None).decode(Some(value.into()));
+
+        assert_eq!(
+            column(ColumnType::Int8, None).decode(None),
+            ExportValue::Null
+        );
+        assert_eq!(decode(ColumnType::Boolean, "t"), ExportValue::Boolean(true));
+        assert_eq!(
+            decode(ColumnType::Boolean, "0"),
+            ExportValue::Boolean(false)
+        );
+        assert_eq!(decode(ColumnType::Int8, "-5"), ExportValue::Integer(-5));
+        assert_eq!(
+            decode(ColumnType::UInt8, "18446744073709551615"),
+            ExportValue::Unsigned(u64::MAX)
+        );
+        assert_eq!(
+            decode(
+                ColumnType::UInt16,
+                "340282366920938463463374607431768211455"
+            ),
+            ExportValue::Text("340282366920938463463374607431768211455".to_string())
+        );
+        assert_eq!(
+            decode(ColumnType::Bytes32, "00ff"),
+            ExportValue::Text("0x00ff".to_string())
+        );
+        assert_eq!(
+            decode(ColumnType::Tai64Timestamp, "4000000064c8f4a0"),
+            ExportValue::Integer(0x64c8f4a0 - 10)
+        );
+        assert_eq!(
+            decode(ColumnType::Json, r#"{"a":1}"#),
+            ExportValue::Json(serde_json::json!({ "a": 1 }))
+        );
+        assert_eq!(
+            decode(ColumnType::Charfield, "hello"),
+            ExportValue::Text("hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_export_columns_decode_addresses_as_bech32() {
+        let hex = "ab".repeat(32);
+        for coltype in [
+            ColumnType::Address,
+            ColumnType::ContractId,
+            ColumnType::Identity,
+        ] {
+            let ExportValue::Text(address) =
+                column(coltype, None).decode(Some(hex.clone()))
+            else {
+                panic!("Expected a text value.");
+            };
+
+            let (hrp, data, variant) = bech32::decode(&address).unwrap();
+            assert_eq!(hrp, BECH32_HRP);
+            assert_eq!(variant, Variant::Bech32m);
+            assert_eq!(Vec::<u8>::from_base32(&data).unwrap(), vec![0xab; 32]);
+        }
+
+        // Values that aren't hex are still exported, as they're stored.
+        assert_eq!(
+            column(ColumnType::Identity, None).decode(Some("xyz".to_string())),
+            ExportValue::Text("0xxyz".to_string())
+        );
+    }
+
+    #[test]
+    fn test_export_columns_decode_list_items() {
+        let hashes = column(ColumnType::Array, Some(ColumnType::Bytes32))
+            .decode(Some(r#"["00ff", null]"#.to_string()));
+        assert_eq!(
+            hashes,
+            ExportValue::Json(serde_json::json!(["0x00ff", null]))
+        );
+
+        let numbers = column(ColumnType::Array, Some(ColumnType::UInt8))
+            .decode(Some("[1, 2]".to_string()));
+        assert_eq!(numbers, ExportValue::Json(serde_json::json!([1, 2])));
+
+        let addresses = column(ColumnType::Array, Some(ColumnType::Address))
+            .decode(Some(format!(r#"["{}"]"#, "00".repeat(32))));
+        let ExportValue::Json(Value::Array(items)) = addresses else {
+            panic!("Expected a list.");
+        };
+        assert!(items[0].as_str().unwrap().starts_with("fuel1"));
+    }
+
+    #[test]
+    fn test_csv_lines_quote_fields_that_need_it() {
+        let line = |fields: &[Option<&str>]| {
+            csv_line(fields.iter().map(|f| f.map(|f| f.to_string())))
+        };
+
+        assert_eq!(line(&[Some("a"), None, Some("1")]), "a,,1\n");
+        assert_eq!(line(&[Some("a,b"), Some("c")]), "\"a,b\",c\n");
+        assert_eq!(
+            line(&[Some("say \"hi\""), Some("x\ny")]),
+            "\"say \"\"hi\"\"\",\"x\ny\"\n"
+        );
+        assert_eq!(line(&[]), "\n");
+    }
+}
@@ -3,6 +3,7 @@
 pub mod api;
 pub mod cli;
 pub(crate) mod commands;
+mod export;
 pub(crate) mod middleware;
 pub(crate) mod models;
 mod uses;
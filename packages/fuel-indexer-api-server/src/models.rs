@@ -12,6 +12,76 @@ pub struct VerifySignatureRequest {
     pub message: String,
 }
 
+/// Parameters of a request for an indexer's logs.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct LogsRequest {
+    /// Least severe level of the lines to return (e.g., `warn` returns `error` and
+    /// `warn` lines). All levels are returned if not specified.
+    pub level: Option<String>,
+
+    /// Only return lines logged after the line with this ID.
+    pub after: Option<i64>,
+
+    /// Maximum number of lines to return.
+    pub limit: Option<usize>,
+}
+
+/// File format to which an indexer's entities are exported.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[serde(rename = "csv")]
+    Csv,
+    #[default]
+    #[serde(rename = "jsonl")]
+    JsonLines,
+    #[serde(rename = "parquet")]
+    Parquet,
+}
+
+impl ExportFormat {
+    /// Content type of the response body of an export in this format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::JsonLines => "application/x-ndjson",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+/// Parameters of a request to export an indexer's entities.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ExportRequest {
+    /// Name of the entity to export. Every entity is exported if not specified,
+    /// which is only supported for JSON Lines exports.
+    pub entity: Option<String>,
+
+    /// File format of the export.
+    #[serde(default)]
+    pub format: ExportFormat,
+
+    /// Filter that the exported records must match, given as JSON in the shape of
+    /// the `filter` argument of GraphQL queries.
+    pub filter: Option<String>,
+
+    /// Only export the versions of historical entities that were valid at or after
+    /// this block.
+    pub from_block: Option<u64>,
+
+    /// Only export the versions of historical entities that were valid at or
+    /// before this block.
+    pub to_block: Option<u64>,
+}
+
+/// Parameters of a request to register an indexer's assets.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RegisterIndexerRequest {
+    /// Migrate the schema of an existing indexer in place, keeping its data, rather
+    /// than replacing the indexer.
+    #[serde(default)]
+    pub migrate: bool,
+}
+
 /// GraphQL web API response.
 #[derive(Serialize)]
 pub(crate) struct QueryResponse {
@@ -1,39 +1,48 @@
 use crate::{
     api::{ApiError, ApiResult, HttpError},
-    models::{Claims, VerifySignatureRequest},
+    export::{parquet_export, ExportCursor, ExportReader, ExportTable},
+    models::{
+        Claims, ExportFormat, ExportRequest, LogsRequest, RegisterIndexerRequest,
+        VerifySignatureRequest,
+    },
 };
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
 use async_graphql_axum::GraphQLRequest;
 use async_std::sync::{Arc, RwLock};
 use axum::{
-    body::Body,
-    extract::{multipart::Multipart, Extension, Json, Path},
+    body::{boxed, Body, StreamBody},
+    extract::{multipart::Multipart, Extension, Json, Path, Query},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
 use fuel_crypto::{Message, Signature};
 use fuel_indexer_database::{
     queries,
-    types::{IndexerAsset, IndexerAssetType},
+    types::{IndexerAsset, IndexerAssetType, LOG_LEVELS},
     IndexerConnectionPool,
 };
 use fuel_indexer_graphql::dynamic::{build_dynamic_schema, execute_query};
 use fuel_indexer_lib::{
     config::{auth::AuthenticationStrategy, IndexerConfig},
     defaults,
+    graphql::GraphQLSchema,
     utils::{
         FuelClientHealthResponse, ReloadRequest, ServiceRequest, ServiceStatus,
         StopRequest,
     },
     ExecutionSource,
 };
-use fuel_indexer_schema::db::manager::SchemaManager;
+use fuel_indexer_schema::db::{
+    manager::SchemaManager, snapshot::IndexerSnapshot, IndexerSchemaDbError,
+};
+use futures_util::stream;
 use hyper::Client;
 use hyper_rustls::HttpsConnectorBuilder;
 use jsonwebtoken::{encode, EncodingKey, Header};
 use serde_json::{json, Value};
 use std::{convert::From, str::FromStr, time::Instant};
 use tokio::sync::mpsc::Sender;
+use tokio_util::io::ReaderStream;
 use tracing::error;
 
 #[cfg(feature = "metrics")]
@@ -137,9 +146,261 @@ pub(crate) async fn indexer_status(
         }
     };
 
-    let json: serde_json::Value = serde_json::to_value(indexers)?;
+    let mut statuses = Vec::with_capacity(indexers.len());
+    for indexer in indexers {
+        let last_failure = queries::last_indexer_failure(&mut conn, &indexer.id).await?;
+        let mut status = serde_json::to_value(indexer)?;
+        status["last_failure"] = serde_json::to_value(last_failure)?;
+        statuses.push(status);
+    }
+
+    Ok(Json(json!(statuses)))
+}
+
+pub(crate) async fn indexer_logs(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Query(request): Query<LogsRequest>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    // Levels are ordered from most to least severe, so a level filter returns the
+    // lines logged at that level or any more severe one.
+    let levels = match request.level {
+        Some(level) => {
+            let level = level.to_lowercase();
+            let position = LOG_LEVELS
+                .iter()
+                .position(|l| *l == level)
+                .ok_or(ApiError::Http(HttpError::BadRequest))?;
+            &LOG_LEVELS[..=position]
+        }
+        None => &LOG_LEVELS[..],
+    }
+    .iter()
+    .map(|level| level.to_string())
+    .collect::<Vec<String>>();
+
+    let limit = request
+        .limit
+        .unwrap_or(defaults::INDEXER_LOG_PAGE_SIZE)
+        .min(defaults::INDEXER_LOG_PAGE_SIZE);
+
+    let mut conn = pool.acquire().await?;
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    let logs = queries::indexer_logs(
+        &mut conn,
+        &namespace,
+        &identifier,
+        &levels,
+        request.after,
+        limit,
+    )
+    .await?;
+
+    Ok(Json(json!(logs)))
+}
+
+/// Export the records of one, or every, entity of an indexer.
+///
+/// CSV and JSON Lines exports are streamed a page of records at a time.
+pub(crate) async fn export_indexer(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Query(request): Query<ExportRequest>,
+    Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+) -> ApiResult<Response> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    // Records of different entities have different columns, so only JSON Lines
+    // exports can hold every entity.
+    if request.entity.is_none() && request.format != ExportFormat::JsonLines {
+        return Err(ApiError::Http(HttpError::BadRequest));
+    }
+
+    if config.authentication.enabled {
+        let mut conn = pool.acquire().await?;
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    let schema = schema_manager
+        .read()
+        .await
+        .load_schema(&namespace, &identifier)
+        .await
+        .map_err(|_e| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "Indexer({namespace}.{identifier}) does not exist"
+            )))
+        })?;
+    let tables = ExportTable::from_request(&schema, &request)?;
+
+    let reader = ExportReader::new(&pool).await?;
+    let body = match request.format {
+        ExportFormat::Parquet => {
+            let file = parquet_export(reader, &tables[0]).await?;
+            boxed(StreamBody::new(ReaderStream::new(
+                tokio::fs::File::from_std(file),
+            )))
+        }
+        format => {
+            let cursor =
+                ExportCursor::new(reader, tables, format, request.entity.is_none());
+            boxed(StreamBody::new(stream::unfold(
+                cursor,
+                ExportCursor::next_chunk,
+            )))
+        }
+    };
+
+    Ok(Response::builder()
+        .header(http::header::CONTENT_TYPE, request.format.content_type())
+        .body(body)?)
+}
+
+/// Take a snapshot of an indexer, returned as a JSON archive.
+pub(crate) async fn snapshot_indexer(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+) -> ApiResult<axum::Json<IndexerSnapshot>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    if queries::get_indexer_id(&mut conn, &namespace, &identifier)
+        .await
+        .is_err()
+    {
+        return Err(ApiError::Http(HttpError::NotFound(format!(
+            "Indexer({namespace}.{identifier}) does not exist"
+        ))));
+    }
+
+    let snapshot = IndexerSnapshot::take(&pool, &namespace, &identifier).await?;
+
+    Ok(Json(snapshot))
+}
+
+/// Restore an indexer from the snapshot in the request body, then start it.
+///
+/// The indexer resumes from the height at which the snapshot was taken.
+pub(crate) async fn restore_indexer(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+    Json(mut snapshot): Json<IndexerSnapshot>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    if snapshot.namespace != namespace || snapshot.identifier != identifier {
+        return Err(ApiError::Http(HttpError::BadRequest));
+    }
+
+    // The restored indexer belongs to whoever restores it.
+    if config.authentication.enabled {
+        snapshot.pubkey = Some(claims.sub().to_string());
+    }
+
+    match snapshot.restore(&pool).await {
+        Ok(()) => {}
+        Err(IndexerSchemaDbError::IndexerExists(e)) => {
+            return Err(ApiError::Http(HttpError::Conflict(format!(
+                "Indexer({e}) already exists"
+            ))));
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    tx.send(ServiceRequest::Reload(ReloadRequest {
+        namespace,
+        identifier,
+    }))
+    .await?;
+
+    Ok(Json(json!({
+        "success": "true",
+        "block_height": snapshot.checkpoint.map(|c| c.block_height),
+    })))
+}
+
+/// Plan the migration of an indexer's schema to the GraphQL schema in the request
+/// body, without applying it.
+pub(crate) async fn plan_schema_migration(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+    schema: String,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    if queries::get_indexer_id(&mut conn, &namespace, &identifier)
+        .await
+        .is_err()
+    {
+        return Err(ApiError::Http(HttpError::NotFound(format!(
+            "Indexer({namespace}.{identifier}) does not exist"
+        ))));
+    }
 
-    Ok(Json(json!(json)))
+    let migration = schema_manager
+        .read()
+        .await
+        .plan_migration(
+            &namespace,
+            &identifier,
+            &GraphQLSchema::new(schema),
+            // Only WASM can be sent over the web.
+            ExecutionSource::Wasm,
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "compatible": migration.is_compatible(),
+        "report": migration.report(),
+        "migration": migration,
+    })))
 }
 
 pub(crate) async fn remove_indexer(
@@ -184,6 +445,7 @@ pub(crate) async fn remove_indexer(
 
 pub(crate) async fn register_indexer_assets(
     Path((namespace, identifier)): Path<(String, String)>,
+    Query(request): Query<RegisterIndexerRequest>,
     Extension(tx): Extension<Sender<ServiceRequest>>,
     Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
     Extension(claims): Extension<Claims>,
@@ -205,7 +467,23 @@ pub(crate) async fn register_indexer_assets(
             .await
             .is_ok();
         if indexer_exists {
-            if !config.replace_indexer {
+            if request.migrate {
+                // The indexer and its data are kept. Its schema is migrated in place
+                // once the new schema is registered below.
+                if config.authentication.enabled {
+                    if let Err(_e) = queries::indexer_owned_by(
+                        &mut conn,
+                        &namespace,
+                        &identifier,
+                        claims.sub(),
+                    )
+                    .await
+                    {
+                        queries::revert_transaction(&mut conn).await?;
+                        return Err(ApiError::Http(HttpError::Unauthorized));
+                    }
+                }
+            } else if !config.replace_indexer {
                 error!("Indexer({namespace}.{identifier}) already exists.");
                 queries::revert_transaction(&mut conn).await?;
                 return Err(ApiError::Http(HttpError::Conflict(format!(
@@ -4,7 +4,7 @@
 
 #![deny(unused_crate_dependencies)]
 use async_graphql_parser::{
-    types::{FieldDefinition, ObjectType, TypeDefinition, TypeKind},
+    types::{ConstDirective, FieldDefinition, ObjectType, TypeDefinition, TypeKind},
     Pos, Positioned,
 };
 use async_graphql_value::Name;
@@ -14,8 +14,10 @@ use chrono::{
 };
 use fuel_indexer_lib::{
     graphql::{
-        extract_foreign_key_info, field_id, is_list_type,
-        types::{IdCol, ObjectCol},
+        constants::TEXT_SEARCH_CONFIG,
+        extract_foreign_key_info, field_id, index_directives, index_method_name,
+        is_list_type,
+        types::{FromBlockCol, IdCol, ObjectCol, SearchCol, ToBlockCol},
         JoinTableMeta, ParsedGraphQLSchema,
     },
     type_id, MAX_ARRAY_LENGTH,
@@ -26,13 +28,14 @@ use std::{
     collections::BTreeMap,
     fmt,
     fmt::Write,
+    str::FromStr,
     string::ToString,
     time::{SystemTime, UNIX_EPOCH},
 };
 use strum::{AsRefStr, EnumString};
 
 // SQL index method.
-#[derive(Debug, EnumString, AsRefStr, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr, Default)]
 pub enum IndexMethod {
     /// SQL BTree index.
     #[default]
@@ -42,6 +45,28 @@ pub enum IndexMethod {
     /// SQL Hash index.
     #[strum(serialize = "hash")]
     Hash,
+
+    /// SQL generalized inverted index, for array and JSON columns.
+    #[strum(serialize = "gin")]
+    Gin,
+
+    /// SQL block range index, for columns whose values follow the order in which
+    /// rows are inserted (e.g., block heights and timestamps).
+    #[strum(serialize = "brin")]
+    Brin,
+}
+
+impl IndexMethod {
+    /// Return the index method given by the named argument of a directive, or the
+    /// default method if there is none.
+    fn from_directive(d: &ConstDirective, arg: &str) -> Self {
+        d.arguments
+            .iter()
+            .find(|(name, _)| name.node.as_str() == arg)
+            .and_then(|(_, value)| index_method_name(&value.node))
+            .and_then(|method| IndexMethod::from_str(&method).ok())
+            .unwrap_or_default()
+    }
 }
 
 /// SQL database types used by indexers.
@@ -309,6 +334,9 @@ pub struct Column {
     ///
     /// Only if this is a `ColumnType::Array`
     pub array_coltype: Option<ColumnType>,
+
+    /// The type of database this column is created in.
+    pub db_type: DbType,
 }
 
 impl SqlNamed for Column {
@@ -362,8 +390,38 @@ impl Column {
         }
     }
 
-    /// Derive the respective PostgreSQL field type for a given `Columns`
+    /// Derive the respective SQL field type for a given `Column`.
     fn sql_type(&self) -> String {
+        match self.db_type {
+            DbType::Postgres => self.postgres_type(),
+            DbType::Sqlite => self.sqlite_type(),
+        }
+    }
+
+    /// Derive the respective SQLite field type for a given `Column`.
+    fn sqlite_type(&self) -> String {
+        // SQLite integers are signed 64-bit values, so anything that can exceed
+        // `i64::MAX` (e.g., `ID`, `UInt8`, `UInt16`) is stored as text. `FtColumn`
+        // quotes these values when writing to SQLite. Text is compared as text, so
+        // queries cast these columns to numbers to order them. Arrays are stored as
+        // JSON text, since SQLite has no array types.
+        match self.coltype {
+            ColumnType::ID => "text primary key".to_string(),
+            ColumnType::BlockHeight
+            | ColumnType::Boolean
+            | ColumnType::Int1
+            | ColumnType::Int4
+            | ColumnType::Int8
+            | ColumnType::Timestamp
+            | ColumnType::UInt1
+            | ColumnType::UInt4 => "integer".to_string(),
+            ColumnType::Object => "blob".to_string(),
+            _ => "text".to_string(),
+        }
+    }
+
+    /// Derive the respective PostgreSQL field type for a given `Column`.
+    fn postgres_type(&self) -> String {
         // Here we're essentially matching `ColumnType`s to PostgreSQL field
         // types. Note that we're using `numeric` field types for integer-like
         // fields due to the ability to specify custom scale and precision. Some
@@ -632,17 +690,29 @@ pub enum DbType {
     /// PostgreSQL database backend.
     #[default]
     Postgres,
+
+    /// SQLite database backend.
+    Sqlite,
 }
 
 impl DbType {
     /// Return the fully qualified table name for a given database type, namespace, and table name.
+    ///
+    /// SQLite has no schemas, so an indexer's tables are prefixed with its namespace instead.
     pub fn table_name(&self, namespace: &str, table_name: &str) -> String {
         match self {
             DbType::Postgres => format!("{namespace}.{table_name}"),
+            DbType::Sqlite => format!("{namespace}__{table_name}"),
         }
     }
 }
 
+/// Return the name of the table that keeps every version of the entities of a
+/// historical table.
+pub fn history_table_name(table_name: &str) -> String {
+    format!("{table_name}_history")
+}
+
 /// SQL database index for a given column.
 #[derive(Debug, Default)]
 pub struct SqlIndex {
@@ -661,14 +731,38 @@ pub struct SqlIndex {
     /// Whether this index is unique.
     pub unique: bool,
 
-    /// Name of column index is applied to.
-    pub column_name: String,
+    /// Columns index is applied to, in order.
+    pub columns: Vec<Column>,
 }
 
 impl SqlNamed for SqlIndex {
     /// Return the SQL name of the index.
     fn sql_name(&self) -> String {
-        format!("{}_{}_idx", &self.table_name, &self.column_name)
+        format!("{}_{}_idx", &self.table_name, self.column_names().join("_"))
+    }
+}
+
+impl SqlIndex {
+    /// Names of the columns index is applied to, in order.
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    /// Return the expressions that are indexed, one per column.
+    fn column_exprs(&self) -> String {
+        self.columns
+            .iter()
+            .map(|c| match (&self.db_type, self.method, c.coltype) {
+                // Postgres has no GIN operator class for `json`, only for `jsonb`.
+                (
+                    DbType::Postgres,
+                    IndexMethod::Gin,
+                    ColumnType::Json | ColumnType::Virtual,
+                ) => format!("({}::jsonb)", c.name),
+                _ => c.name.clone(),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
     }
 }
 
@@ -689,7 +783,17 @@ impl SqlFragment for SqlIndex {
                     self.namespace,
                     self.table_name,
                     self.method.as_ref(),
-                    self.column_name
+                    self.column_exprs()
+                );
+            }
+            // SQLite only has B-tree indexes, so the index method is ignored.
+            DbType::Sqlite => {
+                let _ = write!(
+                    frag,
+                    "INDEX {} ON {} ({});",
+                    self.db_type.table_name(&self.namespace, &self.sql_name()),
+                    self.db_type.table_name(&self.namespace, &self.table_name),
+                    self.column_exprs()
                 );
             }
         }
@@ -743,6 +847,16 @@ impl SqlFragment for Constraint {
     }
 }
 
+impl SqlNamed for Constraint {
+    /// Return the SQL name of the constraint.
+    fn sql_name(&self) -> String {
+        match self {
+            Constraint::Index(idx) => idx.sql_name(),
+            Constraint::Fk(fk) => fk.sql_name(),
+        }
+    }
+}
+
 /// SQL database foreign key for a given column.
 #[derive(Debug, Clone, Default)]
 pub struct ForeignKey {
@@ -802,10 +916,37 @@ impl SqlFragment for ForeignKey {
                     self.on_update.as_ref()
                 )
             }
+            // SQLite can't add constraints to existing tables, so its foreign keys are
+            // created along with their table (see `ForeignKey::table_constraint`).
+            DbType::Sqlite => "".to_string(),
         }
     }
 }
 
+impl ForeignKey {
+    /// Return the foreign key as a constraint within a `CREATE TABLE` statement.
+    pub fn table_constraint(&self) -> String {
+        format!(
+            "CONSTRAINT {} FOREIGN KEY ({}) {}",
+            self.sql_name(),
+            self.column_name,
+            self.column_reference()
+        )
+    }
+
+    /// Return the foreign key as a reference on a single column definition.
+    pub fn column_reference(&self) -> String {
+        format!(
+            "REFERENCES {}({}) ON DELETE {} ON UPDATE {} DEFERRABLE INITIALLY DEFERRED",
+            self.db_type
+                .table_name(&self.namespace, &self.ref_tablename),
+            self.ref_colname,
+            self.on_delete.as_ref(),
+            self.on_update.as_ref()
+        )
+    }
+}
+
 /// Nonce used for indexer authentication.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Nonce {
@@ -828,6 +969,101 @@ impl Nonce {
     }
 }
 
+/// Status of an indexer that is persisted across restarts.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexerStatus {
+    /// Database ID of the indexer.
+    pub index_id: i64,
+
+    /// Block height at which the indexer started.
+    pub start_block: u64,
+
+    /// Manifest option from which `start_block` was resolved.
+    pub start_block_source: String,
+}
+
+/// Last block handled by an indexer, recorded by the executor in the same database
+/// transaction as the entities written while handling it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexerCheckpoint {
+    /// Height of the last handled block.
+    pub block_height: u64,
+
+    /// ID of the last handled block.
+    pub block_id: String,
+
+    /// Node pagination cursor from which to fetch the next block.
+    pub cursor: Option<String>,
+}
+
+/// Range of blocks backfilled by one of several executors of a partitionable indexer,
+/// and the last block that executor handled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IndexerBackfillPartition {
+    /// Height of the first block in the range.
+    pub start_block: u64,
+
+    /// Height of the last block in the range.
+    pub end_block: u64,
+
+    /// Height of the last handled block in the range, if any.
+    pub block_height: Option<u64>,
+
+    /// ID of the last handled block in the range, if any.
+    pub block_id: Option<String>,
+}
+
+impl IndexerBackfillPartition {
+    /// Whether every block in the range has been handled.
+    pub fn is_complete(&self) -> bool {
+        self.block_height
+            .map_or(false, |height| height >= self.end_block)
+    }
+
+    /// Height of the next block to handle in the range.
+    pub fn next_block(&self) -> u64 {
+        self.block_height
+            .map_or(self.start_block, |height| height + 1)
+    }
+}
+
+/// Last failure of an indexer to handle blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerFailure {
+    /// Description of the failure.
+    pub message: String,
+
+    /// Height of the block being handled when the indexer failed, if known.
+    pub block_height: Option<u64>,
+
+    /// Time at which the indexer failed.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Levels at which indexers log, from most to least severe.
+pub const LOG_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// A line logged by an indexer while handling blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerLog {
+    /// Database ID of the log line.
+    pub id: i64,
+
+    /// Level at which the line was logged (e.g., `info`).
+    pub level: String,
+
+    /// Height of the block being handled when the line was logged, if known.
+    pub block_height: Option<u64>,
+
+    /// The logged message.
+    pub message: String,
+
+    /// Time at which the line was logged.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Default, Debug)]
 pub enum TableType {
     /// A table that is used to join two other tables.
@@ -862,6 +1098,15 @@ pub struct Table {
     /// The type of table.
     #[allow(unused)]
     table_type: TableType,
+
+    /// The type of database this table is created in.
+    db_type: DbType,
+
+    /// Whether every version of this table's entities is kept in a history table.
+    historical: bool,
+
+    /// Names of the columns that make up this table's full-text search document.
+    search_fields: Vec<String>,
 }
 
 impl SqlNamed for Table {
@@ -882,8 +1127,280 @@ impl Table {
         &self.columns
     }
 
+    /// How this table is persisted to the database.
+    pub fn persistence(&self) -> Persistence {
+        self.persistence
+    }
+
+    /// Return the fully qualified name of the table.
+    pub fn qualified_name(&self) -> String {
+        self.db_type.table_name(
+            &format!("{}_{}", self.namespace, self.identifier),
+            &self.name,
+        )
+    }
+
+    /// Whether every version of this table's entities is kept in a history table.
+    pub fn is_historical(&self) -> bool {
+        self.historical
+    }
+
+    /// Names of the columns that make up this table's full-text search document.
+    pub fn search_fields(&self) -> &Vec<String> {
+        &self.search_fields
+    }
+
+    /// Return the definition of the generated column holding this table's full-text
+    /// search document, if it has one.
+    ///
+    /// Only Postgres has a text search type. SQLite matches the search fields directly.
+    fn search_column(&self) -> Option<String> {
+        if self.search_fields.is_empty() || self.db_type != DbType::Postgres {
+            return None;
+        }
+
+        let document = self
+            .search_fields
+            .iter()
+            .map(|f| format!("coalesce({f}::text, '')"))
+            .collect::<Vec<String>>()
+            .join(" || ' ' || ");
+
+        Some(format!(
+            "{} tsvector GENERATED ALWAYS AS (to_tsvector('{TEXT_SEARCH_CONFIG}', {document})) STORED",
+            SearchCol::to_lowercase_str()
+        ))
+    }
+
+    /// Return the SQL statement that adds the full-text search column to this table
+    /// once it exists, or an empty statement if the table has none.
+    pub fn add_search_column(&self) -> String {
+        match self.search_column() {
+            Some(definition) => format!(
+                "ALTER TABLE {} ADD COLUMN {definition};",
+                self.qualified_name()
+            ),
+            None => "".to_string(),
+        }
+    }
+
+    /// Return the SQL statement that adds the full-text search column to this table's
+    /// history table once it exists, or an empty statement if the table has none.
+    pub fn add_history_search_column(&self) -> String {
+        match self.search_column() {
+            Some(definition) => format!(
+                "ALTER TABLE {} ADD COLUMN {definition};",
+                self.history_qualified_name()
+            ),
+            None => "".to_string(),
+        }
+    }
+
+    /// Return the condition under which the value of a column is below a cutoff.
+    ///
+    /// Large integers are stored as text in SQLite, so they're compared as numbers
+    /// explicitly.
+    fn is_below(&self, column: &str, cutoff: i64) -> String {
+        match self.db_type {
+            DbType::Postgres => format!("{column} < {cutoff}"),
+            DbType::Sqlite => format!("CAST({column} AS NUMERIC) < {cutoff}"),
+        }
+    }
+
+    /// Return the SQL statement that deletes a batch of at most `limit` rows of this
+    /// table whose `field` is below `cutoff`.
+    ///
+    /// Rows that are still referenced through any of the given foreign keys are kept,
+    /// so that deleting them never violates those constraints.
+    pub fn prune(
+        &self,
+        field: &str,
+        cutoff: i64,
+        references: &[&ForeignKey],
+        limit: usize,
+    ) -> String {
+        let table = self.qualified_name();
+        let unreferenced = references
+            .iter()
+            .map(|fk| {
+                format!(
+                    " AND NOT EXISTS (SELECT 1 FROM {} AS r WHERE r.{} = p.{})",
+                    self.db_type.table_name(&fk.namespace, &fk.table_name),
+                    fk.column_name,
+                    fk.ref_colname
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "DELETE FROM {table} WHERE id IN (SELECT p.id FROM {table} AS p WHERE {}{unreferenced} LIMIT {limit});",
+            self.is_below(&format!("p.{field}"), cutoff),
+        )
+    }
+
+    /// Return the SQL statement that deletes a batch of the versions in this table's
+    /// history table whose `field` is below `cutoff`.
+    ///
+    /// Versions are deleted by entity, so a batch holds every expired version of at
+    /// most `limit` entities.
+    pub fn prune_history(&self, field: &str, cutoff: i64, limit: usize) -> String {
+        let table = self.history_qualified_name();
+        format!(
+            "DELETE FROM {table} WHERE {} AND id IN (SELECT h.id FROM {table} AS h WHERE {} LIMIT {limit});",
+            self.is_below(field, cutoff),
+            self.is_below(&format!("h.{field}"), cutoff),
+        )
+    }
+
+    /// Return the fully qualified name of this table's history table.
+    pub fn history_qualified_name(&self) -> String {
+        history_table_name(&self.qualified_name())
+    }
+
+    /// Return the columns of this table's history table.
+    ///
+    /// Each version of an entity is a row of its own, valid from `from_block` up to, but
+    /// excluding, `to_block`. The current version has no `to_block`. Since versions share
+    /// their entity's `id`, the history table has no unique or foreign key constraints.
+    pub fn history_columns(&self) -> Vec<Column> {
+        let mut columns = self
+            .columns
+            .iter()
+            .map(|c| Column {
+                // Same SQL type as `ID`, without it being a primary key.
+                coltype: if c.coltype == ColumnType::ID {
+                    ColumnType::ForeignKey
+                } else {
+                    c.coltype
+                },
+                unique: false,
+                ..c.clone()
+            })
+            .collect::<Vec<Column>>();
+
+        columns.push(Column {
+            name: FromBlockCol::to_lowercase_string(),
+            coltype: ColumnType::Int8,
+            position: columns.len() as i32,
+            nullable: false,
+            persistence: Persistence::Scalar,
+            db_type: self.db_type.clone(),
+            ..Column::default()
+        });
+
+        columns.push(Column {
+            name: ToBlockCol::to_lowercase_string(),
+            coltype: ColumnType::Int8,
+            position: columns.len() as i32,
+            nullable: true,
+            persistence: Persistence::Scalar,
+            db_type: self.db_type.clone(),
+            ..Column::default()
+        });
+
+        columns
+    }
+
+    /// Return the SQL create statement for this table's history table, or an empty
+    /// statement if the table isn't historical.
+    pub fn create_history(&self) -> String {
+        if !self.historical || self.persistence != Persistence::Scalar {
+            return "".to_string();
+        }
+
+        let mut cols = self
+            .history_columns()
+            .iter()
+            .map(|c| c.create())
+            .collect::<Vec<String>>();
+        cols.extend(self.search_column());
+        cols.push(format!(
+            "PRIMARY KEY ({}, {})",
+            IdCol::to_lowercase_str(),
+            FromBlockCol::to_lowercase_str()
+        ));
+
+        format!(
+            "CREATE TABLE {} (\n{}\n);",
+            self.history_qualified_name(),
+            cols.join(",\n")
+        )
+    }
+
+    /// Return the SQL statement that adds a column to this table's history table once
+    /// it exists.
+    pub fn add_history_column(&self, column: &Column) -> String {
+        format!(
+            "ALTER TABLE {} ADD COLUMN {};",
+            self.history_qualified_name(),
+            Column {
+                unique: false,
+                ..column.clone()
+            }
+            .create()
+        )
+    }
+
+    /// Return the SQL statement that adds a column to this table once it exists.
+    pub fn add_column(&self, column: &Column) -> String {
+        let definition = match self.db_type {
+            DbType::Postgres => column.create(),
+            // SQLite can't add a unique column to an existing table, so uniqueness is
+            // left to the column's unique index.
+            DbType::Sqlite => Column {
+                unique: false,
+                ..column.clone()
+            }
+            .create(),
+        };
+        let mut frag = format!(
+            "ALTER TABLE {} ADD COLUMN {}",
+            self.qualified_name(),
+            definition
+        );
+
+        // SQLite can't add constraints to an existing table either, but the new column
+        // can carry its own reference.
+        if self.db_type == DbType::Sqlite {
+            let fk = self.constraints.iter().find_map(|c| match c {
+                Constraint::Fk(fk) if fk.column_name == column.name => Some(fk),
+                _ => None,
+            });
+            if let Some(fk) = fk {
+                let _ = write!(frag, " {}", fk.column_reference());
+            }
+        }
+
+        frag.push(';');
+        frag
+    }
+
+    /// Return the SQL statement that removes the `NOT NULL` constraint from a column
+    /// of this table.
+    pub fn drop_not_null(&self, column: &Column) -> String {
+        format!(
+            "ALTER TABLE {} ALTER COLUMN {} DROP NOT NULL;",
+            self.qualified_name(),
+            column.name
+        )
+    }
+
+    /// Return the SQL statement that removes the `NOT NULL` constraint from a column
+    /// of this table's history table.
+    pub fn drop_history_not_null(&self, column: &Column) -> String {
+        format!(
+            "ALTER TABLE {} ALTER COLUMN {} DROP NOT NULL;",
+            self.history_qualified_name(),
+            column.name
+        )
+    }
+
     /// Create a new `Table` from a given `TypeDefinition`.
-    pub fn from_typedef(typ: &TypeDefinition, parsed: &ParsedGraphQLSchema) -> Self {
+    pub fn from_typedef(
+        typ: &TypeDefinition,
+        parsed: &ParsedGraphQLSchema,
+        db_type: &DbType,
+    ) -> Self {
         let ty_id = type_id(&parsed.fully_qualified_namespace(), &typ.name.to_string());
         match &typ.kind {
             TypeKind::Object(o) => {
@@ -897,8 +1414,9 @@ impl Table {
                     .fields
                     .iter()
                     .enumerate()
-                    .map(|(i, f)| {
-                        Column::from_field_def(
+                    .map(|(i, f)| Column {
+                        db_type: db_type.clone(),
+                        ..Column::from_field_def(
                             &f.node,
                             parsed,
                             ty_id,
@@ -908,15 +1426,40 @@ impl Table {
                     })
                     .collect::<Vec<Column>>();
 
-                let constraints = o
+                let table_name = typ.name.to_string().to_lowercase();
+                let index = |columns: Vec<Column>, method: IndexMethod, unique: bool| {
+                    Constraint::Index(SqlIndex {
+                        db_type: db_type.clone(),
+                        table_name: table_name.clone(),
+                        namespace: parsed.fully_qualified_namespace(),
+                        method,
+                        unique,
+                        columns,
+                    })
+                };
+
+                let mut constraints = o
                     .fields
                     .iter()
-                    .filter_map(|f| {
+                    .zip(columns.iter())
+                    .flat_map(|(f, column)| {
+                        let mut constraints = Vec::new();
+
+                        let indexed = f
+                            .node
+                            .directives
+                            .iter()
+                            .find(|d| d.node.name.to_string() == "indexed")
+                            .map(|d| IndexMethod::from_directive(&d.node, "type"));
 
-                        // Can't create constraints on array fields. We should have already validated the 
-                        // GraphQL schema to ensure this isn't possible, but this check doesn't hurt.
+                        // Array fields can only be indexed. We should have already validated the
+                        // GraphQL schema to ensure no other constraint is used on them, but this
+                        // check doesn't hurt.
                         if is_list_type(&f.node) {
-                            return None;
+                            if let Some(method) = indexed {
+                                constraints.push(index(vec![column.clone()], method, false));
+                            }
+                            return constraints;
                         }
 
                         let has_unique = f
@@ -926,14 +1469,16 @@ impl Table {
                             .any(|d| d.node.name.to_string() == "unique");
 
                         if has_unique {
-                            return Some(Constraint::Index(SqlIndex {
-                                db_type: DbType::Postgres,
-                                table_name: typ.name.to_string().to_lowercase(),
-                                namespace: parsed.fully_qualified_namespace(),
-                                unique: true,
-                                column_name: f.node.name.to_string(),
-                                ..SqlIndex::default()
-                            }));
+                            constraints.push(index(
+                                vec![column.clone()],
+                                IndexMethod::BTree,
+                                true,
+                            ));
+                            return constraints;
+                        }
+
+                        if let Some(method) = indexed {
+                            constraints.push(index(vec![column.clone()], method, false));
                         }
 
                         let field_typ = f.node.ty.node.to_string().replace(['[', ']', '!'], "");
@@ -944,10 +1489,10 @@ impl Table {
                                     parsed.field_type_mappings(),
                                 );
 
-                            return Some(Constraint::Fk(ForeignKey {
-                                db_type: DbType::Postgres,
+                            constraints.push(Constraint::Fk(ForeignKey {
+                                db_type: db_type.clone(),
                                 namespace: parsed.fully_qualified_namespace(),
-                                table_name: typ.name.to_string().to_lowercase(),
+                                table_name: table_name.clone(),
                                 column_name: f.node.name.to_string(),
                                 ref_tablename,
                                 ref_colname,
@@ -956,10 +1501,46 @@ impl Table {
                             }));
                         }
 
-                        None
+                        constraints
                     })
                     .collect::<Vec<Constraint>>();
 
+                // Indexes on several fields are declared on the type itself. An index on
+                // a single field that's already indexed by a field directive is redundant.
+                for directive in index_directives(typ) {
+                    let index_columns = directive
+                        .fields
+                        .iter()
+                        .filter_map(|name| columns.iter().find(|c| &c.name == name))
+                        .cloned()
+                        .collect::<Vec<Column>>();
+                    let method = directive
+                        .method
+                        .and_then(|method| IndexMethod::from_str(&method).ok())
+                        .unwrap_or_default();
+                    let constraint = index(index_columns, method, directive.unique);
+
+                    if !constraints
+                        .iter()
+                        .any(|c| c.sql_name() == constraint.sql_name())
+                    {
+                        constraints.push(constraint);
+                    }
+                }
+
+                let search_fields = parsed.search_fields(&typ.name.to_string()).to_vec();
+                if !search_fields.is_empty() && *db_type == DbType::Postgres {
+                    constraints.push(index(
+                        vec![Column {
+                            name: SearchCol::to_lowercase_string(),
+                            db_type: db_type.clone(),
+                            ..Column::default()
+                        }],
+                        IndexMethod::Gin,
+                        false,
+                    ));
+                }
+
                 // `Object` columns contain the `FtColumn` bytes for each
                 // column in the object. This column shouldn't really be public
                 columns.push(Column {
@@ -971,6 +1552,7 @@ impl Table {
                     unique: false,
                     nullable: false,
                     persistence,
+                    db_type: db_type.clone(),
                     ..Column::default()
                 });
 
@@ -982,7 +1564,10 @@ impl Table {
                     columns,
                     constraints,
                     persistence,
-                    table_type: TableType::Regular
+                    table_type: TableType::Regular,
+                    db_type: db_type.clone(),
+                    historical: parsed.is_historical_typedef(&typ.name.to_string()),
+                    search_fields,
                 }
             }
             TypeKind::Union(u) => {
@@ -1038,14 +1623,18 @@ impl Table {
                     directives: vec![],
                 };
 
-                Self::from_typedef(&typdef, parsed)
+                Self::from_typedef(&typdef, parsed, db_type)
             }
             _ => unimplemented!("An EnumType TypeDefinition should not have been passed to Table::from_typedef."),
         }
     }
 
     /// Create a new `Table` from a given `JoinTableMeta`.
-    pub fn from_join_meta(item: JoinTableMeta, parsed: &ParsedGraphQLSchema) -> Self {
+    pub fn from_join_meta(
+        item: JoinTableMeta,
+        parsed: &ParsedGraphQLSchema,
+        db_type: &DbType,
+    ) -> Self {
         // Since the join table is just two pre-determined columns, with two pre-determined
         // constraints, we can just manually create it.
         let JoinTableMeta {
@@ -1068,6 +1657,7 @@ impl Table {
                 unique: false,
                 nullable: false,
                 persistence: Persistence::Scalar,
+                db_type: db_type.clone(),
                 ..Column::default()
             },
             Column {
@@ -1079,13 +1669,14 @@ impl Table {
                 unique: false,
                 nullable: false,
                 persistence: Persistence::Scalar,
+                db_type: db_type.clone(),
                 ..Column::default()
             },
         ];
 
         let constraints = vec![
             Constraint::Fk(ForeignKey {
-                db_type: DbType::Postgres,
+                db_type: db_type.clone(),
                 namespace: parsed.fully_qualified_namespace(),
                 table_name: table_name.clone(),
                 column_name: format!("{local_table_name}_{column_name}"),
@@ -1095,7 +1686,7 @@ impl Table {
                 ..ForeignKey::default()
             }),
             Constraint::Fk(ForeignKey {
-                db_type: DbType::Postgres,
+                db_type: db_type.clone(),
                 namespace: parsed.fully_qualified_namespace(),
                 table_name: table_name.clone(),
                 column_name: format!("{ref_table_name}_{ref_column_name}"),
@@ -1114,6 +1705,9 @@ impl Table {
             constraints,
             persistence: Persistence::Scalar,
             table_type: TableType::Join,
+            db_type: db_type.clone(),
+            historical: false,
+            search_fields: Vec::new(),
         }
     }
 }
@@ -1123,16 +1717,22 @@ impl SqlFragment for Table {
     fn create(&self) -> String {
         match self.persistence {
             Persistence::Scalar => {
-                let mut s = format!(
-                    "CREATE TABLE {}_{}.{} (\n",
-                    self.namespace, self.identifier, self.name
-                );
-                let cols = self
+                let mut s = format!("CREATE TABLE {} (\n", self.qualified_name());
+                let mut cols = self
                     .columns
                     .iter()
                     .map(|c| c.create())
-                    .collect::<Vec<String>>()
-                    .join(",\n");
+                    .collect::<Vec<String>>();
+                cols.extend(self.search_column());
+
+                if self.db_type == DbType::Sqlite {
+                    cols.extend(self.constraints.iter().filter_map(|c| match c {
+                        Constraint::Fk(fk) => Some(fk.table_constraint()),
+                        _ => None,
+                    }));
+                }
+
+                let cols = cols.join(",\n");
                 s.push_str(&cols);
                 // Remove last ',\n' from last column to avoid syntax error
                 let chars = s.chars();
@@ -1231,11 +1831,179 @@ type Person {
         )
         .unwrap();
 
-        let table = Table::from_typedef(&typdef, &schema);
+        let table = Table::from_typedef(&typdef, &schema, &DbType::Postgres);
         assert_eq!(table.columns().len(), 4);
         assert_eq!(table.constraints().len(), 1);
     }
 
+    #[test]
+    fn test_can_create_history_table_for_historical_typedef() {
+        let schema = r#"
+type Account @historical {
+    id: ID!
+    label: Charfield! @unique
+}"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+        let typdef = parsed.type_defs().get("Account").unwrap();
+
+        let table = Table::from_typedef(typdef, &parsed, &DbType::Postgres);
+        assert!(table.is_historical());
+        assert_eq!(
+            table.create_history(),
+            "CREATE TABLE test_test.account_history (
+id numeric(20, 0) not null,
+label varchar(255) not null,
+object bytea not null,
+from_block bigint not null,
+to_block bigint,
+PRIMARY KEY (id, from_block)
+);"
+        );
+
+        let table = Table::from_typedef(typdef, &parsed, &DbType::Sqlite);
+        assert!(table
+            .create_history()
+            .starts_with("CREATE TABLE test_test__account_history (\nid text not null,"));
+    }
+
+    #[test]
+    fn test_can_create_search_column_and_index_for_search_fields() {
+        let schema = r#"
+type Token {
+    id: ID!
+    name: Charfield! @search
+    metadata: Json @search
+    supply: UInt8!
+}"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+        let typdef = parsed.type_defs().get("Token").unwrap();
+
+        let table = Table::from_typedef(typdef, &parsed, &DbType::Postgres);
+        assert_eq!(
+            table.search_fields(),
+            &vec!["name".to_string(), "metadata".to_string()]
+        );
+        assert!(table.create().ends_with(
+            "object bytea not null,
+search_vector tsvector GENERATED ALWAYS AS (to_tsvector('english', coalesce(name::text, '') || ' ' || coalesce(metadata::text, ''))) STORED
+);"
+        ));
+        assert_eq!(
+            table.constraints().last().unwrap().create(),
+            "CREATE INDEX token_search_vector_idx ON test_test.token USING gin (search_vector);"
+        );
+
+        let table = Table::from_typedef(typdef, &parsed, &DbType::Sqlite);
+        assert!(!table.create().contains("search_vector"));
+        assert!(table.constraints().is_empty());
+        assert_eq!(table.add_search_column(), "");
+    }
+
+    #[test]
+    fn test_can_prune_expired_rows_that_are_not_referenced() {
+        let schema = r#"
+type Account @historical @retain(blocks: 1000, field: "block_height") {
+    id: ID!
+    block_height: UInt8!
+}
+
+type Transfer {
+    id: ID!
+    account: Account!
+}"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+        let account = parsed.type_defs().get("Account").unwrap();
+        let transfer = parsed.type_defs().get("Transfer").unwrap();
+
+        let transfers = Table::from_typedef(transfer, &parsed, &DbType::Postgres);
+        let references = transfers
+            .constraints()
+            .iter()
+            .filter_map(|c| match c {
+                Constraint::Fk(fk) if fk.ref_tablename == "account" => Some(fk),
+                _ => None,
+            })
+            .collect::<Vec<&ForeignKey>>();
+
+        let table = Table::from_typedef(account, &parsed, &DbType::Postgres);
+        assert_eq!(
+            table.prune("block_height", 4000, &references, 500),
+            "DELETE FROM test_test.account WHERE id IN (SELECT p.id FROM test_test.account AS p WHERE p.block_height < 4000 AND NOT EXISTS (SELECT 1 FROM test_test.transfer AS r WHERE r.account = p.id) LIMIT 500);"
+        );
+        assert_eq!(
+            table.prune_history("block_height", 4000, 500),
+            "DELETE FROM test_test.account_history WHERE block_height < 4000 AND id IN (SELECT h.id FROM test_test.account_history AS h WHERE h.block_height < 4000 LIMIT 500);"
+        );
+
+        let table = Table::from_typedef(account, &parsed, &DbType::Sqlite);
+        assert_eq!(
+            table.prune("block_height", 4000, &[], 500),
+            "DELETE FROM test_test__account WHERE id IN (SELECT p.id FROM test_test__account AS p WHERE CAST(p.block_height AS NUMERIC) < 4000 LIMIT 500);"
+        );
+    }
+
+    #[test]
+    fn test_can_create_composite_indexes_and_index_methods() {
+        let schema = r#"
+type Transfer @index(fields: ["asset", "block_height"], unique: true) @index(fields: ["data"], method: GIN) {
+    id: ID!
+    asset: AssetId!
+    block_height: UInt8! @indexed(type: Brin)
+    data: Json!
+}"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+        let typdef = parsed.type_defs().get("Transfer").unwrap();
+
+        let table = Table::from_typedef(typdef, &parsed, &DbType::Postgres);
+        let stmnts = table
+            .constraints()
+            .iter()
+            .map(|c| c.create())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            stmnts,
+            vec![
+                "CREATE INDEX transfer_block_height_idx ON test_test.transfer USING brin (block_height);",
+                "CREATE UNIQUE INDEX transfer_asset_block_height_idx ON test_test.transfer USING btree (asset, block_height);",
+                "CREATE INDEX transfer_data_idx ON test_test.transfer USING gin ((data::jsonb));",
+            ]
+        );
+
+        let table = Table::from_typedef(typdef, &parsed, &DbType::Sqlite);
+        assert_eq!(
+            table.constraints()[1].create(),
+            "CREATE UNIQUE INDEX test_test__transfer_asset_block_height_idx ON test_test__transfer (asset, block_height);"
+        );
+    }
+
     #[test]
     fn test_can_create_well_formed_column_from_field_defintion() {
         let schema = r#"
@@ -0,0 +1,11 @@
+create table if not exists index_status (
+   index_id bigserial primary key,
+   start_block bigint not null,
+   start_block_source varchar(255) not null,
+   resolved_at timestamp default now(),
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+	        deferrable initially deferred
+);
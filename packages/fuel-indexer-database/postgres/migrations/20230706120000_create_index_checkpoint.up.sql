@@ -0,0 +1,12 @@
+create table if not exists index_checkpoint (
+   index_id bigserial primary key,
+   block_height bigint not null,
+   block_id varchar(64) not null,
+   cursor varchar(255),
+   updated_at timestamp default now(),
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+	        deferrable initially deferred
+);
@@ -0,0 +1,14 @@
+create table if not exists index_backfill_partition (
+   index_id bigint not null,
+   start_block bigint not null,
+   end_block bigint not null,
+   block_height bigint,
+   block_id varchar(64),
+   updated_at timestamp default now(),
+   primary key (index_id, start_block),
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+	        deferrable initially deferred
+);
@@ -0,0 +1,15 @@
+create table if not exists index_log (
+   id bigserial primary key,
+   index_id bigint not null,
+   level varchar(5) not null,
+   block_height bigint,
+   message text not null,
+   created_at timestamp not null default now(),
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+	        deferrable initially deferred
+);
+
+create index if not exists index_log_index_id_idx on index_log (index_id, id);
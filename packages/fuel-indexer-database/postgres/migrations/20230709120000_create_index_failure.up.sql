@@ -0,0 +1,11 @@
+create table if not exists index_failure (
+   index_id bigint primary key,
+   message text not null,
+   block_height bigint,
+   created_at timestamp not null default now(),
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+	        deferrable initially deferred
+);
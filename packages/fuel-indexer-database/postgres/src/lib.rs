@@ -26,6 +26,10 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 
 const NONCE_EXPIRY: u64 = 3600; // 1 hour
 
+/// Table in an indexer's schema that backs its key/value store. GraphQL reserves type
+/// names starting with `__`, so this never collides with an entity's table.
+const KV_STORE_TABLE: &str = "__kv_store";
+
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn put_object(
     conn: &mut PoolConnection<Postgres>,
@@ -50,6 +54,96 @@ pub async fn get_object(
     Ok(row.get(0))
 }
 
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn create_kv_store(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    execute_query(
+        conn,
+        format!(
+            "CREATE TABLE IF NOT EXISTS {namespace}_{identifier}.{KV_STORE_TABLE} (
+                key bytea primary key,
+                value bytea not null
+            )"
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_kv(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+) -> sqlx::Result<Option<Vec<u8>>> {
+    let row = sqlx::query(&format!(
+        "SELECT value FROM {namespace}_{identifier}.{KV_STORE_TABLE} WHERE key = $1"
+    ))
+    .bind(key)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn kv_entries(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    Ok(sqlx::query(&format!(
+        "SELECT key, value FROM {namespace}_{identifier}.{KV_STORE_TABLE} ORDER BY key"
+    ))
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| (row.get(0), row.get(1)))
+    .collect())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_kv(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+    value: &[u8],
+) -> sqlx::Result<()> {
+    sqlx::query(&format!(
+        "INSERT INTO {namespace}_{identifier}.{KV_STORE_TABLE} (key, value) VALUES ($1, $2)
+        ON CONFLICT (key) DO UPDATE SET value = $2"
+    ))
+    .bind(key)
+    .bind(value)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn delete_kv(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+) -> sqlx::Result<()> {
+    sqlx::query(&format!(
+        "DELETE FROM {namespace}_{identifier}.{KV_STORE_TABLE} WHERE key = $1"
+    ))
+    .bind(key)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn run_migration(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<()> {
     sqlx::migrate!().run(conn).await?;
@@ -263,6 +357,35 @@ pub async fn type_id_insert(
     Ok(result.rows_affected() as usize)
 }
 
+/// Remove the type IDs, and the columns registered to them, of an indexer's schema.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn type_id_delete(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<usize> {
+    sqlx::query(
+        "DELETE FROM graph_registry_columns WHERE type_id IN
+        (SELECT id FROM graph_registry_type_ids
+            WHERE schema_name = $1 AND schema_identifier = $2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(&mut *conn)
+    .await?;
+
+    let result = sqlx::query(
+        "DELETE FROM graph_registry_type_ids
+        WHERE schema_name = $1 AND schema_identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn schema_exists(
     conn: &mut PoolConnection<Postgres>,
@@ -347,6 +470,7 @@ pub async fn list_column_by_id(
                     persistence: Persistence::from_str(persistence.as_str())
                         .expect("Bad persistence."),
                     array_coltype: array_coltype.map(|t| ColumnType::from(t.as_str())),
+                    db_type: DbType::Postgres,
                 }
             })
             .collect::<Vec<Column>>(),
@@ -620,6 +744,32 @@ pub async fn latest_asset_for_indexer(
     })
 }
 
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn assets_for_indexer(
+    conn: &mut PoolConnection<Postgres>,
+    index_id: &i64,
+    asset_type: IndexerAssetType,
+) -> sqlx::Result<Vec<IndexerAsset>> {
+    let query = format!(
+        "SELECT * FROM index_asset_registry_{} WHERE index_id = {} ORDER BY version",
+        asset_type.as_ref(),
+        index_id
+    );
+
+    Ok(sqlx::query(&query)
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| IndexerAsset {
+            id: row.get(0),
+            index_id: row.get(1),
+            version: row.get(2),
+            digest: row.get(3),
+            bytes: row.get(4),
+        })
+        .collect())
+}
+
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn latest_assets_for_indexer(
     conn: &mut PoolConnection<Postgres>,
@@ -802,6 +952,56 @@ pub async fn remove_indexer(
     )
     .await?;
 
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_failure WHERE index_id IN
+            (SELECT id FROM index_registry
+                WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+        ),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_log WHERE index_id IN
+            (SELECT id FROM index_registry
+                WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+        ),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_backfill_partition WHERE index_id IN
+            (SELECT id FROM index_registry
+                WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+        ),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_checkpoint WHERE index_id IN
+            (SELECT id FROM index_registry
+                WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+        ),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_status WHERE index_id IN
+            (SELECT id FROM index_registry
+                WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+        ),
+    )
+    .await?;
+
     execute_query(
         conn,
         format!(
@@ -924,6 +1124,321 @@ pub async fn get_nonce(
     Ok(Nonce { uid, expiry })
 }
 
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn indexer_status(
+    conn: &mut PoolConnection<Postgres>,
+    index_id: &i64,
+) -> sqlx::Result<Option<IndexerStatus>> {
+    let row = sqlx::query(&format!(
+        "SELECT index_id, start_block, start_block_source FROM index_status WHERE index_id = {index_id}"
+    ))
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| {
+        let index_id: i64 = row.get(0);
+        let start_block: i64 = row.get(1);
+        let start_block_source: String = row.get(2);
+
+        IndexerStatus {
+            index_id,
+            start_block: start_block as u64,
+            start_block_source,
+        }
+    }))
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn set_indexer_start_block(
+    conn: &mut PoolConnection<Postgres>,
+    index_id: &i64,
+    start_block: u64,
+    start_block_source: &str,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_status (index_id, start_block, start_block_source) VALUES ($1, $2, $3)
+        ON CONFLICT (index_id) DO UPDATE SET start_block = $2, start_block_source = $3, resolved_at = now()",
+    )
+    .bind(index_id)
+    .bind(start_block as i64)
+    .bind(start_block_source)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_checkpoint(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    checkpoint: &IndexerCheckpoint,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_checkpoint (index_id, block_height, block_id, cursor)
+        SELECT id, $3, $4, $5 FROM index_registry WHERE namespace = $1 AND identifier = $2
+        ON CONFLICT (index_id) DO UPDATE SET block_height = $3, block_id = $4, cursor = $5, updated_at = now()",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(checkpoint.block_height as i64)
+    .bind(&checkpoint.block_id)
+    .bind(&checkpoint.cursor)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn last_checkpoint_for_indexer(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerCheckpoint>> {
+    let row = sqlx::query(
+        "SELECT c.block_height, c.block_id, c.cursor FROM index_checkpoint c
+        INNER JOIN index_registry r ON r.id = c.index_id
+        WHERE r.namespace = $1 AND r.identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| {
+        let block_height: i64 = row.get(0);
+        let block_id: String = row.get(1);
+        let cursor: Option<String> = row.get(2);
+
+        IndexerCheckpoint {
+            block_height: block_height as u64,
+            block_id,
+            cursor,
+        }
+    }))
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_backfill_partition(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    partition: &IndexerBackfillPartition,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_backfill_partition (index_id, start_block, end_block, block_height, block_id)
+        SELECT id, $3, $4, $5, $6 FROM index_registry WHERE namespace = $1 AND identifier = $2
+        ON CONFLICT (index_id, start_block) DO UPDATE SET end_block = $4, block_height = $5, block_id = $6, updated_at = now()",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(partition.start_block as i64)
+    .bind(partition.end_block as i64)
+    .bind(partition.block_height.map(|height| height as i64))
+    .bind(&partition.block_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn backfill_partitions_for_indexer(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerBackfillPartition>> {
+    let rows = sqlx::query(
+        "SELECT p.start_block, p.end_block, p.block_height, p.block_id FROM index_backfill_partition p
+        INNER JOIN index_registry r ON r.id = p.index_id
+        WHERE r.namespace = $1 AND r.identifier = $2
+        ORDER BY p.start_block ASC",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let start_block: i64 = row.get(0);
+            let end_block: i64 = row.get(1);
+            let block_height: Option<i64> = row.get(2);
+            let block_id: Option<String> = row.get(3);
+
+            IndexerBackfillPartition {
+                start_block: start_block as u64,
+                end_block: end_block as u64,
+                block_height: block_height.map(|height| height as u64),
+                block_id,
+            }
+        })
+        .collect())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn remove_backfill_partitions(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "DELETE FROM index_backfill_partition WHERE index_id IN
+        (SELECT id FROM index_registry WHERE namespace = $1 AND identifier = $2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Persist the given log lines, then remove all but the latest `capacity` lines
+/// logged by the indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_logs(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    logs: &[IndexerLog],
+    capacity: usize,
+) -> sqlx::Result<()> {
+    let index_id = get_indexer_id(conn, namespace, identifier).await?;
+
+    for log in logs {
+        sqlx::query(
+            "INSERT INTO index_log (index_id, level, block_height, message, created_at)
+            VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(index_id)
+        .bind(&log.level)
+        .bind(log.block_height.map(|height| height as i64))
+        .bind(&log.message)
+        .bind(log.created_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    sqlx::query(
+        "DELETE FROM index_log WHERE index_id = $1 AND id <=
+        (SELECT id FROM index_log WHERE index_id = $1 ORDER BY id DESC OFFSET $2 LIMIT 1)",
+    )
+    .bind(index_id)
+    .bind(capacity as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Return up to `limit` log lines of the indexer logged at one of the given levels,
+/// oldest first.
+///
+/// If `after` is given, these are the lines logged after the line with that ID.
+/// Otherwise, they're the latest lines logged.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn indexer_logs(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    levels: &[String],
+    after: Option<i64>,
+    limit: usize,
+) -> sqlx::Result<Vec<IndexerLog>> {
+    let query = match after {
+        Some(after) => sqlx::query(
+            "SELECT l.id, l.level, l.block_height, l.message, l.created_at FROM index_log l
+            INNER JOIN index_registry r ON r.id = l.index_id
+            WHERE r.namespace = $1 AND r.identifier = $2 AND l.level = ANY($3) AND l.id > $4
+            ORDER BY l.id ASC LIMIT $5",
+        )
+        .bind(namespace)
+        .bind(identifier)
+        .bind(levels)
+        .bind(after),
+        None => sqlx::query(
+            "SELECT * FROM (
+                SELECT l.id, l.level, l.block_height, l.message, l.created_at FROM index_log l
+                INNER JOIN index_registry r ON r.id = l.index_id
+                WHERE r.namespace = $1 AND r.identifier = $2 AND l.level = ANY($3)
+                ORDER BY l.id DESC LIMIT $4
+            ) latest ORDER BY id ASC",
+        )
+        .bind(namespace)
+        .bind(identifier)
+        .bind(levels),
+    };
+
+    let rows = query.bind(limit as i64).fetch_all(conn).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let block_height: Option<i64> = row.get(2);
+            let created_at: NaiveDateTime = row.get(4);
+
+            IndexerLog {
+                id: row.get(0),
+                level: row.get(1),
+                block_height: block_height.map(|height| height as u64),
+                message: row.get(3),
+                created_at: DateTime::<Utc>::from_utc(created_at, Utc),
+            }
+        })
+        .collect())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_indexer_failure(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    failure: &IndexerFailure,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_failure (index_id, message, block_height, created_at)
+        SELECT id, $3, $4, $5 FROM index_registry WHERE namespace = $1 AND identifier = $2
+        ON CONFLICT (index_id) DO UPDATE SET message = $3, block_height = $4, created_at = $5",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(&failure.message)
+    .bind(failure.block_height.map(|height| height as i64))
+    .bind(failure.created_at)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn last_indexer_failure(
+    conn: &mut PoolConnection<Postgres>,
+    index_id: &i64,
+) -> sqlx::Result<Option<IndexerFailure>> {
+    let row = sqlx::query(
+        "SELECT message, block_height, created_at FROM index_failure WHERE index_id = $1",
+    )
+    .bind(index_id)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| {
+        let block_height: Option<i64> = row.get(1);
+        let created_at: NaiveDateTime = row.get(2);
+
+        IndexerFailure {
+            message: row.get(0),
+            block_height: block_height.map(|height| height as u64),
+            created_at: DateTime::<Utc>::from_utc(created_at, Utc),
+        }
+    }))
+}
+
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn remove_latest_assets_for_indexer(
     conn: &mut PoolConnection<Postgres>,
@@ -0,0 +1,14 @@
+drop table if exists index_failure;
+drop table if exists index_log;
+drop table if exists index_backfill_partition;
+drop table if exists index_checkpoint;
+drop table if exists index_status;
+drop table if exists nonce;
+drop table if exists index_asset_registry_manifest;
+drop table if exists index_asset_registry_schema;
+drop table if exists index_asset_registry_wasm;
+drop table if exists index_registry;
+drop table if exists graph_registry_root_columns;
+drop table if exists graph_registry_graph_root;
+drop table if exists graph_registry_columns;
+drop table if exists graph_registry_type_ids;
@@ -0,0 +1,165 @@
+create table if not exists graph_registry_type_ids (
+    id integer primary key,
+    schema_version varchar(512) not null,
+    schema_name varchar(32) not null,
+    graphql_name varchar(32) not null,
+    table_name varchar(255) not null,
+    schema_identifier varchar(255) default 'unnamed'
+);
+
+create table if not exists graph_registry_columns (
+    id integer primary key autoincrement,
+    type_id integer not null,
+    column_position integer not null,
+    column_name varchar(255) not null,
+    column_type varchar(32) not null,
+    nullable boolean not null,
+    graphql_type varchar not null,
+    is_unique boolean not null default false,
+    persistence varchar(255) not null default 'Virtual',
+    array_coltype varchar(32),
+    constraint fk_table_name
+        foreign key(type_id)
+            references graph_registry_type_ids(id)
+);
+
+create table if not exists graph_registry_graph_root (
+    id integer primary key autoincrement,
+    version varchar not null,
+    schema_name varchar not null,
+    schema varchar not null,
+    schema_identifier varchar(255) default 'unnamed',
+    unique(version, schema_name, schema_identifier)
+);
+
+create table if not exists graph_registry_root_columns (
+    id integer primary key autoincrement,
+    root_id integer not null,
+    column_name varchar(32) not null,
+    graphql_type varchar(32) not null,
+    constraint fk_root_id
+        foreign key(root_id)
+            references graph_registry_graph_root(id)
+);
+
+create table if not exists index_registry (
+    id integer primary key autoincrement,
+    namespace varchar(32) not null,
+    identifier varchar(32) not null,
+    pubkey varchar(255),
+    created_at timestamp default '1970-01-01 00:00:00',
+    unique(namespace, identifier)
+);
+
+create table if not exists index_asset_registry_wasm (
+    id integer primary key autoincrement,
+    index_id integer,
+    version integer not null,
+    digest varchar(64) not null,
+    bytes blob not null,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists index_asset_registry_schema (
+    id integer primary key autoincrement,
+    index_id integer,
+    version integer not null,
+    digest varchar(64) not null,
+    bytes blob not null,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists index_asset_registry_manifest (
+    id integer primary key autoincrement,
+    index_id integer,
+    version integer not null,
+    digest varchar(64) not null,
+    bytes blob not null,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists nonce (
+    id integer primary key autoincrement,
+    uid varchar(64) unique not null,
+    expiry integer not null
+);
+
+create table if not exists index_status (
+    index_id integer primary key,
+    start_block integer not null,
+    start_block_source varchar(255) not null,
+    resolved_at timestamp default current_timestamp,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists index_checkpoint (
+    index_id integer primary key,
+    block_height integer not null,
+    block_id varchar(64) not null,
+    cursor varchar(255),
+    updated_at timestamp default current_timestamp,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists index_backfill_partition (
+    index_id integer not null,
+    start_block integer not null,
+    end_block integer not null,
+    block_height integer,
+    block_id varchar(64),
+    updated_at timestamp default current_timestamp,
+    primary key (index_id, start_block),
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create table if not exists index_log (
+    id integer primary key autoincrement,
+    index_id integer not null,
+    level varchar(5) not null,
+    block_height integer,
+    message text not null,
+    created_at timestamp not null default current_timestamp,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
+
+create index if not exists index_log_index_id_idx on index_log (index_id, id);
+
+create table if not exists index_failure (
+    index_id integer primary key,
+    message text not null,
+    block_height integer,
+    created_at timestamp not null default current_timestamp,
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+            deferrable initially deferred
+);
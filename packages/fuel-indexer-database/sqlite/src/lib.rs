@@ -0,0 +1,1458 @@
+#![deny(unused_crate_dependencies)]
+
+use fuel_indexer_database_types::*;
+use fuel_indexer_lib::{fully_qualified_namespace, utils::sha256_digest};
+use serde_json::Value as JsonValue;
+use sqlx::{pool::PoolConnection, sqlite::SqliteRow, Row, Sqlite};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
+
+#[cfg(feature = "metrics")]
+use fuel_indexer_macro_utils::metrics;
+
+use chrono::{DateTime, Utc};
+
+const NONCE_EXPIRY: u64 = 3600; // 1 hour
+
+/// Table that backs an indexer's key/value store. GraphQL reserves type names
+/// starting with `__`, so this never collides with an entity's table.
+const KV_STORE_TABLE: &str = "__kv_store";
+
+/// Return the name of an indexer's table.
+///
+/// SQLite has no schemas, so an indexer's tables are prefixed with its namespace.
+fn table_name(namespace: &str, identifier: &str, table: &str) -> String {
+    DbType::Sqlite.table_name(&fully_qualified_namespace(namespace, identifier), table)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn put_object(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+    bytes: Vec<u8>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+    let query = builder.build();
+    let query = query.bind(bytes);
+    let result = query.execute(conn).await?;
+    Ok(result.rows_affected() as usize)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn get_object(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+) -> sqlx::Result<Vec<u8>> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+    let query = builder.build();
+    let row = query.fetch_one(conn).await?;
+    Ok(row.get(0))
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn create_kv_store(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    execute_query(
+        conn,
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                key blob primary key,
+                value blob not null
+            )",
+            table_name(namespace, identifier, KV_STORE_TABLE)
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn get_kv(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+) -> sqlx::Result<Option<Vec<u8>>> {
+    let row = sqlx::query(&format!(
+        "SELECT value FROM {} WHERE key = ?1",
+        table_name(namespace, identifier, KV_STORE_TABLE)
+    ))
+    .bind(key)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn kv_entries(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    Ok(sqlx::query(&format!(
+        "SELECT key, value FROM {} ORDER BY key",
+        table_name(namespace, identifier, KV_STORE_TABLE)
+    ))
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| (row.get(0), row.get(1)))
+    .collect())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn put_kv(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+    value: &[u8],
+) -> sqlx::Result<()> {
+    sqlx::query(&format!(
+        "INSERT INTO {} (key, value) VALUES (?1, ?2)
+        ON CONFLICT (key) DO UPDATE SET value = ?2",
+        table_name(namespace, identifier, KV_STORE_TABLE)
+    ))
+    .bind(key)
+    .bind(value)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn delete_kv(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+) -> sqlx::Result<()> {
+    sqlx::query(&format!(
+        "DELETE FROM {} WHERE key = ?1",
+        table_name(namespace, identifier, KV_STORE_TABLE)
+    ))
+    .bind(key)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn run_migration(conn: &mut PoolConnection<Sqlite>) -> sqlx::Result<()> {
+    sqlx::migrate!().run(conn).await?;
+    Ok(())
+}
+
+/// Run a query whose rows each hold a single JSON document.
+///
+/// SQLite returns JSON as text, so each document is parsed before being returned.
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn run_query(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+) -> sqlx::Result<JsonValue> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+    let query = builder.build();
+    query
+        .fetch_all(conn)
+        .await?
+        .iter()
+        .map(|r| {
+            let json: String = r.get(0);
+            serde_json::from_str::<JsonValue>(&json)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        })
+        .collect()
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn execute_query(
+    conn: &mut PoolConnection<Sqlite>,
+    query: String,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+    let query = builder.build();
+    let result = query.execute(conn).await?;
+    Ok(result.rows_affected() as usize)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn root_columns_list_by_id(
+    conn: &mut PoolConnection<Sqlite>,
+    root_id: i64,
+) -> sqlx::Result<Vec<RootColumn>> {
+    Ok(
+        sqlx::query("SELECT * FROM graph_registry_root_columns WHERE root_id = ?1")
+            .bind(root_id)
+            .fetch_all(conn)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let id: i64 = row.get(0);
+                let root_id: i64 = row.get(1);
+                let column_name: String = row.get(2);
+                let graphql_type: String = row.get(3);
+                RootColumn {
+                    id,
+                    root_id,
+                    column_name,
+                    graphql_type,
+                }
+            })
+            .collect::<Vec<RootColumn>>(),
+    )
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn new_root_columns(
+    conn: &mut PoolConnection<Sqlite>,
+    cols: Vec<RootColumn>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(
+        "INSERT INTO graph_registry_root_columns (root_id, column_name, graphql_type)",
+    );
+
+    builder.push_values(cols.into_iter(), |mut b, new_col| {
+        b.push_bind(new_col.root_id)
+            .push_bind(new_col.column_name)
+            .push_bind(new_col.graphql_type);
+    });
+
+    let query = builder.build();
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn new_graph_root(
+    conn: &mut PoolConnection<Sqlite>,
+    root: GraphRoot,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(
+        "INSERT INTO graph_registry_graph_root (version, schema_name, schema_identifier, schema)",
+    );
+
+    builder.push_values(std::iter::once(root), |mut b, root| {
+        b.push_bind(root.version)
+            .push_bind(root.schema_name)
+            .push_bind(root.schema_identifier)
+            .push_bind(root.schema);
+    });
+
+    let query = builder.build();
+    let result = query.execute(conn).await?;
+    Ok(result.rows_affected() as usize)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn graph_root_latest(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<GraphRoot> {
+    let row = sqlx::query(
+        "SELECT * FROM graph_registry_graph_root
+        WHERE schema_name = ?1 AND schema_identifier = ?2
+        ORDER BY id DESC LIMIT 1",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    let id: i64 = row.get(0);
+    let version: String = row.get(1);
+    let schema_name: String = row.get(2);
+    let schema: String = row.get(3);
+
+    Ok(GraphRoot {
+        id,
+        version,
+        schema_name,
+        schema,
+        schema_identifier: identifier.to_string(),
+    })
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn type_id_list_by_name(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    version: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<TypeId>> {
+    Ok(sqlx::query(
+        "SELECT * FROM graph_registry_type_ids
+        WHERE schema_name = ?1
+        AND schema_version = ?2
+        AND schema_identifier = ?3",
+    )
+    .bind(namespace)
+    .bind(version)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        let id: i64 = row.get(0);
+        let version: String = row.get(1);
+        let namespace: String = row.get(2);
+        let graphql_name: String = row.get(3);
+        let table_name: String = row.get(4);
+        let identifier: String = row.get(5);
+
+        TypeId {
+            id,
+            version,
+            namespace,
+            table_name,
+            graphql_name,
+            identifier,
+        }
+    })
+    .collect::<Vec<TypeId>>())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn type_id_latest(
+    conn: &mut PoolConnection<Sqlite>,
+    schema_name: &str,
+    identifier: &str,
+) -> sqlx::Result<String> {
+    let latest = sqlx::query(
+        "SELECT schema_version FROM graph_registry_type_ids
+        WHERE schema_name = ?1
+        AND schema_identifier = ?2
+        ORDER BY id",
+    )
+    .bind(schema_name)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    let schema_version: String = latest.get(0);
+
+    Ok(schema_version)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn type_id_insert(
+    conn: &mut PoolConnection<Sqlite>,
+    type_ids: Vec<TypeId>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new("INSERT INTO graph_registry_type_ids (id, schema_version, schema_name, schema_identifier, graphql_name, table_name)");
+
+    builder.push_values(type_ids.into_iter(), |mut b, tid| {
+        b.push_bind(tid.id)
+            .push_bind(tid.version)
+            .push_bind(tid.namespace)
+            .push_bind(tid.identifier)
+            .push_bind(tid.graphql_name)
+            .push_bind(tid.table_name);
+    });
+
+    let query = builder.build();
+    let result = query.execute(conn).await?;
+    Ok(result.rows_affected() as usize)
+}
+
+/// Remove the type IDs, and the columns registered to them, of an indexer's schema.
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn type_id_delete(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<usize> {
+    sqlx::query(
+        "DELETE FROM graph_registry_columns WHERE type_id IN
+        (SELECT id FROM graph_registry_type_ids
+            WHERE schema_name = ?1 AND schema_identifier = ?2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(&mut *conn)
+    .await?;
+
+    let result = sqlx::query(
+        "DELETE FROM graph_registry_type_ids
+        WHERE schema_name = ?1 AND schema_identifier = ?2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn schema_exists(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    version: &str,
+) -> sqlx::Result<bool> {
+    let count = sqlx::query(
+        "SELECT COUNT(*) AS count FROM graph_registry_type_ids
+        WHERE schema_name = ?1
+        AND schema_identifier = ?2
+        AND schema_version = ?3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(version)
+    .fetch_one(conn)
+    .await?;
+
+    let count: i64 = count.get(0);
+
+    Ok(count > 0)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn new_column_insert(
+    conn: &mut PoolConnection<Sqlite>,
+    cols: Vec<Column>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new("INSERT INTO graph_registry_columns (type_id, column_position, column_name, column_type, nullable, graphql_type, is_unique, persistence)");
+
+    builder.push_values(cols.into_iter(), |mut b, new_col| {
+        b.push_bind(new_col.type_id)
+            .push_bind(new_col.position)
+            .push_bind(new_col.name)
+            .push_bind(new_col.coltype.to_string())
+            .push_bind(new_col.nullable)
+            .push_bind(new_col.graphql_type)
+            .push_bind(new_col.unique)
+            .push_bind(new_col.persistence.to_string());
+    });
+
+    let query = builder.build();
+
+    let result = query.execute(conn).await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn list_column_by_id(
+    conn: &mut PoolConnection<Sqlite>,
+    col_id: i64,
+) -> sqlx::Result<Vec<Column>> {
+    Ok(
+        sqlx::query("SELECT * FROM graph_registry_columns WHERE type_id = ?1")
+            .bind(col_id)
+            .fetch_all(conn)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let id: i64 = row.get(0);
+                let type_id: i64 = row.get(1);
+                let position: i32 = row.get(2);
+                let name: String = row.get(3);
+                let coltype: String = row.get(4);
+                let nullable: bool = row.get(5);
+                let graphql_type: String = row.get(6);
+                let unique: bool = row.get(7);
+                let persistence: String = row.get(8);
+                let array_coltype: Option<String> = row.get(9);
+
+                Column {
+                    id,
+                    type_id,
+                    position,
+                    name,
+                    coltype: ColumnType::from(coltype.as_str()),
+                    nullable,
+                    graphql_type,
+                    unique,
+                    persistence: Persistence::from_str(persistence.as_str())
+                        .expect("Bad persistence."),
+                    array_coltype: array_coltype.map(|t| ColumnType::from(t.as_str())),
+                    db_type: DbType::Sqlite,
+                }
+            })
+            .collect::<Vec<Column>>(),
+    )
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn columns_get_schema(
+    conn: &mut PoolConnection<Sqlite>,
+    name: &str,
+    identifier: &str,
+    version: &str,
+) -> sqlx::Result<Vec<ColumnInfo>> {
+    Ok(sqlx::query(
+        "SELECT
+            c.type_id as type_id,
+            t.table_name as table_name,
+            c.column_position as column_position,
+            c.column_name as column_name,
+            c.column_type as column_type
+            FROM graph_registry_type_ids as t
+            INNER JOIN graph_registry_columns as c ON t.id = c.type_id
+            WHERE t.schema_name = ?1
+            AND t.schema_identifier = ?2
+            AND t.schema_version = ?3
+            ORDER BY c.type_id, c.column_position",
+    )
+    .bind(name)
+    .bind(identifier)
+    .bind(version)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row: SqliteRow| {
+        let type_id: i64 = row.get(0);
+        let table_name: String = row.get(1);
+        let column_position: i32 = row.get(2);
+        let column_name: String = row.get(3);
+        let column_type: String = row.get(4);
+
+        ColumnInfo {
+            type_id,
+            table_name,
+            column_position,
+            column_name,
+            column_type,
+        }
+    })
+    .collect::<Vec<ColumnInfo>>())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn get_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<RegisteredIndexer>> {
+    match sqlx::query(
+        "SELECT * FROM index_registry
+        WHERE namespace = ?1
+        AND identifier = ?2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?
+    {
+        Some(row) => Ok(Some(RegisteredIndexer {
+            id: row.get(0),
+            namespace: row.get(1),
+            identifier: row.get(2),
+            pubkey: row.get(3),
+            created_at: row.get(4),
+        })),
+        None => Ok(None),
+    }
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn register_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    pubkey: Option<&str>,
+    created_at: DateTime<Utc>,
+) -> sqlx::Result<RegisteredIndexer> {
+    if let Some(index) = get_indexer(conn, namespace, identifier).await? {
+        return Ok(index);
+    }
+
+    let row = sqlx::query(
+        "INSERT INTO index_registry (namespace, identifier, pubkey, created_at)
+         VALUES (?1, ?2, ?3, ?4)
+         RETURNING *",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(pubkey)
+    .bind(created_at)
+    .fetch_one(conn)
+    .await?;
+
+    let id: i64 = row.get(0);
+    let namespace: String = row.get(1);
+    let identifier: String = row.get(2);
+    let pubkey = row.get(3);
+    let created_at: DateTime<Utc> = row.get(4);
+
+    Ok(RegisteredIndexer {
+        id,
+        namespace,
+        identifier,
+        pubkey,
+        created_at,
+    })
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn all_registered_indexers(
+    conn: &mut PoolConnection<Sqlite>,
+) -> sqlx::Result<Vec<RegisteredIndexer>> {
+    Ok(sqlx::query("SELECT * FROM index_registry")
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let id: i64 = row.get(0);
+            let namespace: String = row.get(1);
+            let identifier: String = row.get(2);
+            let pubkey = row.get(3);
+            let created_at: DateTime<Utc> = row.get(4);
+
+            RegisteredIndexer {
+                id,
+                namespace,
+                identifier,
+                pubkey,
+                created_at,
+            }
+        })
+        .collect::<Vec<RegisteredIndexer>>())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn indexer_asset_version(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    asset_type: &IndexerAssetType,
+) -> sqlx::Result<i64> {
+    match sqlx::query(&format!(
+        "SELECT COUNT(*)
+        FROM index_asset_registry_{}
+        WHERE index_id = {}",
+        asset_type.as_ref(),
+        index_id,
+    ))
+    .fetch_one(conn)
+    .await
+    {
+        Ok(row) => Ok(row.try_get::<i64, usize>(0).unwrap_or(0)),
+        Err(_e) => Ok(0),
+    }
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn register_indexer_asset(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    bytes: Vec<u8>,
+    asset_type: IndexerAssetType,
+    pubkey: Option<&str>,
+) -> sqlx::Result<IndexerAsset> {
+    let index = match get_indexer(conn, namespace, identifier).await? {
+        Some(index) => index,
+        None => {
+            let created_at = DateTime::<Utc>::from(SystemTime::now());
+            register_indexer(conn, namespace, identifier, pubkey, created_at).await?
+        }
+    };
+
+    let digest = sha256_digest(&bytes);
+
+    if let Some(asset) =
+        asset_already_exists(conn, &asset_type, &bytes, &index.id).await?
+    {
+        info!(
+            "Asset({asset_type:?}) for Indexer({}) already registered.",
+            index.uid()
+        );
+        return Ok(asset);
+    }
+
+    let current_version = indexer_asset_version(conn, &index.id, &asset_type)
+        .await
+        .expect("Failed to get asset version.");
+
+    let query = format!(
+        "INSERT INTO index_asset_registry_{} (index_id, bytes, version, digest) VALUES ({}, ?1, {}, '{digest}') RETURNING *",
+        asset_type.as_ref(),
+        index.id,
+        current_version + 1,
+    );
+
+    let row = sqlx::QueryBuilder::new(query)
+        .build()
+        .bind(bytes)
+        .fetch_one(conn)
+        .await?;
+
+    info!(
+        "Registered Asset({:?}) to Indexer({}).",
+        asset_type,
+        index.uid()
+    );
+
+    let id = row.get(0);
+    let index_id = row.get(1);
+    let version = row.get(2);
+    let digest = row.get(3);
+    let bytes = row.get(4);
+
+    Ok(IndexerAsset {
+        id,
+        index_id,
+        version,
+        digest,
+        bytes,
+    })
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn latest_asset_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    asset_type: IndexerAssetType,
+) -> sqlx::Result<IndexerAsset> {
+    let query = format!(
+        "SELECT * FROM index_asset_registry_{} WHERE index_id = {} ORDER BY id DESC LIMIT 1",
+        asset_type.as_ref(),
+        index_id
+    );
+
+    let row = sqlx::query(&query).fetch_one(conn).await?;
+
+    let id = row.get(0);
+    let index_id = row.get(1);
+    let version = row.get(2);
+    let digest = row.get(3);
+    let bytes = row.get(4);
+
+    Ok(IndexerAsset {
+        id,
+        index_id,
+        version,
+        digest,
+        bytes,
+    })
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn assets_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    asset_type: IndexerAssetType,
+) -> sqlx::Result<Vec<IndexerAsset>> {
+    let query = format!(
+        "SELECT * FROM index_asset_registry_{} WHERE index_id = {} ORDER BY version",
+        asset_type.as_ref(),
+        index_id
+    );
+
+    Ok(sqlx::query(&query)
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| IndexerAsset {
+            id: row.get(0),
+            index_id: row.get(1),
+            version: row.get(2),
+            digest: row.get(3),
+            bytes: row.get(4),
+        })
+        .collect())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn latest_assets_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    indexer_id: &i64,
+) -> sqlx::Result<IndexerAssetBundle> {
+    let wasm = latest_asset_for_indexer(conn, indexer_id, IndexerAssetType::Wasm).await?;
+    let schema =
+        latest_asset_for_indexer(conn, indexer_id, IndexerAssetType::Schema).await?;
+    let manifest =
+        latest_asset_for_indexer(conn, indexer_id, IndexerAssetType::Manifest).await?;
+
+    Ok(IndexerAssetBundle {
+        wasm,
+        schema,
+        manifest,
+    })
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn last_block_height_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<u64> {
+    let query = format!(
+        "SELECT MAX(block_height) FROM {} LIMIT 1",
+        table_name(namespace, identifier, "indexmetadataentity")
+    );
+
+    let row = sqlx::query(&query).fetch_one(conn).await?;
+
+    Ok(row
+        .try_get::<Option<i64>, usize>(0)
+        .ok()
+        .flatten()
+        .map(|height| height as u64)
+        .unwrap_or(1))
+}
+
+// TODO: https://github.com/FuelLabs/fuel-indexer/issues/251
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn asset_already_exists(
+    conn: &mut PoolConnection<Sqlite>,
+    asset_type: &IndexerAssetType,
+    bytes: &Vec<u8>,
+    index_id: &i64,
+) -> sqlx::Result<Option<IndexerAsset>> {
+    let digest = sha256_digest(bytes);
+
+    let query = format!(
+        "SELECT * FROM index_asset_registry_{} WHERE index_id = {} AND digest = '{}'",
+        asset_type.as_ref(),
+        index_id,
+        digest
+    );
+
+    match sqlx::QueryBuilder::new(query).build().fetch_one(conn).await {
+        Ok(row) => {
+            let id = row.get(0);
+            let index_id = row.get(1);
+            let version = row.get(2);
+            let digest = row.get(3);
+            let bytes = row.get(4);
+
+            Ok(Some(IndexerAsset {
+                id,
+                index_id,
+                version,
+                digest,
+                bytes,
+            }))
+        }
+        Err(_e) => Ok(None),
+    }
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn get_indexer_id(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<i64> {
+    let row = sqlx::query(
+        "SELECT id FROM index_registry
+        WHERE namespace = ?1
+        AND identifier = ?2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    let id: i64 = row.get(0);
+
+    Ok(id)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn penultimate_asset_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    asset_type: IndexerAssetType,
+) -> sqlx::Result<IndexerAsset> {
+    let index_id = get_indexer_id(conn, namespace, identifier).await?;
+    let query = format!(
+        "SELECT * FROM index_asset_registry_{}
+        WHERE index_id = {} ORDER BY id DESC LIMIT 1 OFFSET 1",
+        asset_type.as_ref(),
+        index_id
+    );
+    let row = sqlx::query(&query).fetch_one(conn).await?;
+
+    let id = row.get(0);
+    let index_id = row.get(1);
+    let version = row.get(2);
+    let digest = row.get(3);
+    let bytes = row.get(4);
+
+    Ok(IndexerAsset {
+        id,
+        index_id,
+        version,
+        digest,
+        bytes,
+    })
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn start_transaction(conn: &mut PoolConnection<Sqlite>) -> sqlx::Result<usize> {
+    execute_query(conn, "BEGIN".into()).await
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn commit_transaction(
+    conn: &mut PoolConnection<Sqlite>,
+) -> sqlx::Result<usize> {
+    execute_query(conn, "COMMIT".into()).await
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn revert_transaction(
+    conn: &mut PoolConnection<Sqlite>,
+) -> sqlx::Result<usize> {
+    execute_query(conn, "ROLLBACK".into()).await
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn remove_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    for table in [
+        "index_asset_registry_wasm",
+        "index_asset_registry_manifest",
+        "index_asset_registry_schema",
+        "index_failure",
+        "index_log",
+        "index_backfill_partition",
+        "index_checkpoint",
+        "index_status",
+    ] {
+        sqlx::query(&format!(
+            "DELETE FROM {table} WHERE index_id IN
+            (SELECT id FROM index_registry WHERE namespace = ?1 AND identifier = ?2)"
+        ))
+        .bind(namespace)
+        .bind(identifier)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    sqlx::query("DELETE FROM index_registry WHERE namespace = ?1 AND identifier = ?2")
+        .bind(namespace)
+        .bind(identifier)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query(
+        "DELETE FROM graph_registry_columns WHERE type_id IN
+        (SELECT id FROM graph_registry_type_ids WHERE schema_name = ?1 AND schema_identifier = ?2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM graph_registry_type_ids WHERE schema_name = ?1 AND schema_identifier = ?2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM graph_registry_root_columns WHERE root_id IN
+        (SELECT id FROM graph_registry_graph_root WHERE schema_name = ?1 AND schema_identifier = ?2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM graph_registry_graph_root WHERE schema_name = ?1 AND schema_identifier = ?2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(&mut *conn)
+    .await?;
+
+    // Without schemas to drop, we drop every table carrying the indexer's prefix.
+    let prefix = table_name(namespace, identifier, "");
+    let tables: Vec<String> = sqlx::query(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND substr(name, 1, length(?1)) = ?1",
+    )
+    .bind(&prefix)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| row.get(0))
+    .collect();
+
+    for table in tables {
+        execute_query(conn, format!("DROP TABLE IF EXISTS {table}")).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn remove_asset_by_version(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    version: &i32,
+    asset_type: IndexerAssetType,
+) -> sqlx::Result<()> {
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_asset_registry_{0} WHERE index_id = {1} AND version = {2}",
+            asset_type.as_ref(),
+            index_id,
+            version
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn create_nonce(conn: &mut PoolConnection<Sqlite>) -> sqlx::Result<Nonce> {
+    let uid = uuid::Uuid::new_v4().as_simple().to_string();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let expiry = now + NONCE_EXPIRY;
+
+    let row = sqlx::QueryBuilder::new(&format!(
+        "INSERT INTO nonce (uid, expiry) VALUES ('{uid}', {expiry}) RETURNING *"
+    ))
+    .build()
+    .fetch_one(conn)
+    .await?;
+
+    let uid: String = row.get(1);
+    let expiry: i64 = row.get(2);
+
+    Ok(Nonce { uid, expiry })
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn delete_nonce(
+    conn: &mut PoolConnection<Sqlite>,
+    nonce: &Nonce,
+) -> sqlx::Result<()> {
+    let _ = sqlx::query(&format!("DELETE FROM nonce WHERE uid = '{}'", nonce.uid))
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn get_nonce(
+    conn: &mut PoolConnection<Sqlite>,
+    uid: &str,
+) -> sqlx::Result<Nonce> {
+    let row = sqlx::query(&format!("SELECT * FROM nonce WHERE uid = '{uid}'"))
+        .fetch_one(conn)
+        .await?;
+
+    let uid: String = row.get(1);
+    let expiry: i64 = row.get(2);
+
+    Ok(Nonce { uid, expiry })
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn indexer_status(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+) -> sqlx::Result<Option<IndexerStatus>> {
+    let row = sqlx::query(&format!(
+        "SELECT index_id, start_block, start_block_source FROM index_status WHERE index_id = {index_id}"
+    ))
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| {
+        let index_id: i64 = row.get(0);
+        let start_block: i64 = row.get(1);
+        let start_block_source: String = row.get(2);
+
+        IndexerStatus {
+            index_id,
+            start_block: start_block as u64,
+            start_block_source,
+        }
+    }))
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn set_indexer_start_block(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+    start_block: u64,
+    start_block_source: &str,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_status (index_id, start_block, start_block_source) VALUES (?1, ?2, ?3)
+        ON CONFLICT (index_id) DO UPDATE SET start_block = ?2, start_block_source = ?3, resolved_at = CURRENT_TIMESTAMP",
+    )
+    .bind(index_id)
+    .bind(start_block as i64)
+    .bind(start_block_source)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn put_checkpoint(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    checkpoint: &IndexerCheckpoint,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_checkpoint (index_id, block_height, block_id, cursor)
+        SELECT id, ?3, ?4, ?5 FROM index_registry WHERE namespace = ?1 AND identifier = ?2
+        ON CONFLICT (index_id) DO UPDATE SET block_height = ?3, block_id = ?4, cursor = ?5, updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(checkpoint.block_height as i64)
+    .bind(&checkpoint.block_id)
+    .bind(&checkpoint.cursor)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn last_checkpoint_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerCheckpoint>> {
+    let row = sqlx::query(
+        "SELECT c.block_height, c.block_id, c.cursor FROM index_checkpoint c
+        INNER JOIN index_registry r ON r.id = c.index_id
+        WHERE r.namespace = ?1 AND r.identifier = ?2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| {
+        let block_height: i64 = row.get(0);
+        let block_id: String = row.get(1);
+        let cursor: Option<String> = row.get(2);
+
+        IndexerCheckpoint {
+            block_height: block_height as u64,
+            block_id,
+            cursor,
+        }
+    }))
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn put_backfill_partition(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    partition: &IndexerBackfillPartition,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_backfill_partition (index_id, start_block, end_block, block_height, block_id)
+        SELECT id, ?3, ?4, ?5, ?6 FROM index_registry WHERE namespace = ?1 AND identifier = ?2
+        ON CONFLICT (index_id, start_block) DO UPDATE SET end_block = ?4, block_height = ?5, block_id = ?6, updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(partition.start_block as i64)
+    .bind(partition.end_block as i64)
+    .bind(partition.block_height.map(|height| height as i64))
+    .bind(&partition.block_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn backfill_partitions_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerBackfillPartition>> {
+    let rows = sqlx::query(
+        "SELECT p.start_block, p.end_block, p.block_height, p.block_id FROM index_backfill_partition p
+        INNER JOIN index_registry r ON r.id = p.index_id
+        WHERE r.namespace = ?1 AND r.identifier = ?2
+        ORDER BY p.start_block ASC",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let start_block: i64 = row.get(0);
+            let end_block: i64 = row.get(1);
+            let block_height: Option<i64> = row.get(2);
+            let block_id: Option<String> = row.get(3);
+
+            IndexerBackfillPartition {
+                start_block: start_block as u64,
+                end_block: end_block as u64,
+                block_height: block_height.map(|height| height as u64),
+                block_id,
+            }
+        })
+        .collect())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn remove_backfill_partitions(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "DELETE FROM index_backfill_partition WHERE index_id IN
+        (SELECT id FROM index_registry WHERE namespace = ?1 AND identifier = ?2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Persist the given log lines, then remove all but the latest `capacity` lines
+/// logged by the indexer.
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn put_logs(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    logs: &[IndexerLog],
+    capacity: usize,
+) -> sqlx::Result<()> {
+    let index_id = get_indexer_id(conn, namespace, identifier).await?;
+
+    for log in logs {
+        sqlx::query(
+            "INSERT INTO index_log (index_id, level, block_height, message, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind(index_id)
+        .bind(&log.level)
+        .bind(log.block_height.map(|height| height as i64))
+        .bind(&log.message)
+        .bind(log.created_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    sqlx::query(
+        "DELETE FROM index_log WHERE index_id = ?1 AND id <=
+        (SELECT id FROM index_log WHERE index_id = ?1 ORDER BY id DESC LIMIT 1 OFFSET ?2)",
+    )
+    .bind(index_id)
+    .bind(capacity as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Return up to `limit` log lines of the indexer logged at one of the given levels,
+/// oldest first.
+///
+/// If `after` is given, these are the lines logged after the line with that ID.
+/// Otherwise, they're the latest lines logged.
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn indexer_logs(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    levels: &[String],
+    after: Option<i64>,
+    limit: usize,
+) -> sqlx::Result<Vec<IndexerLog>> {
+    // SQLite has no array parameters, so the levels are bound one at a time.
+    let mut builder = sqlx::QueryBuilder::new(
+        "SELECT l.id, l.level, l.block_height, l.message, l.created_at FROM index_log l
+        INNER JOIN index_registry r ON r.id = l.index_id
+        WHERE r.namespace = ",
+    );
+    builder
+        .push_bind(namespace)
+        .push(" AND r.identifier = ")
+        .push_bind(identifier)
+        .push(" AND l.level IN (");
+
+    let mut separated = builder.separated(", ");
+    for level in levels {
+        separated.push_bind(level);
+    }
+    separated.push_unseparated(")");
+
+    match after {
+        Some(after) => {
+            builder
+                .push(" AND l.id > ")
+                .push_bind(after)
+                .push(" ORDER BY l.id ASC LIMIT ")
+                .push_bind(limit as i64);
+        }
+        None => {
+            builder
+                .push(" ORDER BY l.id DESC LIMIT ")
+                .push_bind(limit as i64);
+        }
+    }
+
+    let mut logs = builder
+        .build()
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let block_height: Option<i64> = row.get(2);
+
+            IndexerLog {
+                id: row.get(0),
+                level: row.get(1),
+                block_height: block_height.map(|height| height as u64),
+                message: row.get(3),
+                created_at: row.get(4),
+            }
+        })
+        .collect::<Vec<IndexerLog>>();
+
+    if after.is_none() {
+        logs.reverse();
+    }
+
+    Ok(logs)
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn put_indexer_failure(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    failure: &IndexerFailure,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_failure (index_id, message, block_height, created_at)
+        SELECT id, ?3, ?4, ?5 FROM index_registry WHERE namespace = ?1 AND identifier = ?2
+        ON CONFLICT (index_id) DO UPDATE SET message = ?3, block_height = ?4, created_at = ?5",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(&failure.message)
+    .bind(failure.block_height.map(|height| height as i64))
+    .bind(failure.created_at)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn last_indexer_failure(
+    conn: &mut PoolConnection<Sqlite>,
+    index_id: &i64,
+) -> sqlx::Result<Option<IndexerFailure>> {
+    let row = sqlx::query(
+        "SELECT message, block_height, created_at FROM index_failure WHERE index_id = ?1",
+    )
+    .bind(index_id)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| {
+        let block_height: Option<i64> = row.get(1);
+
+        IndexerFailure {
+            message: row.get(0),
+            block_height: block_height.map(|height| height as u64),
+            created_at: row.get(2),
+        }
+    }))
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn remove_latest_assets_for_indexer(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    let indexer_id = get_indexer_id(conn, namespace, identifier).await?;
+
+    let wasm =
+        latest_asset_for_indexer(conn, &indexer_id, IndexerAssetType::Wasm).await?;
+    let manifest =
+        latest_asset_for_indexer(conn, &indexer_id, IndexerAssetType::Manifest).await?;
+    let schema =
+        latest_asset_for_indexer(conn, &indexer_id, IndexerAssetType::Schema).await?;
+
+    remove_asset_by_version(conn, &indexer_id, &wasm.version, IndexerAssetType::Wasm)
+        .await?;
+    remove_asset_by_version(
+        conn,
+        &indexer_id,
+        &manifest.version,
+        IndexerAssetType::Manifest,
+    )
+    .await?;
+    remove_asset_by_version(conn, &indexer_id, &schema.version, IndexerAssetType::Schema)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "metrics", metrics(sqlite))]
+pub async fn indexer_owned_by(
+    conn: &mut PoolConnection<Sqlite>,
+    namespace: &str,
+    identifier: &str,
+    pubkey: &str,
+) -> sqlx::Result<()> {
+    let row = sqlx::query(
+        "SELECT COUNT(*) FROM index_registry WHERE namespace = ?1 AND identifier = ?2 AND pubkey = ?3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(pubkey)
+    .fetch_one(conn)
+    .await?;
+
+    let count = row.get::<i64, usize>(0);
+    if count == 1 {
+        return Ok(());
+    }
+
+    Err(sqlx::Error::RowNotFound)
+}
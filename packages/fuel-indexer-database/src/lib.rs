@@ -11,8 +11,10 @@ use fuel_indexer_lib::{
     utils::{attempt_database_connection, ServiceStatus},
 };
 use fuel_indexer_postgres as postgres;
+use fuel_indexer_sqlite as sqlite;
 use sqlx::{
-    pool::PoolConnection, postgres::PgConnectOptions, ConnectOptions, Error as SqlxError,
+    pool::PoolConnection, postgres::PgConnectOptions, sqlite::SqliteConnectOptions,
+    ConnectOptions, Error as SqlxError,
 };
 use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 use thiserror::Error;
@@ -36,17 +38,20 @@ pub enum IndexerDatabaseError {
 #[derive(Debug)]
 pub enum IndexerConnection {
     Postgres(Box<PoolConnection<sqlx::Postgres>>),
+    Sqlite(Box<PoolConnection<sqlx::Sqlite>>),
 }
 
 #[derive(Clone, Debug)]
 pub enum IndexerConnectionPool {
     Postgres(sqlx::Pool<sqlx::Postgres>),
+    Sqlite(sqlx::Pool<sqlx::Sqlite>),
 }
 
 impl IndexerConnectionPool {
     pub fn database_type(&self) -> DbType {
         match self {
             IndexerConnectionPool::Postgres(_) => DbType::Postgres,
+            IndexerConnectionPool::Sqlite(_) => DbType::Sqlite,
         }
     }
 
@@ -92,6 +97,20 @@ impl IndexerConnectionPool {
 
                 Ok(IndexerConnectionPool::Postgres(pool))
             }
+            "sqlite" => {
+                let mut opts =
+                    SqliteConnectOptions::from_str(url.as_str())?.create_if_missing(true);
+                if verbose != "true" {
+                    opts = opts.disable_statement_logging().clone();
+                }
+
+                let pool = attempt_database_connection(|| {
+                    sqlx::sqlite::SqlitePoolOptions::new().connect_with(opts.clone())
+                })
+                .await;
+
+                Ok(IndexerConnectionPool::Sqlite(pool))
+            }
             err => Err(IndexerDatabaseError::BackendNotSupported(err.into())),
         }
     }
@@ -109,6 +128,12 @@ impl IndexerConnectionPool {
                     _ => Ok(ServiceStatus::NotOk),
                 }
             }
+            IndexerConnectionPool::Sqlite(p) => {
+                let mut conn = p.acquire().await?;
+                sqlite::execute_query(&mut conn, "SELECT true;".to_string()).await?;
+
+                Ok(ServiceStatus::OK)
+            }
         }
     }
 
@@ -117,6 +142,9 @@ impl IndexerConnectionPool {
             IndexerConnectionPool::Postgres(p) => {
                 Ok(IndexerConnection::Postgres(Box::new(p.acquire().await?)))
             }
+            IndexerConnectionPool::Sqlite(p) => {
+                Ok(IndexerConnection::Sqlite(Box::new(p.acquire().await?)))
+            }
         }
     }
 }
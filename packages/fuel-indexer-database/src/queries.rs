@@ -1,5 +1,6 @@
 use crate::{types::*, IndexerConnection};
 use fuel_indexer_postgres as postgres;
+use fuel_indexer_sqlite as sqlite;
 use sqlx::types::{
     chrono::{DateTime, Utc},
     JsonValue,
@@ -14,6 +15,9 @@ pub async fn graph_root_latest(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::graph_root_latest(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::graph_root_latest(c, namespace, identifier).await
+        }
     }
 }
 
@@ -23,6 +27,7 @@ pub async fn new_graph_root(
 ) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::new_graph_root(c, root).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::new_graph_root(c, root).await,
     }
 }
 
@@ -36,6 +41,9 @@ pub async fn type_id_list_by_name(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::type_id_list_by_name(c, name, version, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::type_id_list_by_name(c, name, version, identifier).await
+        }
     }
 }
 
@@ -48,6 +56,9 @@ pub async fn type_id_latest(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::type_id_latest(c, schema_name, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::type_id_latest(c, schema_name, identifier).await
+        }
     }
 }
 
@@ -59,6 +70,23 @@ pub async fn type_id_insert(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::type_id_insert(c, type_ids).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::type_id_insert(c, type_ids).await,
+    }
+}
+
+/// Remove the type IDs, and the columns registered to them, of an indexer's schema.
+pub async fn type_id_delete(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::type_id_delete(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::type_id_delete(c, namespace, identifier).await
+        }
     }
 }
 
@@ -72,6 +100,9 @@ pub async fn schema_exists(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::schema_exists(c, namespace, identifier, version).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::schema_exists(c, namespace, identifier, version).await
+        }
     }
 }
 
@@ -83,6 +114,7 @@ pub async fn new_column_insert(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::new_column_insert(c, cols).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::new_column_insert(c, cols).await,
     }
 }
 
@@ -94,6 +126,9 @@ pub async fn list_column_by_id(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::list_column_by_id(c, col_id).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::list_column_by_id(c, col_id).await
+        }
     }
 }
 
@@ -107,6 +142,9 @@ pub async fn columns_get_schema(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::columns_get_schema(c, name, identifier, version).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::columns_get_schema(c, name, identifier, version).await
+        }
     }
 }
 
@@ -119,6 +157,7 @@ pub async fn put_object(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::put_object(c, query, bytes).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::put_object(c, query, bytes).await,
     }
 }
 
@@ -128,6 +167,7 @@ pub async fn get_object(
 ) -> sqlx::Result<Vec<u8>> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::get_object(c, query).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::get_object(c, query).await,
     }
 }
 
@@ -137,6 +177,7 @@ pub async fn run_query(
 ) -> sqlx::Result<JsonValue> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::run_query(c, query).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::run_query(c, query).await,
     }
 }
 
@@ -146,6 +187,7 @@ pub async fn execute_query(
 ) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::execute_query(c, query).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::execute_query(c, query).await,
     }
 }
 
@@ -157,6 +199,9 @@ pub async fn root_columns_list_by_id(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::root_columns_list_by_id(c, root_id).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::root_columns_list_by_id(c, root_id).await
+        }
     }
 }
 
@@ -168,6 +213,7 @@ pub async fn new_root_columns(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::new_root_columns(c, cols).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::new_root_columns(c, cols).await,
     }
 }
 
@@ -180,6 +226,9 @@ pub async fn get_indexer(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::get_indexer(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::get_indexer(c, namespace, identifier).await
+        }
     }
 }
 
@@ -194,6 +243,10 @@ pub async fn register_indexer(
             let created_at = DateTime::<Utc>::from(std::time::SystemTime::now());
             postgres::register_indexer(c, namespace, identifier, pubkey, created_at).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            let created_at = DateTime::<Utc>::from(std::time::SystemTime::now());
+            sqlite::register_indexer(c, namespace, identifier, pubkey, created_at).await
+        }
     }
 }
 
@@ -204,6 +257,7 @@ pub async fn all_registered_indexers(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::all_registered_indexers(c).await
         }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::all_registered_indexers(c).await,
     }
 }
 
@@ -216,6 +270,9 @@ pub async fn indexer_asset_version(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::indexer_asset_version(c, index_id, asset_type).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::indexer_asset_version(c, index_id, asset_type).await
+        }
     }
 }
 
@@ -234,6 +291,12 @@ pub async fn register_indexer_asset(
             )
             .await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::register_indexer_asset(
+                c, namespace, identifier, bytes, asset_type, pubkey,
+            )
+            .await
+        }
     }
 }
 
@@ -246,6 +309,25 @@ pub async fn latest_asset_for_indexer(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::latest_asset_for_indexer(c, index_id, asset_type).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::latest_asset_for_indexer(c, index_id, asset_type).await
+        }
+    }
+}
+
+/// Return every version of an asset of an indexer, oldest first.
+pub async fn assets_for_indexer(
+    conn: &mut IndexerConnection,
+    index_id: &i64,
+    asset_type: IndexerAssetType,
+) -> sqlx::Result<Vec<IndexerAsset>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::assets_for_indexer(c, index_id, asset_type).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::assets_for_indexer(c, index_id, asset_type).await
+        }
     }
 }
 
@@ -257,6 +339,9 @@ pub async fn latest_assets_for_indexer(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::latest_assets_for_indexer(c, index_id).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::latest_assets_for_indexer(c, index_id).await
+        }
     }
 }
 
@@ -269,6 +354,86 @@ pub async fn last_block_height_for_indexer(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::last_block_height_for_indexer(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::last_block_height_for_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
+pub async fn put_checkpoint(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    checkpoint: &IndexerCheckpoint,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_checkpoint(c, namespace, identifier, checkpoint).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::put_checkpoint(c, namespace, identifier, checkpoint).await
+        }
+    }
+}
+
+pub async fn last_checkpoint_for_indexer(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerCheckpoint>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::last_checkpoint_for_indexer(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::last_checkpoint_for_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
+pub async fn put_backfill_partition(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    partition: &IndexerBackfillPartition,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_backfill_partition(c, namespace, identifier, partition).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::put_backfill_partition(c, namespace, identifier, partition).await
+        }
+    }
+}
+
+pub async fn backfill_partitions_for_indexer(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerBackfillPartition>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::backfill_partitions_for_indexer(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::backfill_partitions_for_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
+pub async fn remove_backfill_partitions(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::remove_backfill_partitions(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::remove_backfill_partitions(c, namespace, identifier).await
+        }
     }
 }
 
@@ -282,6 +447,9 @@ pub async fn asset_already_exists(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::asset_already_exists(c, asset_type, bytes, index_id).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::asset_already_exists(c, asset_type, bytes, index_id).await
+        }
     }
 }
 
@@ -294,6 +462,9 @@ pub async fn get_indexer_id(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::get_indexer_id(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::get_indexer_id(c, namespace, identifier).await
+        }
     }
 }
 
@@ -308,30 +479,218 @@ pub async fn penultimate_asset_for_indexer(
             postgres::penultimate_asset_for_indexer(c, namespace, identifier, asset_type)
                 .await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::penultimate_asset_for_indexer(c, namespace, identifier, asset_type)
+                .await
+        }
+    }
+}
+
+pub async fn indexer_status(
+    conn: &mut IndexerConnection,
+    index_id: &i64,
+) -> sqlx::Result<Option<IndexerStatus>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_status(c, index_id).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => sqlite::indexer_status(c, index_id).await,
+    }
+}
+
+pub async fn set_indexer_start_block(
+    conn: &mut IndexerConnection,
+    index_id: &i64,
+    start_block: u64,
+    start_block_source: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::set_indexer_start_block(
+                c,
+                index_id,
+                start_block,
+                start_block_source,
+            )
+            .await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::set_indexer_start_block(c, index_id, start_block, start_block_source)
+                .await
+        }
     }
 }
 
 pub async fn start_transaction(conn: &mut IndexerConnection) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::start_transaction(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::start_transaction(c).await,
     }
 }
 
 pub async fn commit_transaction(conn: &mut IndexerConnection) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::commit_transaction(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::commit_transaction(c).await,
     }
 }
 
 pub async fn revert_transaction(conn: &mut IndexerConnection) -> sqlx::Result<usize> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::revert_transaction(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::revert_transaction(c).await,
+    }
+}
+
+pub async fn create_kv_store(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::create_kv_store(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::create_kv_store(c, namespace, identifier).await
+        }
+    }
+}
+
+pub async fn get_kv(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+) -> sqlx::Result<Option<Vec<u8>>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_kv(c, namespace, identifier, key).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::get_kv(c, namespace, identifier, key).await
+        }
+    }
+}
+
+/// Return every entry of an indexer's key/value store, ordered by key.
+pub async fn kv_entries(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::kv_entries(c, namespace, identifier).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::kv_entries(c, namespace, identifier).await
+        }
+    }
+}
+
+pub async fn put_kv(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+    value: &[u8],
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_kv(c, namespace, identifier, key, value).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::put_kv(c, namespace, identifier, key, value).await
+        }
+    }
+}
+
+pub async fn delete_kv(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    key: &[u8],
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::delete_kv(c, namespace, identifier, key).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::delete_kv(c, namespace, identifier, key).await
+        }
+    }
+}
+
+pub async fn put_logs(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    logs: &[IndexerLog],
+    capacity: usize,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_logs(c, namespace, identifier, logs, capacity).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::put_logs(c, namespace, identifier, logs, capacity).await
+        }
+    }
+}
+
+pub async fn indexer_logs(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    levels: &[String],
+    after: Option<i64>,
+    limit: usize,
+) -> sqlx::Result<Vec<IndexerLog>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_logs(c, namespace, identifier, levels, after, limit).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::indexer_logs(c, namespace, identifier, levels, after, limit).await
+        }
+    }
+}
+
+pub async fn put_indexer_failure(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    failure: &IndexerFailure,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_indexer_failure(c, namespace, identifier, failure).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::put_indexer_failure(c, namespace, identifier, failure).await
+        }
+    }
+}
+
+pub async fn last_indexer_failure(
+    conn: &mut IndexerConnection,
+    index_id: &i64,
+) -> sqlx::Result<Option<IndexerFailure>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::last_indexer_failure(c, index_id).await
+        }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::last_indexer_failure(c, index_id).await
+        }
     }
 }
 
 pub async fn run_migration(conn: &mut IndexerConnection) -> sqlx::Result<()> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::run_migration(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::run_migration(c).await,
     }
 }
 
@@ -344,6 +703,9 @@ pub async fn remove_indexer(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::remove_indexer(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::remove_indexer(c, namespace, identifier).await
+        }
     }
 }
 
@@ -356,6 +718,9 @@ pub async fn remove_latest_assets_for_indexer(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::remove_latest_assets_for_indexer(c, namespace, identifier).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::remove_latest_assets_for_indexer(c, namespace, identifier).await
+        }
     }
 }
 
@@ -369,18 +734,23 @@ pub async fn remove_asset_by_version(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::remove_asset_by_version(c, index_id, version, asset_type).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::remove_asset_by_version(c, index_id, version, asset_type).await
+        }
     }
 }
 
 pub async fn create_nonce(conn: &mut IndexerConnection) -> sqlx::Result<Nonce> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::create_nonce(c).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::create_nonce(c).await,
     }
 }
 
 pub async fn get_nonce(conn: &mut IndexerConnection, uid: &str) -> sqlx::Result<Nonce> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::get_nonce(c, uid).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::get_nonce(c, uid).await,
     }
 }
 
@@ -390,6 +760,7 @@ pub async fn delete_nonce(
 ) -> sqlx::Result<()> {
     match conn {
         IndexerConnection::Postgres(ref mut c) => postgres::delete_nonce(c, nonce).await,
+        IndexerConnection::Sqlite(ref mut c) => sqlite::delete_nonce(c, nonce).await,
     }
 }
 
@@ -403,5 +774,8 @@ pub async fn indexer_owned_by(
         IndexerConnection::Postgres(ref mut c) => {
             postgres::indexer_owned_by(c, namespace, identifier, pubkey).await
         }
+        IndexerConnection::Sqlite(ref mut c) => {
+            sqlite::indexer_owned_by(c, namespace, identifier, pubkey).await
+        }
     }
 }
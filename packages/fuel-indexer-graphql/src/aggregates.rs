@@ -0,0 +1,321 @@
+use super::{
+    arguments::{is_numeric_field, numeric_column, parse_argument_into_param, ParamType},
+    dynamic::NUMERIC_SCALAR_TYPES,
+    graphql::{GraphqlError, GraphqlResult, Selection},
+    queries::QueryElement,
+};
+use async_graphql_parser::types::{Field, Selection as ParsedSelection, SelectionSet};
+use async_graphql_value::Value;
+use fuel_indexer_database::DbType;
+use fuel_indexer_lib::graphql::{field_id, is_list_type};
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use std::fmt;
+
+/// Suffix of the root query fields that aggregate the records of an entity.
+pub const AGGREGATE_SUFFIX: &str = "Aggregate";
+
+/// Represents a SQL function that aggregates the values of a numeric column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFunction {
+    /// Return the aggregate function for a given selection name, if any.
+    fn from_selection(name: &str) -> Option<Self> {
+        match name {
+            "sum" => Some(Self::Sum),
+            "avg" => Some(Self::Avg),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AggregateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregateFunction::Sum => write!(f, "SUM"),
+            AggregateFunction::Avg => write!(f, "AVG"),
+            AggregateFunction::Min => write!(f, "MIN"),
+            AggregateFunction::Max => write!(f, "MAX"),
+        }
+    }
+}
+
+/// Represents a value computed over the records matched by an aggregate query.
+///
+/// Each selected field is kept along with the key under which it's returned, which
+/// is its alias, if it has one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Aggregate {
+    /// Number of records, or of distinct values of a field.
+    Count {
+        key: String,
+        distinct: Option<String>,
+    },
+    /// Aggregates of the values of numeric fields.
+    Function {
+        function: AggregateFunction,
+        key: String,
+        fields: Vec<(String, String)>,
+    },
+    /// Values of the fields by which records are grouped.
+    Group {
+        key: String,
+        fields: Vec<(String, String)>,
+    },
+}
+
+impl Aggregate {
+    /// Return the query elements that select this aggregate from the given table.
+    pub fn into_elements(self, table: &str, db_type: &DbType) -> Vec<QueryElement> {
+        match self {
+            Aggregate::Count { key, distinct } => vec![QueryElement::Field {
+                key,
+                value: match distinct {
+                    Some(field) => format!("COUNT(DISTINCT {table}.{field})"),
+                    None => "COUNT(*)".to_string(),
+                },
+            }],
+            Aggregate::Function {
+                function,
+                key,
+                fields,
+            } => object_elements(key, fields, |field| {
+                format!(
+                    "{function}({})",
+                    numeric_column(db_type, &format!("{table}.{field}"))
+                )
+            }),
+            Aggregate::Group { key, fields } => {
+                object_elements(key, fields, |field| format!("{table}.{field}"))
+            }
+        }
+    }
+}
+
+/// Return the query elements of a nested object holding a value for each field.
+fn object_elements(
+    key: String,
+    fields: Vec<(String, String)>,
+    value: impl Fn(&str) -> String,
+) -> Vec<QueryElement> {
+    let mut elements = vec![QueryElement::ObjectOpeningBoundary { key }];
+    elements.extend(fields.into_iter().map(|(field, key)| QueryElement::Field {
+        value: value(&field),
+        key,
+    }));
+    elements.push(QueryElement::ObjectClosingBoundary);
+    elements
+}
+
+/// Parse a root query field that aggregates the records of an entity.
+///
+/// Aggregate queries take the same filtering arguments as entity queries, along with
+/// a `groupBy` argument, but can't be ordered or paginated.
+pub fn parse_aggregate_selection(
+    schema: &IndexerSchema,
+    entity_type: &String,
+    field: &Field,
+) -> GraphqlResult<Selection> {
+    let name = field.name.node.to_string();
+    let entity_name = name
+        .strip_suffix(AGGREGATE_SUFFIX)
+        .unwrap_or(&name)
+        .to_string();
+
+    let mut params = Vec::new();
+    let mut group_by = Vec::new();
+    for (arg, value) in field.arguments.iter() {
+        match arg.node.as_str() {
+            "groupBy" => {
+                group_by = parse_group_by(schema, entity_type, value.node.clone())?
+            }
+            "order" | "offset" | "first" => {
+                return Err(GraphqlError::UnrecognizedArgument(name, arg.to_string()))
+            }
+            other => params.push(parse_argument_into_param(
+                Some(entity_type),
+                other,
+                value.node.clone(),
+                schema,
+            )?),
+        }
+    }
+
+    let aggregates = field
+        .selection_set
+        .node
+        .items
+        .iter()
+        .map(|item| match &item.node {
+            ParsedSelection::Field(f) => {
+                parse_aggregate(schema, entity_type, &f.node, &group_by)
+            }
+            _ => Err(GraphqlError::SelectionNotSupported),
+        })
+        .collect::<GraphqlResult<Vec<Aggregate>>>()?;
+
+    params.extend(group_by.into_iter().map(|field| {
+        let numeric = is_numeric_field(schema, Some(entity_type), &field);
+        ParamType::GroupBy(field, numeric)
+    }));
+
+    Ok(Selection::Aggregate {
+        name: entity_name,
+        params,
+        aggregates,
+        alias: field.alias.as_ref().map(|a| a.to_string()),
+    })
+}
+
+/// Parse the fields by which the records of an aggregate query are grouped.
+fn parse_group_by(
+    schema: &IndexerSchema,
+    entity_type: &String,
+    value: Value,
+) -> GraphqlResult<Vec<String>> {
+    // A single field can be given without being wrapped in a list.
+    let values = match value {
+        Value::List(values) => values,
+        value => vec![value],
+    };
+
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::Enum(field) => {
+                let field = field.to_string();
+                let is_scalar = schema
+                    .parsed()
+                    .field_defs()
+                    .get(&field_id(entity_type, &field))
+                    .map(|(f, _)| !is_list_type(f))
+                    .unwrap_or(false);
+                if is_scalar {
+                    Ok(field)
+                } else {
+                    Err(GraphqlError::UnrecognizedField(
+                        entity_type.to_string(),
+                        field,
+                    ))
+                }
+            }
+            value => Err(GraphqlError::UnsupportedValueType(value.to_string())),
+        })
+        .collect()
+}
+
+/// Parse a single selection of an aggregate query.
+fn parse_aggregate(
+    schema: &IndexerSchema,
+    entity_type: &String,
+    field: &Field,
+    group_by: &[String],
+) -> GraphqlResult<Aggregate> {
+    let name = field.name.node.to_string();
+    let key = field
+        .alias
+        .as_ref()
+        .map(|a| a.to_string())
+        .unwrap_or(name.clone());
+
+    let has_field =
+        |f: &str| schema.parsed().graphql_type(Some(entity_type), f).is_some();
+
+    match name.as_str() {
+        "count" => {
+            let mut distinct = None;
+            for (arg, value) in field.arguments.iter() {
+                match (arg.node.as_str(), &value.node) {
+                    ("distinct", Value::Enum(f)) if has_field(f.as_str()) => {
+                        distinct = Some(f.to_string())
+                    }
+                    ("distinct", value) => {
+                        return Err(GraphqlError::UnsupportedValueType(value.to_string()))
+                    }
+                    (other, _) => {
+                        return Err(GraphqlError::UnrecognizedArgument(
+                            name,
+                            other.to_string(),
+                        ))
+                    }
+                }
+            }
+            Ok(Aggregate::Count { key, distinct })
+        }
+        "group" => {
+            let fields = aggregate_fields(entity_type, &field.selection_set.node)?;
+            if let Some((f, _)) = fields.iter().find(|(f, _)| !group_by.contains(f)) {
+                return Err(GraphqlError::UngroupedField(f.to_string()));
+            }
+            Ok(Aggregate::Group { key, fields })
+        }
+        other => {
+            let function = AggregateFunction::from_selection(other).ok_or_else(|| {
+                GraphqlError::UnrecognizedField(
+                    format!("{entity_type}{AGGREGATE_SUFFIX}"),
+                    other.to_string(),
+                )
+            })?;
+            let fields = aggregate_fields(entity_type, &field.selection_set.node)?;
+            for (f, _) in fields.iter() {
+                let is_numeric = schema
+                    .parsed()
+                    .graphql_type(Some(entity_type), f)
+                    .map(|t| NUMERIC_SCALAR_TYPES.contains(t.as_str()))
+                    .unwrap_or(false);
+                if !is_numeric {
+                    return Err(GraphqlError::UnsupportedAggregate(
+                        function.to_string(),
+                        f.to_string(),
+                    ));
+                }
+            }
+            Ok(Aggregate::Function {
+                function,
+                key,
+                fields,
+            })
+        }
+    }
+}
+
+/// Parse the fields selected from an aggregate, along with the keys they're
+/// returned under.
+fn aggregate_fields(
+    entity_type: &String,
+    set: &SelectionSet,
+) -> GraphqlResult<Vec<(String, String)>> {
+    if set.items.is_empty() {
+        return Err(GraphqlError::SelectionNotSupported);
+    }
+
+    set.items
+        .iter()
+        .map(|item| match &item.node {
+            ParsedSelection::Field(f) => {
+                let name = f.node.name.node.to_string();
+                if !f.node.selection_set.node.items.is_empty() {
+                    return Err(GraphqlError::UnrecognizedField(
+                        entity_type.to_string(),
+                        name,
+                    ));
+                }
+                let key = f
+                    .node
+                    .alias
+                    .as_ref()
+                    .map(|a| a.to_string())
+                    .unwrap_or(name.clone());
+                Ok((name, key))
+            }
+            _ => Err(GraphqlError::SelectionNotSupported),
+        })
+        .collect()
+}
@@ -1,5 +1,9 @@
-use super::graphql::GraphqlError;
+use super::{dynamic::NUMERIC_SCALAR_TYPES, graphql::GraphqlError};
 use fuel_indexer_database::DbType;
+use fuel_indexer_lib::graphql::{
+    constants::TEXT_SEARCH_CONFIG,
+    types::{FromBlockCol, SearchCol, ToBlockCol},
+};
 use fuel_indexer_schema::db::tables::IndexerSchema;
 
 use async_graphql_value::{indexmap::IndexMap, Name, Value};
@@ -10,6 +14,8 @@ use std::fmt;
 pub struct QueryParams {
     pub filters: Vec<Filter>,
     pub sorts: Vec<Sort>,
+    pub ranks: Vec<Rank>,
+    pub group_by: Vec<String>,
     pub offset: Option<u64>,
     pub limit: Option<u64>,
 }
@@ -27,15 +33,40 @@ impl QueryParams {
                     fully_qualified_table_name: fully_qualified_table_name.clone(),
                     filter_type: f,
                 }),
-                ParamType::Sort(field, order) => self.sorts.push(Sort {
+                ParamType::Sort(field, order, numeric) => self.sorts.push(Sort {
                     fully_qualified_table_name: format!(
                         "{}.{}",
                         fully_qualified_table_name, field
                     ),
                     order,
+                    numeric,
                 }),
                 ParamType::Offset(n) => self.offset = Some(n),
                 ParamType::Limit(n) => self.limit = Some(n),
+                ParamType::Block(n) => self.filters.push(Filter {
+                    fully_qualified_table_name: fully_qualified_table_name.clone(),
+                    filter_type: FilterType::ValidAt(n),
+                }),
+                // Groups are returned in the order of their fields' values.
+                ParamType::GroupBy(field, numeric) => {
+                    let column = format!("{fully_qualified_table_name}.{field}");
+                    self.sorts.push(Sort {
+                        fully_qualified_table_name: column.clone(),
+                        order: SortOrder::Asc,
+                        numeric,
+                    });
+                    self.group_by.push(column);
+                }
+                ParamType::Search(query, fields) => {
+                    self.ranks.push(Rank {
+                        fully_qualified_table_name: fully_qualified_table_name.clone(),
+                        query: query.clone(),
+                    });
+                    self.filters.push(Filter {
+                        fully_qualified_table_name: fully_qualified_table_name.clone(),
+                        filter_type: FilterType::Search(query, fields),
+                    });
+                }
             }
         }
     }
@@ -63,12 +94,15 @@ impl QueryParams {
         let mut query_clause = "".to_string();
 
         match db_type {
-            DbType::Postgres => {
-                if !self.sorts.is_empty() {
+            DbType::Postgres | DbType::Sqlite => {
+                if self.is_ordered(db_type) {
+                    // The best matches of a full-text search come first, and any
+                    // sorts break ties between them.
                     let sort_expressions = self
-                        .sorts
+                        .ranks
                         .iter()
-                        .map(|s| format!("{} {}", s.fully_qualified_table_name, s.order))
+                        .filter_map(|r| r.to_sql(db_type))
+                        .chain(self.sorts.iter().map(|s| s.to_sql(db_type)))
                         .collect::<Vec<String>>()
                         .join(", ");
                     query_clause =
@@ -80,6 +114,27 @@ impl QueryParams {
 
         query_clause
     }
+
+    /// Return a string comprised of the columns by which records are grouped, if any.
+    pub(crate) fn get_grouping_expression(&self) -> String {
+        let mut query_clause = "".to_string();
+
+        if !self.group_by.is_empty() {
+            query_clause = [
+                query_clause,
+                "GROUP BY".to_string(),
+                self.group_by.join(", "),
+            ]
+            .join(" ");
+        }
+
+        query_clause
+    }
+
+    /// Whether the result set is given an order, either by sorts or by search rank.
+    pub(crate) fn is_ordered(&self, db_type: &DbType) -> bool {
+        !self.sorts.is_empty() || self.ranks.iter().any(|r| r.to_sql(db_type).is_some())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -99,15 +154,58 @@ impl Filter {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParamType {
     Filter(FilterType),
-    Sort(String, SortOrder),
+    /// Sorts by a field, along with whether the field is numeric.
+    Sort(String, SortOrder, bool),
     Offset(u64),
     Limit(u64),
+    Block(u64),
+    Search(String, Vec<String>),
+    /// Groups by a field, along with whether the field is numeric.
+    GroupBy(String, bool),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Sort {
     pub fully_qualified_table_name: String,
     pub order: SortOrder,
+    pub numeric: bool,
+}
+
+impl Sort {
+    pub fn to_sql(&self, db_type: &DbType) -> String {
+        let column = if self.numeric {
+            numeric_column(db_type, &self.fully_qualified_table_name)
+        } else {
+            self.fully_qualified_table_name.clone()
+        };
+        format!("{column} {}", self.order)
+    }
+}
+
+/// Returns the expression through which a numeric column is compared and sorted.
+///
+/// SQLite stores integers that may not fit into an `i64` (e.g., `ID`, `UInt8` and
+/// `UInt16`) as text, which would otherwise be compared as strings, so that '9' sorts
+/// after '10'. Values beyond the range of an `i64` are compared as floating point
+/// numbers there.
+pub(crate) fn numeric_column(db_type: &DbType, column: &str) -> String {
+    match db_type {
+        DbType::Postgres => column.to_string(),
+        DbType::Sqlite => format!("CAST({column} AS NUMERIC)"),
+    }
+}
+
+/// Whether a field holds numbers, and so has to be compared and sorted as a number.
+pub(crate) fn is_numeric_field(
+    schema: &IndexerSchema,
+    entity_type: Option<&String>,
+    field: &str,
+) -> bool {
+    schema
+        .parsed()
+        .graphql_type(entity_type, field)
+        .map(|t| t == "ID" || NUMERIC_SCALAR_TYPES.contains(t.as_str()))
+        .unwrap_or(false)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -116,6 +214,37 @@ pub enum SortOrder {
     Desc,
 }
 
+/// Represents the ranking of records by how well they match a full-text search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rank {
+    pub fully_qualified_table_name: String,
+    pub query: String,
+}
+
+impl Rank {
+    /// Returns the descending sort by search rank, if the database can rank matches.
+    pub fn to_sql(&self, db_type: &DbType) -> Option<String> {
+        match db_type {
+            DbType::Postgres => Some(format!(
+                "ts_rank({}.{}, {}) DESC",
+                self.fully_qualified_table_name,
+                SearchCol::to_lowercase_str(),
+                tsquery(&self.query)
+            )),
+            // SQLite has no text search type, so matches aren't ranked.
+            DbType::Sqlite => None,
+        }
+    }
+}
+
+/// Return the Postgres text search query for a user's search terms.
+fn tsquery(query: &str) -> String {
+    format!(
+        "websearch_to_tsquery('{TEXT_SEARCH_CONFIG}', '{}')",
+        query.replace('\'', "''")
+    )
+}
+
 impl fmt::Display for SortOrder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -158,6 +287,26 @@ impl fmt::Display for ParsedValue {
     }
 }
 
+impl ParsedValue {
+    /// Returns the value in the format required by the given database type.
+    ///
+    /// SQLite stores integers that may not fit into an `i64` as text, so numbers are
+    /// quoted there, to be tested for equality against such columns. Integer columns
+    /// convert them back to numbers. Ranges are compared as numbers instead, by
+    /// `Comparison::to_sql`.
+    pub fn to_sql(&self, db_type: &DbType) -> String {
+        match (db_type, self) {
+            (DbType::Sqlite, Self::BigNumber(_) | Self::Number(_)) => format!("'{self}'"),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Whether this value is a number.
+    fn is_number(&self) -> bool {
+        matches!(self, Self::BigNumber(_) | Self::Number(_))
+    }
+}
+
 /// Represents an operation through which records can be included or excluded.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilterType {
@@ -166,6 +315,10 @@ pub enum FilterType {
     Membership(Membership),
     NullValueCheck(NullValueCheck),
     LogicOp(LogicOp),
+    /// Selects the versions of historical entities that were valid at a block height.
+    ValidAt(u64),
+    /// Selects the records whose search fields match a full-text search query.
+    Search(String, Vec<String>),
 }
 
 /// Represents an operation in which a record is compared against a particular value.
@@ -180,6 +333,57 @@ pub enum Comparison {
     NotEquals(String, ParsedValue),
 }
 
+impl Comparison {
+    /// Returns a string to be used as part of a SQL database query.
+    ///
+    /// Columns are ordered against numbers as numbers, rather than as the text SQLite
+    /// may store them as.
+    fn to_sql(&self, fully_qualified_table: &str, db_type: &DbType) -> String {
+        let column = |field: &str, val: &ParsedValue| {
+            let column = format!("{fully_qualified_table}.{field}");
+            if val.is_number() {
+                numeric_column(db_type, &column)
+            } else {
+                column
+            }
+        };
+        // Numbers compared against numeric columns are left unquoted.
+        let value = |val: &ParsedValue| match (db_type, val.is_number()) {
+            (DbType::Sqlite, true) => val.to_string(),
+            _ => val.to_sql(db_type),
+        };
+
+        match self {
+            Comparison::Between(field, min, max) => {
+                format!(
+                    "{} BETWEEN {} AND {}",
+                    column(field, min),
+                    value(min),
+                    value(max)
+                )
+            }
+            Comparison::Equals(field, val) => {
+                format!("{fully_qualified_table}.{field} = {}", val.to_sql(db_type))
+            }
+            Comparison::NotEquals(field, val) => {
+                format!("{fully_qualified_table}.{field} <> {}", val.to_sql(db_type))
+            }
+            Comparison::Greater(field, val) => {
+                format!("{} > {}", column(field, val), value(val))
+            }
+            Comparison::GreaterEqual(field, val) => {
+                format!("{} >= {}", column(field, val), value(val))
+            }
+            Comparison::Less(field, val) => {
+                format!("{} < {}", column(field, val), value(val))
+            }
+            Comparison::LessEqual(field, val) => {
+                format!("{} <= {}", column(field, val), value(val))
+            }
+        }
+    }
+}
+
 /// Represents an operation in which a record's column value is checked for membership in a set.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Membership {
@@ -206,33 +410,46 @@ impl FilterType {
     /// Returns a string to be used as part of a SQL database query.
     pub fn to_sql(&self, fully_qualified_table: String, db_type: &DbType) -> String {
         match db_type {
-            DbType::Postgres => match self {
-                Self::Comparison(c) => match c {
-                    Comparison::Between(field, min, max) => {
-                        format!("{fully_qualified_table}.{field} BETWEEN {min} AND {max}",)
-                    }
-                    Comparison::Equals(field, val) => {
-                        format!("{fully_qualified_table}.{field} = {val}",)
-                    }
-                    Comparison::NotEquals(field, val) => {
-                        format!("{fully_qualified_table}.{field} <> {val}",)
-                    }
-                    Comparison::Greater(field, val) => {
-                        format!("{fully_qualified_table}.{field} > {val}",)
-                    }
-                    Comparison::GreaterEqual(field, val) => {
-                        format!("{fully_qualified_table}.{field} >= {val}",)
-                    }
-                    Comparison::Less(field, val) => {
-                        format!("{fully_qualified_table}.{field} < {val}",)
-                    }
-                    Comparison::LessEqual(field, val) => {
-                        format!("{fully_qualified_table}.{field} <= {val}",)
-                    }
-                },
+            DbType::Postgres | DbType::Sqlite => match self {
+                Self::Comparison(c) => c.to_sql(&fully_qualified_table, db_type),
                 Self::IdSelection(id) => {
-                    format!("{fully_qualified_table}.id = {id}")
+                    format!("{fully_qualified_table}.id = {}", id.to_sql(db_type))
+                }
+                Self::ValidAt(height) => {
+                    let (from_block, to_block) = (
+                        FromBlockCol::to_lowercase_str(),
+                        ToBlockCol::to_lowercase_str(),
+                    );
+                    format!(
+                        "({fully_qualified_table}.{from_block} <= {height} AND ({fully_qualified_table}.{to_block} IS NULL OR {fully_qualified_table}.{to_block} > {height}))"
+                    )
                 }
+                Self::Search(query, fields) => match db_type {
+                    DbType::Postgres => format!(
+                        "{fully_qualified_table}.{} @@ {}",
+                        SearchCol::to_lowercase_str(),
+                        tsquery(query)
+                    ),
+                    // SQLite has no text search type, so the query is matched against
+                    // each search field as a whole.
+                    DbType::Sqlite => {
+                        let pattern = query
+                            .replace('\\', "\\\\")
+                            .replace('\'', "''")
+                            .replace('%', "\\%")
+                            .replace('_', "\\_");
+                        format!(
+                            "({})",
+                            fields
+                                .iter()
+                                .map(|f| format!(
+                                    "{fully_qualified_table}.{f} LIKE '%{pattern}%' ESCAPE '\\'"
+                                ))
+                                .collect::<Vec<String>>()
+                                .join(" OR ")
+                        )
+                    }
+                },
                 Self::LogicOp(lo) => match lo {
                     LogicOp::And(r1, r2) => format!(
                         "({} AND {})",
@@ -255,7 +472,7 @@ impl FilterType {
                             "{fully_qualified_table}.{field} IN ({})",
                             member_set
                                 .iter()
-                                .map(|v| v.to_string())
+                                .map(|v| v.to_sql(db_type))
                                 .collect::<Vec<String>>()
                                 .join(", ")
                         )
@@ -265,7 +482,7 @@ impl FilterType {
                             "{fully_qualified_table}.{field} NOT IN ({})",
                             member_set
                                 .iter()
-                                .map(|v| v.to_string())
+                                .map(|v| v.to_sql(db_type))
                                 .collect::<Vec<String>>()
                                 .join(", ")
                         )
@@ -305,6 +522,12 @@ impl FilterType {
             FilterType::IdSelection(_) => Err(GraphqlError::UnsupportedNegation(
                 "ID selection".to_string(),
             )),
+            FilterType::ValidAt(_) => Err(GraphqlError::UnsupportedNegation(
+                "block selection".to_string(),
+            )),
+            FilterType::Search(..) => {
+                Err(GraphqlError::UnsupportedNegation("search".to_string()))
+            }
             FilterType::Comparison(c) => match c {
                 Comparison::Between(field, val1, val2) => {
                     Ok(FilterType::LogicOp(LogicOp::And(
@@ -404,18 +627,22 @@ pub fn parse_argument_into_param(
                         .graphql_type(entity_type, field.as_str())
                         .is_some()
                     {
+                        let numeric =
+                            is_numeric_field(schema, entity_type, field.as_str());
                         if let Value::Enum(sort_order) = sort_order {
                             match sort_order.as_str() {
                                 "asc" => {
                                     return Ok(ParamType::Sort(
                                         field.to_string(),
                                         SortOrder::Asc,
+                                        numeric,
                                     ))
                                 }
                                 "desc" => {
                                     return Ok(ParamType::Sort(
                                         field.to_string(),
                                         SortOrder::Desc,
+                                        numeric,
                                     ))
                                 }
                                 other => {
@@ -436,6 +663,40 @@ pub fn parse_argument_into_param(
                 Err(GraphqlError::UnsupportedValueType(value.to_string()))
             }
         }
+        "block" => {
+            let entity = entity_type.cloned().unwrap_or_default();
+            if !schema.parsed().is_historical_typedef(&entity) {
+                return Err(GraphqlError::NotHistorical(entity));
+            }
+
+            if let Value::Object(obj) = &value {
+                if let Some((_, Value::Number(number))) =
+                    obj.iter().find(|(k, _)| k.as_str() == "number")
+                {
+                    return match number.as_u64() {
+                        Some(height) => Ok(ParamType::Block(height)),
+                        None => {
+                            Err(GraphqlError::UnsupportedValueType(number.to_string()))
+                        }
+                    };
+                }
+            }
+
+            Err(GraphqlError::UnsupportedValueType(value.to_string()))
+        }
+        "search" => {
+            let entity = entity_type.cloned().unwrap_or_default();
+            let fields = schema.parsed().search_fields(&entity);
+            if fields.is_empty() {
+                return Err(GraphqlError::NotSearchable(entity));
+            }
+
+            if let Value::String(query) = value {
+                Ok(ParamType::Search(query, fields.to_vec()))
+            } else {
+                Err(GraphqlError::UnsupportedValueType(value.to_string()))
+            }
+        }
         "offset" => {
             if let Value::Number(number) = value {
                 if let Some(offset) = number.as_u64() {
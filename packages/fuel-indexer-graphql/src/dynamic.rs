@@ -15,7 +15,10 @@ use fuel_indexer_schema::db::tables::IndexerSchema;
 use lazy_static::lazy_static;
 use serde_json::Value;
 
-use crate::graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult};
+use crate::{
+    aggregates::AGGREGATE_SUFFIX,
+    graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult},
+};
 
 lazy_static! {
     /// Scalar types supported by the Fuel indexer. These should always stay up-to-date
@@ -58,7 +61,7 @@ lazy_static! {
 
     /// Scalar types that are represented by a numeric type. This ensures that the
     /// value type provided for a field filter matches the type of the scalar itself.
-    static ref NUMERIC_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
+    pub(crate) static ref NUMERIC_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
         "Int16",
         "Int4",
         "Int8",
@@ -204,6 +207,10 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
 
     let sort_enum = Enum::new("SortOrder").item("asc").item("desc");
 
+    // Historical entities can be queried as of a given block.
+    let block_filter = InputObject::new("BlockFilter")
+        .field(InputValue::new("number", TypeRef::named_nn(TypeRef::INT)));
+
     for (entity_type, field_map) in schema.parsed().object_field_mappings() {
         if IGNORED_ENTITY_TYPES.contains(&entity_type.as_str()) {
             continue;
@@ -341,7 +348,7 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             .fold(Object::new(entity_type.clone()), |obj, f| obj.field(f));
 
         // Create field for entity object and add it to root level query object.
-        let field = create_field_with_assoc_args(
+        let mut field = create_field_with_assoc_args(
             entity_type.to_string().to_lowercase(),
             TypeRef::named(obj.type_name()),
             &BaseType::Named(Name::new(obj.type_name())),
@@ -350,10 +357,112 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             &sorter_tracker,
             &sort_object_list,
         );
+        if schema.parsed().is_historical_typedef(entity_type) {
+            field = field.argument(InputValue::new(
+                "block",
+                TypeRef::named(block_filter.type_name()),
+            ));
+        }
+        if !schema.parsed().search_fields(entity_type).is_empty() {
+            field = field
+                .argument(InputValue::new("search", TypeRef::named(TypeRef::STRING)));
+        }
         if !SCALAR_TYPES.contains(&obj.type_name()) {
             query_root = query_root.field(field);
         }
 
+        // Entities can also be aggregated, as a whole or in groups of records that
+        // share the values of some of their fields.
+        if filter_tracker.contains_key(entity_type)
+            && !schema.parsed().is_enum_typedef(entity_type)
+            && !schema.parsed().is_virtual_typedef(entity_type)
+        {
+            let numeric_fields = field_map
+                .iter()
+                .filter(|(field_name, field_type)| {
+                    !IGNORED_ENTITY_FIELD_TYPES.contains(field_name.as_str())
+                        && NUMERIC_SCALAR_TYPES.contains(field_type.as_str())
+                })
+                .collect::<Vec<(&String, &String)>>();
+
+            let mut aggregate_obj = Object::new(format!("{entity_type}Aggregate"))
+                .field(
+                    create_introspection_field("count", TypeRef::named_nn(TypeRef::INT))
+                        .argument(InputValue::new(
+                            "distinct",
+                            TypeRef::named(object_field_enum.type_name()),
+                        )),
+                )
+                .field(create_introspection_field(
+                    "group",
+                    TypeRef::named(obj.type_name()),
+                ));
+
+            if !numeric_fields.is_empty() {
+                // Sums and extremes have the type of their field, while averages
+                // are fractional.
+                let numeric_obj = numeric_fields.iter().fold(
+                    Object::new(format!("{entity_type}NumericFields")),
+                    |obj, (field_name, field_type)| {
+                        obj.field(create_introspection_field(
+                            field_name.as_str(),
+                            TypeRef::named(field_type.as_str()),
+                        ))
+                    },
+                );
+                let avg_obj = numeric_fields.iter().fold(
+                    Object::new(format!("{entity_type}AvgFields")),
+                    |obj, (field_name, _)| {
+                        obj.field(create_introspection_field(
+                            field_name.as_str(),
+                            TypeRef::named(TypeRef::FLOAT),
+                        ))
+                    },
+                );
+
+                for function in ["sum", "min", "max"] {
+                    aggregate_obj = aggregate_obj.field(create_introspection_field(
+                        function,
+                        TypeRef::named(numeric_obj.type_name()),
+                    ));
+                }
+                aggregate_obj = aggregate_obj.field(create_introspection_field(
+                    "avg",
+                    TypeRef::named(avg_obj.type_name()),
+                ));
+
+                schema_builder = schema_builder.register(numeric_obj).register(avg_obj);
+            }
+
+            let mut aggregate_field = create_introspection_field(
+                format!("{}{AGGREGATE_SUFFIX}", entity_type.to_lowercase()),
+                TypeRef::named_nn_list_nn(aggregate_obj.type_name()),
+            )
+            .argument(InputValue::new(
+                "groupBy",
+                TypeRef::named_nn_list(object_field_enum.type_name()),
+            ));
+            if let Some(idx) = filter_tracker.get(entity_type) {
+                aggregate_field = aggregate_field.argument(InputValue::new(
+                    "filter",
+                    TypeRef::named(filter_object_list[*idx].type_name()),
+                ));
+            }
+            if schema.parsed().is_historical_typedef(entity_type) {
+                aggregate_field = aggregate_field.argument(InputValue::new(
+                    "block",
+                    TypeRef::named(block_filter.type_name()),
+                ));
+            }
+            if !schema.parsed().search_fields(entity_type).is_empty() {
+                aggregate_field = aggregate_field
+                    .argument(InputValue::new("search", TypeRef::named(TypeRef::STRING)));
+            }
+
+            query_root = query_root.field(aggregate_field);
+            schema_builder = schema_builder.register(aggregate_obj);
+        }
+
         schema_builder = schema_builder.register(obj).register(object_field_enum);
     }
 
@@ -373,11 +482,24 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
     }
 
     schema_builder = schema_builder.register(sort_enum);
+    schema_builder = schema_builder.register(block_filter);
     schema_builder = schema_builder.register(query_root);
 
     Ok(schema_builder.finish()?)
 }
 
+/// Create a field that only appears in introspection results.
+fn create_introspection_field(
+    field_name: impl Into<String>,
+    field_type_ref: TypeRef,
+) -> Field {
+    // Because the dynamic schema is set to only resolve introspection
+    // queries, we set the resolver to return a dummy value.
+    Field::new(field_name, field_type_ref, move |_ctx: ResolverContext| {
+        FieldFuture::new(async move { Ok(Some(FieldValue::value(1))) })
+    })
+}
+
 /// Create input values and objects that are used to build introspection information for a field.
 fn create_input_values_and_objects_for_field(
     field_name: String,
@@ -1,4 +1,5 @@
 use super::{
+    aggregates::{parse_aggregate_selection, Aggregate, AGGREGATE_SUFFIX},
     arguments::{parse_argument_into_param, ParamType, QueryParams},
     queries::{JoinCondition, QueryElement, QueryJoinNode, UserQuery},
 };
@@ -9,9 +10,9 @@ use async_graphql_parser::{
         FragmentSpread, OperationDefinition, OperationType, SelectionSet, TypeCondition,
     },
 };
-use fuel_indexer_database_types::DbType;
+use fuel_indexer_database_types::{history_table_name, DbType};
 use fuel_indexer_schema::db::tables::IndexerSchema;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 
 pub type GraphqlResult<T> = Result<T, GraphqlError>;
@@ -54,6 +55,14 @@ pub enum GraphqlError {
     UnorderedPaginatedQuery,
     #[error("Query error: {0:?}")]
     QueryError(String),
+    #[error("Type is not historical: {0:?}")]
+    NotHistorical(String),
+    #[error("Type has no search fields: {0:?}")]
+    NotSearchable(String),
+    #[error("Aggregate {0:?} is not supported on field {1:?}")]
+    UnsupportedAggregate(String, String),
+    #[error("Field is not grouped by: {0:?}")]
+    UngroupedField(String),
 }
 
 #[derive(Clone, Debug)]
@@ -65,6 +74,13 @@ pub enum Selection {
         alias: Option<String>,
     },
     Fragment(String),
+    /// Aggregates of the records of a top-level entity.
+    Aggregate {
+        name: String,
+        params: Vec<ParamType>,
+        aggregates: Vec<Aggregate>,
+        alias: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -98,6 +114,22 @@ impl Selections {
                         {
                             Some(typ) => typ,
                             None => {
+                                // Top-level entities can also be aggregated.
+                                let aggregated = name
+                                    .node
+                                    .as_str()
+                                    .strip_suffix(AGGREGATE_SUFFIX)
+                                    .filter(|_| field_type.is_none())
+                                    .and_then(|n| schema.parsed().graphql_type(None, n));
+                                if let Some(entity_type) = aggregated {
+                                    selections.push(parse_aggregate_selection(
+                                        schema,
+                                        entity_type,
+                                        &field.node,
+                                    )?);
+                                    continue;
+                                }
+
                                 if let Some(field_type) = field_type {
                                     return Err(GraphqlError::UnrecognizedField(
                                         field_type.into(),
@@ -123,6 +155,17 @@ impl Selections {
                         })
                         .collect::<Result<Vec<ParamType>, GraphqlError>>()?;
 
+                    // A query is resolved as of a single block, so only top-level
+                    // entities can be given one.
+                    if let Some(field_type) = field_type {
+                        if params.iter().any(|p| matches!(p, ParamType::Block(_))) {
+                            return Err(GraphqlError::UnrecognizedArgument(
+                                field_type.into(),
+                                "block".to_string(),
+                            ));
+                        }
+                    }
+
                     let sub_selections = Selections::new(
                         schema,
                         Some(subfield_type),
@@ -216,6 +259,7 @@ impl Selections {
                         alias: alias.clone(),
                     });
                 }
+                Selection::Aggregate { .. } => selections.push(selection.clone()),
             }
         }
 
@@ -299,7 +343,74 @@ impl Operation {
 
         let mut queries = Vec::new();
 
+        let history_tables = schema
+            .tables()
+            .iter()
+            .filter(|t| t.is_historical())
+            .map(|t| t.history_qualified_name())
+            .collect::<HashSet<String>>();
+
         for selection in selections.get_selections() {
+            // When a query is resolved as of a block, historical entities are read from
+            // their history tables, in which every version of each entity is kept.
+            let block = match &selection {
+                Selection::Field { params, .. } | Selection::Aggregate { params, .. } => {
+                    params.iter().find_map(|p| match p {
+                        ParamType::Block(height) => Some(*height),
+                        _ => None,
+                    })
+                }
+                Selection::Fragment(_) => None,
+            };
+
+            let table_name = |entity_name: &str| {
+                let table = schema
+                    .db_type()
+                    .table_name(&format!("{namespace}_{identifier}"), entity_name);
+                let history = history_table_name(&table);
+
+                if block.is_some() && history_tables.contains(&history) {
+                    history
+                } else {
+                    table
+                }
+            };
+
+            // Aggregates are computed over the top-level entity's table alone.
+            if let Selection::Aggregate {
+                name: entity_name,
+                params,
+                aggregates,
+                alias,
+            } = selection
+            {
+                let table = table_name(&entity_name);
+                let mut query_params = QueryParams::default();
+                query_params.add_params(params, table.clone());
+                // Search matches aren't ranked, since there are no records to order.
+                query_params.ranks.clear();
+
+                let elements = aggregates
+                    .into_iter()
+                    .flat_map(|a| a.into_elements(&table, schema.db_type()))
+                    .collect();
+
+                let entity_name = match block {
+                    Some(_) => history_table_name(&entity_name),
+                    None => entity_name,
+                };
+
+                queries.push(UserQuery {
+                    elements,
+                    joins: HashMap::new(),
+                    namespace_identifier: format!("{namespace}_{identifier}"),
+                    entity_name,
+                    query_params,
+                    alias,
+                });
+                continue;
+            }
+
             let mut elements: Vec<QueryElement> = Vec::new();
             let mut entities: Vec<String> = Vec::new();
 
@@ -339,10 +450,7 @@ impl Operation {
                 );
 
                 if !filters.is_empty() {
-                    query_params.add_params(
-                        filters,
-                        format!("{namespace}_{identifier}.{entity_name}"),
-                    );
+                    query_params.add_params(filters, table_name(&entity_name));
                 }
 
                 let mut last_seen_entities_len = entities.len();
@@ -376,14 +484,13 @@ impl Operation {
                             elements.push(QueryElement::Field {
                                 key: alias.unwrap_or(field_name.clone()),
                                 value: format!(
-                                    "{namespace}_{identifier}.{entity_name}.{field_name}"
+                                    "{}.{field_name}",
+                                    table_name(&entity_name)
                                 ),
                             });
                             if !filters.is_empty() {
-                                query_params.add_params(
-                                    filters,
-                                    format!("{namespace}_{identifier}.{entity_name}"),
-                                );
+                                query_params
+                                    .add_params(filters, table_name(&entity_name));
                             }
                         } else {
                             let mut new_entity = field_name.clone();
@@ -399,13 +506,9 @@ impl Operation {
                                     field_to_foreign_key.get(&field_name.to_lowercase())
                                 {
                                     let join_condition = JoinCondition {
-                                        referencing_key_table: format!(
-                                            "{namespace}_{identifier}.{entity_name}"
-                                        ),
+                                        referencing_key_table: table_name(&entity_name),
                                         referencing_key_col: field_name.clone(),
-                                        primary_key_table: format!(
-                                            "{namespace}_{identifier}.{foreign_key_table}"
-                                        ),
+                                        primary_key_table: table_name(foreign_key_table),
                                         primary_key_col: foreign_key_col.clone(),
                                     };
 
@@ -470,8 +573,8 @@ impl Operation {
                                     };
                                     if !filters.is_empty() {
                                         query_params.add_params(
-                                    filters,
-                                    format!("{namespace}_{identifier}.{foreign_key_table}"),
+                                            filters,
+                                            table_name(foreign_key_table),
                                         );
                                     }
                                 }
@@ -505,6 +608,31 @@ impl Operation {
                     ]);
                 }
 
+                // Historical entities that are joined are resolved as of the same block.
+                let (entity_name, alias) = match block {
+                    Some(height) => {
+                        let root_table = table_name(&entity_name);
+                        let mut joined = joins
+                            .keys()
+                            .filter(|t| **t != root_table && history_tables.contains(*t))
+                            .cloned()
+                            .collect::<Vec<String>>();
+                        joined.sort();
+
+                        for table in joined {
+                            query_params
+                                .add_params(vec![ParamType::Block(height)], table);
+                        }
+
+                        // Results are keyed by the entity, not by its history table.
+                        (
+                            history_table_name(&entity_name),
+                            alias.or(Some(entity_name)),
+                        )
+                    }
+                    None => (entity_name, alias),
+                };
+
                 let query = UserQuery {
                     elements,
                     joins,
@@ -843,4 +971,235 @@ type Tx {
         }];
         assert_eq!(expected, operation.parse(&schema));
     }
+
+    #[test]
+    fn test_query_as_of_block_reads_history_tables() {
+        let schema = r#"
+type Account @historical {
+    id: ID!
+    balance: UInt8!
+}
+
+type Transfer {
+    id: ID!
+    account: Account!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let query = GraphqlQueryBuilder::new(
+            &schema,
+            "query { account(block: { number: 10 }) { id balance } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let expected = "SELECT json_build_object('id', fuel_indexer_test_test_index.account_history.id, 'balance', fuel_indexer_test_test_index.account_history.balance) FROM fuel_indexer_test_test_index.account_history  WHERE  (fuel_indexer_test_test_index.account_history.from_block <= 10 AND (fuel_indexer_test_test_index.account_history.to_block IS NULL OR fuel_indexer_test_test_index.account_history.to_block > 10)) ";
+        assert_eq!(
+            query.as_sql(&schema, DbType::Postgres).unwrap(),
+            vec![expected.to_string()]
+        );
+
+        let not_historical = GraphqlQueryBuilder::new(
+            &schema,
+            "query { transfer(block: { number: 10 }) { id } }",
+        )
+        .unwrap()
+        .build();
+        assert!(matches!(
+            not_historical,
+            Err(GraphqlError::NotHistorical(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_query_filters_and_ranks_matches() {
+        let schema = r#"
+type Token {
+    id: ID!
+    name: Charfield! @search
+    supply: UInt8!
+}
+
+type Wallet {
+    id: ID!
+    label: Charfield!
+}
+"#;
+
+        let query = "query { token(search: \"gold coin\") { id name } }";
+        let table = "fuel_indexer_test_test_index.token";
+
+        let postgres = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let expected = format!("SELECT json_build_object('id', {table}.id, 'name', {table}.name) FROM {table}  WHERE  {table}.search_vector @@ websearch_to_tsquery('english', 'gold coin')  ORDER BY ts_rank({table}.search_vector, websearch_to_tsquery('english', 'gold coin')) DESC");
+        assert_eq!(
+            GraphqlQueryBuilder::new(&postgres, query)
+                .unwrap()
+                .build()
+                .unwrap()
+                .as_sql(&postgres, DbType::Postgres)
+                .unwrap(),
+            vec![expected]
+        );
+
+        let sqlite = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Sqlite,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+        let table = "fuel_indexer_test_test_index__token";
+
+        let expected = format!("SELECT json_object('id', {table}.id, 'name', {table}.name) FROM {table}  WHERE  ({table}.name LIKE '%gold coin%' ESCAPE '\\') ");
+        assert_eq!(
+            GraphqlQueryBuilder::new(&sqlite, query)
+                .unwrap()
+                .build()
+                .unwrap()
+                .as_sql(&sqlite, DbType::Sqlite)
+                .unwrap(),
+            vec![expected]
+        );
+
+        let unsearchable = GraphqlQueryBuilder::new(
+            &postgres,
+            "query { wallet(search: \"gold\") { id } }",
+        )
+        .unwrap()
+        .build();
+        assert!(matches!(unsearchable, Err(GraphqlError::NotSearchable(_))));
+    }
+
+    #[test]
+    fn test_aggregate_query_groups_and_aggregates_records() {
+        let schema = r#"
+type Transfer {
+    id: ID!
+    asset: AssetId!
+    amount: UInt8!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+        let table = "fuel_indexer_test_test_index.transfer";
+
+        let query = GraphqlQueryBuilder::new(
+            &schema,
+            "query { transferAggregate(filter: { amount: { gt: 10 } }, groupBy: [asset]) { group { asset } count senders: count(distinct: id) total: sum { amount } avg { amount } } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let expected = format!("SELECT json_build_object('group', json_build_object('asset', {table}.asset), 'count', COUNT(*), 'senders', COUNT(DISTINCT {table}.id), 'total', json_build_object('amount', SUM({table}.amount)), 'avg', json_build_object('amount', AVG({table}.amount))) FROM {table}  WHERE  {table}.amount > 10 GROUP BY {table}.asset  ORDER BY {table}.asset ASC");
+        assert_eq!(
+            query.as_sql(&schema, DbType::Postgres).unwrap(),
+            vec![expected]
+        );
+
+        let not_numeric = GraphqlQueryBuilder::new(
+            &schema,
+            "query { transferAggregate { sum { asset } } }",
+        )
+        .unwrap()
+        .build();
+        assert!(matches!(
+            not_numeric,
+            Err(GraphqlError::UnsupportedAggregate(_, _))
+        ));
+
+        let not_grouped = GraphqlQueryBuilder::new(
+            &schema,
+            "query { transferAggregate { group { asset } count } }",
+        )
+        .unwrap()
+        .build();
+        assert!(matches!(not_grouped, Err(GraphqlError::UngroupedField(_))));
+    }
+
+    #[test]
+    fn test_sqlite_compares_and_sorts_numeric_columns_as_numbers() {
+        let schema = r#"
+type Transfer {
+    id: ID!
+    asset: AssetId!
+    amount: UInt8!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Sqlite,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+        let table = "fuel_indexer_test_test_index__transfer";
+
+        let sql = GraphqlQueryBuilder::new(
+            &schema,
+            "query { transfer(filter: { amount: { gt: 9 } }, order: { amount: desc }) { id } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .as_sql(&schema, DbType::Sqlite)
+        .unwrap()
+        .join("");
+        assert!(sql.contains(&format!("WHERE  CAST({table}.amount AS NUMERIC) > 9 ")));
+        assert!(sql.contains(&format!("ORDER BY CAST({table}.amount AS NUMERIC) DESC")));
+
+        // Strings and equality are still compared against the stored text.
+        let sql = GraphqlQueryBuilder::new(
+            &schema,
+            "query { transfer(filter: { amount: { equals: 9 } }, order: { asset: asc }) { id } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .as_sql(&schema, DbType::Sqlite)
+        .unwrap()
+        .join("");
+        assert!(sql.contains(&format!("WHERE  {table}.amount = '9'")));
+        assert!(sql.contains(&format!("ORDER BY {table}.asset ASC")));
+
+        let sql = GraphqlQueryBuilder::new(
+            &schema,
+            "query { transferAggregate { max { amount } } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .as_sql(&schema, DbType::Sqlite)
+        .unwrap()
+        .join("");
+        assert!(sql.contains(&format!("MAX(CAST({table}.amount AS NUMERIC))")));
+    }
 }
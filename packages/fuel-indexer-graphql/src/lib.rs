@@ -1,3 +1,4 @@
+pub mod aggregates;
 pub mod arguments;
 pub mod dynamic;
 pub mod graphql;
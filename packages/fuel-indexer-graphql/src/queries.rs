@@ -3,6 +3,14 @@ use fuel_indexer_database::DbType;
 
 use std::{collections::HashMap, fmt::Display};
 
+/// Returns the name of the function that builds a JSON object from key-value pairs.
+fn json_object_fn(db_type: &DbType) -> &'static str {
+    match db_type {
+        DbType::Postgres => "json_build_object",
+        DbType::Sqlite => "json_object",
+    }
+}
+
 /// Represents a part of a user query. Each part can be a key-value pair
 /// describing an entity field and its corresponding database table, or a
 /// boundary for a nested object; opening boundaries contain a string to
@@ -72,7 +80,7 @@ impl UserQuery {
         // Different database solutions have unique ways of
         // constructing JSON-formatted queries and results.
         match db_type {
-            DbType::Postgres => {
+            DbType::Postgres | DbType::Sqlite => {
                 let selections = self.parse_query_elements_into_selections(db_type);
 
                 let selections_str = selections.join("");
@@ -111,8 +119,9 @@ impl UserQuery {
                 // If there's a limit applied to the query, then we need to create a query
                 // with pagination info. Otherwise, we can return the entire result set.
                 let query: String = if let Some(limit) = self.query_params.limit {
-                    // Paginated queries must have an order applied to at least one field.
-                    if !self.query_params.sorts.is_empty() {
+                    // Paginated queries must have an order applied to at least one field,
+                    // or be ranked by a full-text search.
+                    if self.query_params.is_ordered(db_type) {
                         self.create_query_with_pageinfo(
                             db_type,
                             selections_str,
@@ -124,12 +133,13 @@ impl UserQuery {
                     }
                 } else {
                     format!(
-                        "SELECT json_build_object({}) FROM {}.{} {} {} {}",
+                        "SELECT {}({}) FROM {} {} {}{} {}",
+                        json_object_fn(db_type),
                         selections_str,
-                        self.namespace_identifier,
-                        self.entity_name,
+                        db_type.table_name(&self.namespace_identifier, &self.entity_name),
                         joins_str,
                         self.query_params.get_filtering_expression(db_type),
+                        self.query_params.get_grouping_expression(),
                         self.query_params.get_ordering_modififer(db_type)
                     )
                 };
@@ -161,14 +171,13 @@ impl UserQuery {
                 let selection_cte = format!(
                     r#"WITH selection_cte AS (
                         SELECT json_build_object({}) AS {}
-                        FROM {}.{}
+                        FROM {}
                         {}
                         {}
                         {}),"#,
                     selections_str,
                     self.entity_name,
-                    self.namespace_identifier,
-                    self.entity_name,
+                    db_type.table_name(&self.namespace_identifier, &self.entity_name),
                     joins_str,
                     self.query_params.get_filtering_expression(db_type),
                     self.query_params.get_ordering_modififer(db_type),
@@ -200,6 +209,56 @@ impl UserQuery {
                     );"#
                 );
 
+                [selection_cte, total_count_cte, selection_query].join("\n")
+            }
+            db_type @ DbType::Sqlite => {
+                let json_selections_str =
+                    self.get_json_selections_from_cte(db_type).join(",");
+
+                let selection_cte = format!(
+                    r#"WITH selection_cte AS (
+                        SELECT json_object({}) AS {}
+                        FROM {}
+                        {}
+                        {}
+                        {}),"#,
+                    selections_str,
+                    self.entity_name,
+                    db_type.table_name(&self.namespace_identifier, &self.entity_name),
+                    joins_str,
+                    self.query_params.get_filtering_expression(db_type),
+                    self.query_params.get_ordering_modififer(db_type),
+                );
+
+                let total_count_cte =
+                    "total_count_cte AS (SELECT COUNT(*) as count FROM selection_cte)"
+                        .to_string();
+
+                let offset = self.query_params.offset.unwrap_or(0);
+                let alias = self.alias.clone().unwrap_or(self.entity_name.clone());
+
+                // SQLite has no boolean type, so `has_next_page` is built from JSON
+                // text. JSON stored in the CTE loses its JSON subtype, so items
+                // are passed through `json()` to keep them from being quoted.
+                let selection_query = format!(
+                    r#"SELECT json_object(
+                        'page_info', json_object(
+                            'has_next_page', json(CASE WHEN (({limit} + {offset}) < (SELECT count from total_count_cte)) THEN 'true' ELSE 'false' END),
+                            'limit', {limit},
+                            'offset', {offset},
+                            'pages', ((SELECT count from total_count_cte) + {limit} - 1) / {limit},
+                            'total_count', (SELECT count from total_count_cte)
+                        ),
+                        '{alias}', (
+                            SELECT json_group_array(json(item))
+                            FROM (
+                                SELECT {json_selections_str} AS item FROM selection_cte
+                                LIMIT {limit} OFFSET {offset}
+                            )
+                        )
+                    );"#
+                );
+
                 [selection_cte, total_count_cte, selection_query].join("\n")
             }
         }
@@ -215,7 +274,7 @@ impl UserQuery {
         let mut selections = Vec::new();
 
         match db_type {
-            DbType::Postgres => {
+            DbType::Postgres | DbType::Sqlite => {
                 while let Some(e) = peekable_elements.next() {
                     match e {
                         // Set the key for this JSON element to the name of the entity field
@@ -239,9 +298,8 @@ impl UserQuery {
 
                         // If the element is an object opener boundary, then we need to set a
                         // key so that the recipient can properly refer to the nested object.
-                        QueryElement::ObjectOpeningBoundary { key } => {
-                            selections.push(format!("'{key}', json_build_object("))
-                        }
+                        QueryElement::ObjectOpeningBoundary { key } => selections
+                            .push(format!("'{key}', {}(", json_object_fn(db_type))),
 
                         QueryElement::ObjectClosingBoundary => {
                             selections.push(")".to_string());
@@ -313,6 +371,8 @@ impl UserQuery {
                     }
                 }
             }
+            // The CTE holds the requested JSON object as is, so it's selected whole.
+            DbType::Sqlite => selections.push(self.entity_name.clone()),
         }
 
         selections
@@ -462,6 +522,8 @@ mod tests {
                     filter_type: FilterType::IdSelection(ParsedValue::Number(1)),
                 }],
                 sorts: vec![],
+                ranks: vec![],
+                group_by: vec![],
                 offset: None,
                 limit: None,
             },
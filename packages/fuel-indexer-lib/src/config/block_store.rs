@@ -0,0 +1,64 @@
+use crate::{
+    config::{Env, IndexerConfigResult},
+    defaults,
+};
+use serde::{Deserialize, Serialize};
+
+/// Local, on-disk store of the blocks fetched from the Fuel node, from which indexers
+/// are served when re-indexing, instead of fetching the blocks from the node again.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct BlockStoreConfig {
+    /// Whether fetched blocks are persisted to, and served from, the block store.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Directory in which blocks are stored.
+    #[serde(default = "default_path")]
+    pub path: String,
+
+    /// Height of the first block to persist.
+    #[serde(default)]
+    pub start_block: Option<u64>,
+
+    /// Height of the last block to persist.
+    #[serde(default)]
+    pub end_block: Option<u64>,
+
+    /// Number of blocks that stored pages are compacted into, once all of them are stored.
+    #[serde(default = "default_segment_size")]
+    pub segment_size: u64,
+}
+
+fn default_path() -> String {
+    defaults::BLOCK_STORE_PATH.to_string()
+}
+
+fn default_segment_size() -> u64 {
+    defaults::BLOCK_STORE_SEGMENT_SIZE
+}
+
+impl Default for BlockStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: defaults::BLOCK_STORE_ENABLED,
+            path: defaults::BLOCK_STORE_PATH.to_string(),
+            start_block: None,
+            end_block: None,
+            segment_size: defaults::BLOCK_STORE_SEGMENT_SIZE,
+        }
+    }
+}
+
+impl BlockStoreConfig {
+    /// Whether a block at the given height should be persisted.
+    pub fn contains(&self, height: u64) -> bool {
+        self.start_block.map_or(true, |start| height >= start)
+            && self.end_block.map_or(true, |end| height <= end)
+    }
+}
+
+impl Env for BlockStoreConfig {
+    fn inject_opt_env_vars(&mut self) -> IndexerConfigResult<()> {
+        Ok(())
+    }
+}
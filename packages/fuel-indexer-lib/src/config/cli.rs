@@ -60,13 +60,17 @@ pub struct IndexerArgs {
     pub graphql_api_port: String,
 
     /// Database type.
-    #[clap(long, help = "Database type.", default_value = defaults::DATABASE, value_parser(["postgres"]))]
+    #[clap(long, help = "Database type.", default_value = defaults::DATABASE, value_parser(["postgres", "sqlite"]))]
     pub database: String,
 
     /// Max body size for GraphQL API requests.
     #[clap(long, help = "Max body size for GraphQL API requests.", default_value_t = defaults::MAX_BODY_SIZE )]
     pub max_body_size: usize,
 
+    /// Max body size for indexer snapshot restore requests.
+    #[clap(long, help = "Max body size for indexer snapshot restore requests.", default_value_t = defaults::MAX_SNAPSHOT_SIZE )]
+    pub max_snapshot_size: usize,
+
     /// Postgres username.
     #[clap(long, help = "Postgres username.")]
     pub postgres_user: Option<String>,
@@ -87,6 +91,10 @@ pub struct IndexerArgs {
     #[clap(long, help = "Postgres port.")]
     pub postgres_port: Option<String>,
 
+    /// Path to the SQLite database file.
+    #[clap(long, help = "Path to the SQLite database file.")]
+    pub sqlite_path: Option<String>,
+
     /// Run database migrations before starting service.
     #[clap(long, help = "Run database migrations before starting service.")]
     pub run_migrations: bool,
@@ -176,6 +184,82 @@ pub struct IndexerArgs {
         help = "Whether to allow replacing an existing indexer. If not specified, an attempt to deploy over an existing indexer results in an error."
     )]
     pub replace_indexer: bool,
+
+    /// Maximum number of blocks an indexer handles before committing its database transaction.
+    #[clap(
+        long,
+        default_value_t = defaults::COMMIT_MAX_BLOCKS,
+        help = "Maximum number of blocks an indexer handles before committing its database transaction."
+    )]
+    pub commit_max_blocks: u64,
+
+    /// Maximum length of time (in seconds) an indexer handles blocks before committing its database transaction.
+    #[clap(
+        long,
+        help = "Maximum length of time (in seconds) an indexer handles blocks before committing its database transaction."
+    )]
+    pub commit_max_interval_secs: Option<u64>,
+
+    /// Do not commit after every block once an indexer has caught up with the chain.
+    #[clap(
+        long,
+        help = "Do not commit after every block once an indexer has caught up with the chain."
+    )]
+    pub no_commit_at_tip: bool,
+
+    /// Number of executors that concurrently backfill indexers whose manifest declares them partitionable.
+    #[clap(
+        long,
+        default_value_t = defaults::BACKFILL_PARTITIONS,
+        help = "Number of executors that concurrently backfill indexers whose manifest declares them partitionable."
+    )]
+    pub backfill_partitions: usize,
+
+    /// Number of seconds between runs that delete the expired rows of indexers with retention policies.
+    #[clap(
+        long,
+        default_value_t = defaults::PRUNE_INTERVAL_SECS,
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Number of seconds between runs that delete the expired rows of indexers with retention policies."
+    )]
+    pub prune_interval_secs: u64,
+
+    /// Persist blocks fetched from the Fuel node to a local block store, and serve indexers from it.
+    #[clap(
+        long,
+        help = "Persist blocks fetched from the Fuel node to a local block store, and serve indexers from it."
+    )]
+    pub block_store: bool,
+
+    /// Directory in which the local block store keeps its blocks.
+    #[clap(
+        long,
+        default_value = defaults::BLOCK_STORE_PATH,
+        help = "Directory in which the local block store keeps its blocks."
+    )]
+    pub block_store_path: String,
+
+    /// Height of the first block to persist in the local block store.
+    #[clap(
+        long,
+        help = "Height of the first block to persist in the local block store."
+    )]
+    pub block_store_start_block: Option<u64>,
+
+    /// Height of the last block to persist in the local block store.
+    #[clap(
+        long,
+        help = "Height of the last block to persist in the local block store."
+    )]
+    pub block_store_end_block: Option<u64>,
+
+    /// Number of blocks that the local block store compacts its pages into.
+    #[clap(
+        long,
+        default_value_t = defaults::BLOCK_STORE_SEGMENT_SIZE,
+        help = "Number of blocks that the local block store compacts its pages into."
+    )]
+    pub block_store_segment_size: u64,
 }
 
 #[derive(Debug, Parser, Clone)]
@@ -218,13 +302,17 @@ pub struct ApiServerArgs {
     pub graphql_api_port: String,
 
     /// Database type.
-    #[clap(long, help = "Database type.", default_value = defaults::DATABASE, value_parser(["postgres"]))]
+    #[clap(long, help = "Database type.", default_value = defaults::DATABASE, value_parser(["postgres", "sqlite"]))]
     pub database: String,
 
     /// Max body size for GraphQL API requests.
     #[clap(long, help = "Max body size for GraphQL API requests.", default_value_t = defaults::MAX_BODY_SIZE )]
     pub max_body_size: usize,
 
+    /// Max body size for indexer snapshot restore requests.
+    #[clap(long, help = "Max body size for indexer snapshot restore requests.", default_value_t = defaults::MAX_SNAPSHOT_SIZE )]
+    pub max_snapshot_size: usize,
+
     /// Run database migrations before starting service.
     #[clap(long, help = "Run database migrations before starting service.")]
     pub run_migrations: bool,
@@ -249,6 +337,10 @@ pub struct ApiServerArgs {
     #[clap(long, help = "Postgres port.")]
     pub postgres_port: Option<String>,
 
+    /// Path to the SQLite database file.
+    #[clap(long, help = "Path to the SQLite database file.")]
+    pub sqlite_path: Option<String>,
+
     /// Use Prometheus metrics reporting.
     #[clap(long, help = "Use Prometheus metrics reporting.")]
     pub metrics: bool,
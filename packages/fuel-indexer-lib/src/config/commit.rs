@@ -0,0 +1,140 @@
+use crate::{
+    config::{Env, IndexerConfigResult},
+    defaults,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Determines how often an indexer commits the database transaction in which its
+/// handlers run, independently of how many blocks are fetched from the node at once.
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct CommitPolicy {
+    /// Commit once this many blocks have been handled since the last commit.
+    #[serde(default = "default_max_blocks")]
+    pub max_blocks: u64,
+
+    /// Commit once this many seconds have passed since the last commit.
+    #[serde(default)]
+    pub max_interval_secs: Option<u64>,
+
+    /// Commit after every block once the indexer has caught up with the chain tip.
+    #[serde(default = "default_commit_at_tip")]
+    pub commit_at_tip: bool,
+}
+
+fn default_max_blocks() -> u64 {
+    defaults::COMMIT_MAX_BLOCKS
+}
+
+fn default_commit_at_tip() -> bool {
+    defaults::COMMIT_AT_TIP
+}
+
+impl Default for CommitPolicy {
+    fn default() -> Self {
+        Self {
+            max_blocks: defaults::COMMIT_MAX_BLOCKS,
+            max_interval_secs: None,
+            commit_at_tip: defaults::COMMIT_AT_TIP,
+        }
+    }
+}
+
+impl CommitPolicy {
+    /// Whether the open transaction should be committed, given the number of blocks
+    /// handled and the time elapsed since the last commit.
+    pub fn should_commit(
+        &self,
+        uncommitted_blocks: u64,
+        elapsed: Duration,
+        at_tip: bool,
+    ) -> bool {
+        if uncommitted_blocks == 0 {
+            return false;
+        }
+
+        (at_tip && self.commit_at_tip)
+            || uncommitted_blocks >= self.max_blocks
+            || self
+                .max_interval_secs
+                .map(|secs| elapsed >= Duration::from_secs(secs))
+                .unwrap_or(false)
+    }
+
+    /// Maximum number of blocks to handle before the open transaction may have to be
+    /// committed, given the number of blocks already handled since the last commit.
+    pub fn remaining_blocks(&self, uncommitted_blocks: u64, at_tip: bool) -> usize {
+        if at_tip && self.commit_at_tip {
+            return 1;
+        }
+
+        self.max_blocks.saturating_sub(uncommitted_blocks).max(1) as usize
+    }
+}
+
+impl Env for CommitPolicy {
+    fn inject_opt_env_vars(&mut self) -> IndexerConfigResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_blocks: u64, max_interval_secs: Option<u64>) -> CommitPolicy {
+        CommitPolicy {
+            max_blocks,
+            max_interval_secs,
+            commit_at_tip: true,
+        }
+    }
+
+    #[test]
+    fn test_should_commit_never_commits_without_uncommitted_blocks() {
+        let policy = policy(1, Some(0));
+
+        assert!(!policy.should_commit(0, Duration::from_secs(100), true));
+    }
+
+    #[test]
+    fn test_should_commit_after_max_blocks() {
+        let policy = policy(10, None);
+
+        assert!(!policy.should_commit(9, Duration::from_secs(100), false));
+        assert!(policy.should_commit(10, Duration::ZERO, false));
+        assert!(policy.should_commit(11, Duration::ZERO, false));
+    }
+
+    #[test]
+    fn test_should_commit_after_max_interval() {
+        let policy = policy(10, Some(5));
+
+        assert!(!policy.should_commit(1, Duration::from_secs(4), false));
+        assert!(policy.should_commit(1, Duration::from_secs(5), false));
+    }
+
+    #[test]
+    fn test_should_commit_at_tip_only_when_enabled() {
+        let mut policy = policy(10, None);
+
+        assert!(policy.should_commit(1, Duration::ZERO, true));
+
+        policy.commit_at_tip = false;
+        assert!(!policy.should_commit(1, Duration::ZERO, true));
+    }
+
+    #[test]
+    fn test_remaining_blocks() {
+        let mut policy = policy(10, None);
+
+        assert_eq!(policy.remaining_blocks(0, false), 10);
+        assert_eq!(policy.remaining_blocks(4, false), 6);
+        assert_eq!(policy.remaining_blocks(10, false), 1);
+        assert_eq!(policy.remaining_blocks(15, false), 1);
+        assert_eq!(policy.remaining_blocks(0, true), 1);
+
+        policy.commit_at_tip = false;
+        assert_eq!(policy.remaining_blocks(4, true), 6);
+    }
+}
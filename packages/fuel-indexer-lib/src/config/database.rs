@@ -33,6 +33,13 @@ pub enum DatabaseConfig {
         /// Postgres database.
         database: String,
 
+        /// Verbose output for database operations.
+        verbose: String,
+    },
+    Sqlite {
+        /// Path to the SQLite database file.
+        path: String,
+
         /// Verbose output for database operations.
         verbose: String,
     },
@@ -68,6 +75,11 @@ impl Env for DatabaseConfig {
                     *database = std::env::var(trim_opt_env_key(database))?;
                 }
             }
+            DatabaseConfig::Sqlite { path, .. } => {
+                if is_opt_env_var(path) {
+                    *path = std::env::var(trim_opt_env_key(path))?;
+                }
+            }
         }
         Ok(())
     }
@@ -91,6 +103,9 @@ impl ToString for DatabaseConfig {
                     .join("&");
                 format!("postgres://{user}:{password}@{host}:{port}/{database}?{params}")
             }
+            DatabaseConfig::Sqlite { path, verbose } => {
+                format!("sqlite://{path}?verbose={verbose}")
+            }
         }
     }
 }
@@ -116,6 +131,13 @@ impl Debug for DatabaseConfig {
                     .field("verbose", &verbose)
                     .finish();
             }
+            DatabaseConfig::Sqlite { path, verbose } => {
+                let _ = f
+                    .debug_struct("SqliteConfig")
+                    .field("path", &path)
+                    .field("verbose", &verbose)
+                    .finish();
+            }
         }
 
         Ok(())
@@ -171,6 +193,21 @@ impl FromStr for DatabaseConfig {
                     verbose: value,
                 })
             }
+            "sqlite" => {
+                // Everything between the scheme and the query string is the path, so
+                // that both `sqlite://indexer.db` and `sqlite:///tmp/indexer.db` work.
+                let path = db_url
+                    .trim_start_matches("sqlite:")
+                    .trim_start_matches("//")
+                    .split('?')
+                    .next()
+                    .unwrap_or_default();
+
+                Ok(DatabaseConfig::Sqlite {
+                    path: path.to_string(),
+                    verbose: value,
+                })
+            }
             _ => {
                 unimplemented!("Unsupported database. Please check your database URL.")
             }
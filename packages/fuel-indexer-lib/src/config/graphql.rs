@@ -21,6 +21,10 @@ pub struct GraphQLConfig {
     /// Max body size for GraphQL API requests.
     #[serde(default)]
     pub max_body_size: usize,
+
+    /// Max body size for indexer snapshot restore requests.
+    #[serde(default)]
+    pub max_snapshot_size: usize,
 }
 
 impl std::string::ToString for GraphQLConfig {
@@ -44,6 +48,7 @@ impl Default for GraphQLConfig {
             host: defaults::GRAPHQL_API_HOST.into(),
             port: defaults::GRAPHQL_API_PORT.into(),
             max_body_size: defaults::MAX_BODY_SIZE,
+            max_snapshot_size: defaults::MAX_SNAPSHOT_SIZE,
         }
     }
 }
@@ -1,6 +1,8 @@
 pub mod auth;
+pub mod block_store;
 pub mod cli;
 pub mod client;
+pub mod commit;
 pub mod database;
 pub mod graphql;
 pub mod limit;
@@ -9,8 +11,10 @@ pub mod utils;
 pub use crate::{
     config::{
         auth::{AuthenticationConfig, AuthenticationStrategy},
+        block_store::BlockStoreConfig,
         cli::{ApiServerArgs, IndexerArgs},
         client::FuelClientConfig,
+        commit::CommitPolicy,
         database::DatabaseConfig,
         graphql::GraphQLConfig,
         limit::RateLimitConfig,
@@ -36,6 +40,8 @@ pub enum EnvVar {
     PostgresPort,
     #[strum(serialize = "POSTGRES_USER")]
     PostgresUser,
+    #[strum(serialize = "SQLITE_PATH")]
+    SqlitePath,
     #[strum(serialize = "JWT_SECRET")]
     JwtSecret,
 }
@@ -63,6 +69,8 @@ pub enum IndexerConfigError {
     ParseError(#[from] url::ParseError),
     #[error("File IO error: {0:?}")]
     FileIoError(#[from] std::io::Error),
+    #[error("Invalid value for '{0}' in config file, expected {1}.")]
+    InvalidConfigValue(String, &'static str),
 }
 
 /// Used to inject environment variables into configuration.
@@ -83,11 +91,13 @@ impl Default for IndexerArgs {
             graphql_api_port: defaults::GRAPHQL_API_PORT.to_string(),
             database: defaults::DATABASE.to_string(),
             max_body_size: defaults::MAX_BODY_SIZE,
+            max_snapshot_size: defaults::MAX_SNAPSHOT_SIZE,
             postgres_user: Some(defaults::POSTGRES_USER.to_string()),
             postgres_database: Some(defaults::POSTGRES_DATABASE.to_string()),
             postgres_password: None,
             postgres_host: Some(defaults::POSTGRES_HOST.to_string()),
             postgres_port: Some(defaults::POSTGRES_PORT.to_string()),
+            sqlite_path: None,
             run_migrations: defaults::RUN_MIGRATIONS,
             metrics: defaults::USE_METRICS,
             stop_idle_indexers: defaults::STOP_IDLE_INDEXERS,
@@ -104,6 +114,16 @@ impl Default for IndexerArgs {
             rate_limit_request_count: Some(defaults::RATE_LIMIT_REQUEST_COUNT),
             rate_limit_window_size: Some(defaults::RATE_LIMIT_WINDOW_SIZE),
             replace_indexer: defaults::REPLACE_INDEXER,
+            commit_max_blocks: defaults::COMMIT_MAX_BLOCKS,
+            commit_max_interval_secs: None,
+            no_commit_at_tip: !defaults::COMMIT_AT_TIP,
+            backfill_partitions: defaults::BACKFILL_PARTITIONS,
+            prune_interval_secs: defaults::PRUNE_INTERVAL_SECS,
+            block_store: defaults::BLOCK_STORE_ENABLED,
+            block_store_path: defaults::BLOCK_STORE_PATH.to_string(),
+            block_store_start_block: None,
+            block_store_end_block: None,
+            block_store_segment_size: defaults::BLOCK_STORE_SEGMENT_SIZE,
         }
     }
 }
@@ -131,6 +151,22 @@ pub struct IndexerConfig {
     pub authentication: AuthenticationConfig,
     pub rate_limit: RateLimitConfig,
     pub replace_indexer: bool,
+    #[serde(default)]
+    pub commit_policy: CommitPolicy,
+    #[serde(default)]
+    pub block_store: BlockStoreConfig,
+    #[serde(default = "default_backfill_partitions")]
+    pub backfill_partitions: usize,
+    #[serde(default = "default_prune_interval_secs")]
+    pub prune_interval_secs: u64,
+}
+
+fn default_backfill_partitions() -> usize {
+    defaults::BACKFILL_PARTITIONS
+}
+
+fn default_prune_interval_secs() -> u64 {
+    defaults::PRUNE_INTERVAL_SECS
 }
 
 impl From<IndexerArgs> for IndexerConfig {
@@ -169,6 +205,12 @@ impl From<IndexerArgs> for IndexerConfig {
                 }),
                 verbose: args.verbose.to_string(),
             },
+            "sqlite" => DatabaseConfig::Sqlite {
+                path: args.sqlite_path.unwrap_or_else(|| {
+                    env_or_default(EnvVar::SqlitePath, defaults::SQLITE_PATH.to_string())
+                }),
+                verbose: args.verbose.to_string(),
+            },
             _ => {
                 panic!("Unrecognized database type in options.");
             }
@@ -189,6 +231,7 @@ impl From<IndexerArgs> for IndexerConfig {
                 host: args.graphql_api_host,
                 port: args.graphql_api_port,
                 max_body_size: args.max_body_size,
+                max_snapshot_size: args.max_snapshot_size,
             },
             metrics: args.metrics,
             stop_idle_indexers: args.stop_idle_indexers,
@@ -208,6 +251,20 @@ impl From<IndexerArgs> for IndexerConfig {
                 window_size: args.rate_limit_window_size,
             },
             replace_indexer: args.replace_indexer,
+            commit_policy: CommitPolicy {
+                max_blocks: args.commit_max_blocks,
+                max_interval_secs: args.commit_max_interval_secs,
+                commit_at_tip: !args.no_commit_at_tip,
+            },
+            block_store: BlockStoreConfig {
+                enabled: args.block_store,
+                path: args.block_store_path,
+                start_block: args.block_store_start_block,
+                end_block: args.block_store_end_block,
+                segment_size: args.block_store_segment_size,
+            },
+            backfill_partitions: args.backfill_partitions,
+            prune_interval_secs: args.prune_interval_secs,
         };
 
         config
@@ -254,6 +311,12 @@ impl From<ApiServerArgs> for IndexerConfig {
                 }),
                 verbose: args.verbose.to_string(),
             },
+            "sqlite" => DatabaseConfig::Sqlite {
+                path: args.sqlite_path.unwrap_or_else(|| {
+                    env_or_default(EnvVar::SqlitePath, defaults::SQLITE_PATH.to_string())
+                }),
+                verbose: args.verbose.to_string(),
+            },
             _ => {
                 panic!("Unrecognized database type in options.");
             }
@@ -274,6 +337,7 @@ impl From<ApiServerArgs> for IndexerConfig {
                 host: args.graphql_api_host,
                 port: args.graphql_api_port,
                 max_body_size: args.max_body_size,
+                max_snapshot_size: args.max_snapshot_size,
             },
             metrics: args.metrics,
             stop_idle_indexers: defaults::STOP_IDLE_INDEXERS,
@@ -293,6 +357,10 @@ impl From<ApiServerArgs> for IndexerConfig {
                 window_size: args.rate_limit_window_size,
             },
             replace_indexer: defaults::REPLACE_INDEXER,
+            commit_policy: CommitPolicy::default(),
+            block_store: BlockStoreConfig::default(),
+            backfill_partitions: defaults::BACKFILL_PARTITIONS,
+            prune_interval_secs: defaults::PRUNE_INTERVAL_SECS,
         };
 
         config
@@ -368,6 +436,28 @@ impl IndexerConfig {
         let database_config_key = serde_yaml::Value::String("database".into());
         let auth_config_key = serde_yaml::Value::String("authentication".into());
         let rate_limit_config_key = serde_yaml::Value::String("rate_limit".into());
+        let commit_policy_config_key = serde_yaml::Value::String("commit_policy".into());
+        let block_store_config_key = serde_yaml::Value::String("block_store".into());
+        let backfill_partitions_key =
+            serde_yaml::Value::String("backfill_partitions".into());
+        let prune_interval_secs_key =
+            serde_yaml::Value::String("prune_interval_secs".into());
+
+        if let Some(backfill_partitions) = content.get(backfill_partitions_key) {
+            config.backfill_partitions = backfill_partitions.as_u64().unwrap() as usize;
+        }
+
+        if let Some(prune_interval_secs) = content.get(prune_interval_secs_key) {
+            config.prune_interval_secs = prune_interval_secs
+                .as_u64()
+                .filter(|secs| *secs > 0)
+                .ok_or_else(|| {
+                    IndexerConfigError::InvalidConfigValue(
+                        "prune_interval_secs".into(),
+                        "a positive number of seconds",
+                    )
+                })?;
+        }
 
         if let Some(section) = content.get(fuel_config_key) {
             let fuel_node_host = section.get(&serde_yaml::Value::String("host".into()));
@@ -400,6 +490,19 @@ impl IndexerConfig {
                 config.graphql_api.max_body_size =
                     max_body_size.as_u64().unwrap() as usize;
             }
+
+            let max_snapshot_size =
+                section.get(&serde_yaml::Value::String("max_snapshot_size".into()));
+
+            if let Some(max_snapshot_size) = max_snapshot_size {
+                config.graphql_api.max_snapshot_size =
+                    max_snapshot_size.as_u64().ok_or_else(|| {
+                        IndexerConfigError::InvalidConfigValue(
+                            "max_snapshot_size".to_string(),
+                            "a number of bytes",
+                        )
+                    })? as usize;
+            }
         }
 
         if let Some(section) = content.get(database_config_key) {
@@ -451,6 +554,21 @@ impl IndexerConfig {
                     verbose: config.verbose.to_string(),
                 };
             }
+
+            if let Some(sqlite_section) = section.get("sqlite") {
+                let mut sqlite_path = defaults::SQLITE_PATH.to_string();
+
+                let sqlite_path_value =
+                    sqlite_section.get(&serde_yaml::Value::String("path".into()));
+                if let Some(sqlite_path_value) = sqlite_path_value {
+                    sqlite_path = sqlite_path_value.as_str().unwrap().to_string();
+                }
+
+                config.database = DatabaseConfig::Sqlite {
+                    path: sqlite_path,
+                    verbose: config.verbose.to_string(),
+                };
+            }
         }
 
         if let Some(section) = content.get(auth_config_key) {
@@ -499,6 +617,72 @@ impl IndexerConfig {
             }
         }
 
+        if let Some(section) = content.get(commit_policy_config_key) {
+            let max_blocks = section.get(&serde_yaml::Value::String("max_blocks".into()));
+            if let Some(max_blocks) = max_blocks {
+                config.commit_policy.max_blocks =
+                    max_blocks.as_u64().ok_or_else(|| {
+                        IndexerConfigError::InvalidConfigValue(
+                            "commit_policy.max_blocks".into(),
+                            "a non-negative integer",
+                        )
+                    })?;
+            }
+
+            let max_interval_secs =
+                section.get(&serde_yaml::Value::String("max_interval_secs".into()));
+            if let Some(max_interval_secs) = max_interval_secs {
+                config.commit_policy.max_interval_secs =
+                    Some(max_interval_secs.as_u64().ok_or_else(|| {
+                        IndexerConfigError::InvalidConfigValue(
+                            "commit_policy.max_interval_secs".into(),
+                            "a non-negative integer",
+                        )
+                    })?);
+            }
+
+            let commit_at_tip =
+                section.get(&serde_yaml::Value::String("commit_at_tip".into()));
+            if let Some(commit_at_tip) = commit_at_tip {
+                config.commit_policy.commit_at_tip =
+                    commit_at_tip.as_bool().ok_or_else(|| {
+                        IndexerConfigError::InvalidConfigValue(
+                            "commit_policy.commit_at_tip".into(),
+                            "a boolean",
+                        )
+                    })?;
+            }
+        }
+
+        if let Some(section) = content.get(block_store_config_key) {
+            let enabled = section.get(&serde_yaml::Value::String("enabled".into()));
+            if let Some(enabled) = enabled {
+                config.block_store.enabled = enabled.as_bool().unwrap();
+            }
+
+            let path = section.get(&serde_yaml::Value::String("path".into()));
+            if let Some(path) = path {
+                config.block_store.path = path.as_str().unwrap().to_string();
+            }
+
+            let start_block =
+                section.get(&serde_yaml::Value::String("start_block".into()));
+            if let Some(start_block) = start_block {
+                config.block_store.start_block = Some(start_block.as_u64().unwrap());
+            }
+
+            let end_block = section.get(&serde_yaml::Value::String("end_block".into()));
+            if let Some(end_block) = end_block {
+                config.block_store.end_block = Some(end_block.as_u64().unwrap());
+            }
+
+            let segment_size =
+                section.get(&serde_yaml::Value::String("segment_size".into()));
+            if let Some(segment_size) = segment_size {
+                config.block_store.segment_size = segment_size.as_u64().unwrap();
+            }
+        }
+
         config.inject_opt_env_vars()?;
 
         Ok(config)
@@ -541,8 +725,12 @@ mod tests {
         assert!(!config.run_migrations);
         assert!(!config.verbose);
 
-        let DatabaseConfig::Postgres { verbose, .. } = config.database;
-        assert_eq!(verbose.as_str(), "false");
+        match config.database {
+            DatabaseConfig::Postgres { verbose, .. } => {
+                assert_eq!(verbose.as_str(), "false");
+            }
+            _ => panic!("Expected a Postgres database config."),
+        }
 
         fs::remove_file(file_path).unwrap();
     }
@@ -602,6 +790,76 @@ mod tests {
 
                 fs::remove_file(file_path).unwrap();
             }
+            _ => panic!("Expected a Postgres database config."),
+        }
+    }
+
+    #[test]
+    fn test_indexer_config_can_use_sqlite() {
+        let file_path: &str = "foo4.yaml";
+        let config_str = r#"
+        ## Database configuration options.
+        #
+        database:
+          sqlite:
+            path: /tmp/indexer.db
+
+        "#;
+
+        fs::write(file_path, config_str).unwrap();
+        let config = IndexerConfig::from_file(file_path).unwrap();
+        fs::remove_file(file_path).unwrap();
+
+        match config.database {
+            DatabaseConfig::Sqlite { ref path, .. } => {
+                assert_eq!(path, "/tmp/indexer.db");
+            }
+            _ => panic!("Expected a SQLite database config."),
+        }
+
+        let url = config.database.to_string();
+        assert_eq!(url, "sqlite:///tmp/indexer.db?verbose=false");
+
+        match url.parse::<DatabaseConfig>().unwrap() {
+            DatabaseConfig::Sqlite { path, .. } => assert_eq!(path, "/tmp/indexer.db"),
+            _ => panic!("Expected a SQLite database config."),
+        }
+    }
+
+    #[test]
+    fn test_indexer_config_rejects_invalid_commit_policy_values() {
+        let file_path: &str = "foo5.yaml";
+        let config_str = r#"
+        commit_policy:
+          max_blocks: lots
+        "#;
+
+        fs::write(file_path, config_str).unwrap();
+        let result = IndexerConfig::from_file(file_path);
+        fs::remove_file(file_path).unwrap();
+
+        match result {
+            Err(IndexerConfigError::InvalidConfigValue(key, _)) => {
+                assert_eq!(key, "commit_policy.max_blocks");
+            }
+            _ => panic!("Expected an invalid config value error."),
+        }
+    }
+
+    #[test]
+    fn test_indexer_config_rejects_invalid_prune_intervals() {
+        for (i, value) in ["0", "often", "-5"].iter().enumerate() {
+            let file_path = format!("foo_prune_{i}.yaml");
+            fs::write(&file_path, format!("prune_interval_secs: {value}\n")).unwrap();
+            let result = IndexerConfig::from_file(&file_path);
+            fs::remove_file(&file_path).unwrap();
+
+            match result {
+                Err(IndexerConfigError::InvalidConfigValue(key, _)) => {
+                    assert_eq!(key, "prune_interval_secs");
+                }
+                _ => panic!("Expected an invalid config value error for {value}."),
+            }
         }
     }
 }
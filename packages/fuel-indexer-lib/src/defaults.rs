@@ -28,6 +28,9 @@ pub const POSTGRES_PORT: &str = "5432";
 /// Postgres password.
 pub const POSTGRES_PASSWORD: &str = "postgres";
 
+/// Path to the SQLite database file.
+pub const SQLITE_PATH: &str = "fuel-indexer.db";
+
 /// Number of failed calls to allow before stopping the indexer.
 pub const INDEXER_FAILED_CALLS: usize = 10;
 
@@ -37,6 +40,9 @@ pub const STOP_IDLE_INDEXERS: bool = false;
 /// Max body size for GraphQL API requests (5 MB).
 pub const MAX_BODY_SIZE: usize = 5242880;
 
+/// Max body size for indexer snapshot restore requests (1 GB).
+pub const MAX_SNAPSHOT_SIZE: usize = 1073741824;
+
 /// Size of web-API-to-executor-service channel.
 pub const SERVICE_REQUEST_CHANNEL_SIZE: usize = 100;
 
@@ -94,6 +100,45 @@ pub const VERBOSE_DB_LOGGING: &str = "false";
 /// Amount of blocks to return in a GraphQL page.
 pub const NODE_GRAPHQL_PAGE_SIZE: usize = 10;
 
+/// Number of recently fetched blocks kept in memory per Fuel node, and shared by all indexers on that node.
+pub const SHARED_BLOCK_WINDOW_SIZE: usize = 1000;
+
+/// Maximum number of blocks an indexer handles before committing its database transaction.
+pub const COMMIT_MAX_BLOCKS: u64 = 10;
+
+/// Commit an indexer's database transaction after every block once it has caught up with the chain.
+pub const COMMIT_AT_TIP: bool = true;
+
+/// Number of executors that concurrently backfill a partitionable indexer.
+pub const BACKFILL_PARTITIONS: usize = 4;
+
+/// Number of seconds between runs that delete the expired rows of indexers with retention policies.
+pub const PRUNE_INTERVAL_SECS: u64 = 600;
+
+/// Maximum number of rows deleted by each statement of a pruning run.
+pub const PRUNE_BATCH_SIZE: usize = 1000;
+
+/// Minimum number of blocks backfilled by each executor of a partitionable indexer.
+pub const MIN_BACKFILL_PARTITION_SIZE: u64 = 1000;
+
+/// Persist blocks fetched from the Fuel node to a local block store.
+pub const BLOCK_STORE_ENABLED: bool = false;
+
+/// Directory in which the local block store keeps its blocks.
+pub const BLOCK_STORE_PATH: &str = "fuel-indexer-blocks";
+
+/// Number of blocks that the local block store compacts its pages into.
+pub const BLOCK_STORE_SEGMENT_SIZE: u64 = 1000;
+
+/// Number of lines logged by an indexer that are kept for retrieval through the API.
+pub const INDEXER_LOG_CAPACITY: usize = 10000;
+
+/// Maximum number of log lines returned per request to the logs API.
+pub const INDEXER_LOG_PAGE_SIZE: usize = 100;
+
+/// Number of records read from the database at a time when exporting an entity.
+pub const EXPORT_BATCH_SIZE: usize = 1000;
+
 /// Start a local Fuel node.
 pub const LOCAL_FUEL_NODE: bool = false;
 
@@ -1,8 +1,47 @@
 use lazy_static::lazy_static;
 use std::collections::HashSet;
 
+/// Text search configuration used to build and query full-text search documents.
+pub const TEXT_SEARCH_CONFIG: &str = "english";
+
+/// Fields that every time-series entity declares, and which aren't dimensions.
+pub const TIMESERIES_RESERVED_FIELDS: [&str; 3] = ["id", "interval", "bucket"];
+
 lazy_static! {
 
+    /// Set of supported index methods, by lowercase name.
+    pub static ref INDEX_METHODS: HashSet<&'static str> = HashSet::from([
+        "btree",
+        "hash",
+        "gin",
+        "brin",
+    ]);
+
+    /// Set of scalar types that can hold the time or block height of a record, such as the
+    /// time of a time-series entity's source record, or the field of a retention policy.
+    pub static ref TIME_FIELD_TYPES: HashSet<&'static str> = HashSet::from([
+        "BlockHeight",
+        "Int4",
+        "Int8",
+        "Timestamp",
+        "UInt4",
+        "UInt8",
+    ]);
+
+    /// Set of scalar types whose values can be summed or compared by a time-series rollup.
+    pub static ref ROLLUP_NUMERIC_TYPES: HashSet<&'static str> = HashSet::from([
+        "BlockHeight",
+        "Int1",
+        "Int16",
+        "Int4",
+        "Int8",
+        "Timestamp",
+        "UInt1",
+        "UInt16",
+        "UInt4",
+        "UInt8",
+    ]);
+
     /// Set of internal indexer entities.
     pub static ref INTERNAL_INDEXER_ENTITIES: HashSet<&'static str> = HashSet::from([
         "IndexMetadataEntity",
@@ -68,19 +107,32 @@ lazy_static! {
         "Identity"
     ]);
 
-    /// Fuel-specific receipt-related type names.
+    /// Fuel-specific receipt- and transaction-related type names.
     pub static ref FUEL_PRIMITIVES: HashSet<&'static str> = HashSet::from([
         "BlockData",
         "Call",
+        "ChangeOutput",
+        "CoinOutput",
+        "ContractCreated",
+        "ContractOutput",
+        "Input",
+        "InputCoin",
+        "InputContract",
+        "InputMessage",
         "Log",
         "LogData",
         "MessageOut",
+        "MessageOutput",
+        "Output",
         "Panic",
         "Return",
+        "ReturnData",
         "Revert",
         "ScriptResult",
+        "TransactionData",
         "Transfer",
         "TransferOut",
+        "VariableOutput",
     ]);
 
     /// ABI types not allowed in the contract ABI.
@@ -106,6 +158,7 @@ lazy_static! {
         "MessageOut",
         "Panic",
         "Return",
+        "ReturnData",
         "Revert",
         "ScriptResult",
         "Transfer",
@@ -185,6 +238,7 @@ lazy_static! {
         "MessageId",
         "Outputs",
         "ReceiptsRoot",
+        "ReturnData",
         "Script",
         "ScriptData",
         "ScriptResult",
@@ -6,7 +6,11 @@ pub mod validator;
 pub use parser::{JoinTableMeta, ParsedError, ParsedGraphQLSchema};
 pub use validator::GraphQLSchemaValidator;
 
-use async_graphql_parser::types::FieldDefinition;
+use async_graphql_parser::types::{
+    ConstDirective, FieldDefinition, TypeDefinition, TypeKind,
+};
+use async_graphql_value::ConstValue;
+use constants::TIMESERIES_RESERVED_FIELDS;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -156,3 +160,318 @@ pub fn field_type_name(f: &FieldDefinition) -> String {
 pub fn list_field_type_name(f: &FieldDefinition) -> String {
     f.ty.to_string().replace(['!'], "")
 }
+
+/// Arguments of an `@index` directive, which indexes one or more fields of a `TypeDefinition`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexDirective {
+    /// Names of the indexed fields, in order.
+    pub fields: Vec<String>,
+
+    /// Whether the combination of the fields is unique.
+    pub unique: bool,
+
+    /// Lowercase name of the index method, if one is given.
+    pub method: Option<String>,
+}
+
+impl IndexDirective {
+    /// Parse the arguments of a given `@index` directive.
+    pub fn from_directive(d: &ConstDirective) -> Self {
+        let mut directive = IndexDirective::default();
+
+        for (name, value) in d.arguments.iter() {
+            match (name.node.as_str(), &value.node) {
+                ("fields", ConstValue::List(fields)) => {
+                    directive.fields = fields
+                        .iter()
+                        .filter_map(|f| match f {
+                            ConstValue::String(s) => Some(s.to_owned()),
+                            _ => None,
+                        })
+                        .collect();
+                }
+                ("unique", ConstValue::Boolean(unique)) => directive.unique = *unique,
+                ("method", value) => directive.method = index_method_name(value),
+                _ => {}
+            }
+        }
+
+        directive
+    }
+}
+
+/// Return the `@index` directives of a given `TypeDefinition`.
+pub fn index_directives(typ: &TypeDefinition) -> Vec<IndexDirective> {
+    typ.directives
+        .iter()
+        .filter(|d| d.node.name.to_string() == "index")
+        .map(|d| IndexDirective::from_directive(&d.node))
+        .collect()
+}
+
+/// Return the lowercase name of the index method given as a directive argument.
+///
+/// Methods are accepted in any case (e.g., `BTree` or `BTREE`).
+pub fn index_method_name(value: &ConstValue) -> Option<String> {
+    match value {
+        ConstValue::Enum(name) => Some(name.to_string().to_lowercase()),
+        ConstValue::String(name) => Some(name.to_lowercase()),
+        _ => None,
+    }
+}
+
+/// Function used by a time-series entity to roll up the records of its source entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupFunction {
+    Sum,
+    Count,
+    First,
+    Last,
+    Min,
+    Max,
+}
+
+impl RollupFunction {
+    /// Return the rollup function for a given `RollupFunction` enum value name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "sum" => Some(Self::Sum),
+            "count" => Some(Self::Count),
+            "first" => Some(Self::First),
+            "last" => Some(Self::Last),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+}
+
+/// A field of a time-series entity that rolls up a field of its source entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rollup {
+    /// Name of the field of the time-series entity.
+    pub field: String,
+
+    /// Function used to roll up the values of the source field.
+    pub function: RollupFunction,
+
+    /// Name of the rolled up field of the source entity, which is `None` when records
+    /// are only counted.
+    pub source_field: Option<String>,
+}
+
+/// Arguments of a `@timeseries` directive, along with the rollups it declares.
+///
+/// Each record of a time-series entity holds the rollups of the source records whose
+/// `time_field` falls within a bucket of one of its intervals. Records are also kept
+/// apart by the values of the time-series entity's dimensions, which are its fields
+/// that aren't reserved or rolled up, and are copied from the same-named source fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timeseries {
+    /// Name of the time-series `TypeDefinition`.
+    pub name: String,
+
+    /// Name of the `TypeDefinition` whose records are rolled up.
+    pub source: String,
+
+    /// Name of the source field holding the time of a record.
+    pub time_field: String,
+
+    /// Intervals (e.g., `1h` or `1d`) by which records are bucketed.
+    pub intervals: Vec<String>,
+
+    /// Fields that roll up the fields of the source entity.
+    pub rollups: Vec<Rollup>,
+
+    /// Fields that are copied from the source entity.
+    pub dimensions: Vec<String>,
+}
+
+impl Timeseries {
+    /// Parse the `@timeseries` directive and `@rollup` fields of a given `TypeDefinition`,
+    /// if it's a time-series entity.
+    pub fn from_typedef(typ: &TypeDefinition) -> Option<Self> {
+        let d = typ
+            .directives
+            .iter()
+            .find(|d| d.node.name.to_string() == "timeseries")?;
+
+        let mut timeseries = Timeseries {
+            name: typ.name.to_string(),
+            ..Default::default()
+        };
+
+        for (name, value) in d.node.arguments.iter() {
+            match (name.node.as_str(), &value.node) {
+                ("interval", ConstValue::List(intervals)) => {
+                    timeseries.intervals = intervals
+                        .iter()
+                        .filter_map(|i| match i {
+                            ConstValue::String(s) => Some(s.to_owned()),
+                            _ => None,
+                        })
+                        .collect();
+                }
+                // A single interval can be given without being wrapped in a list.
+                ("interval", ConstValue::String(interval)) => {
+                    timeseries.intervals = vec![interval.to_owned()]
+                }
+                ("source", ConstValue::String(source)) => {
+                    timeseries.source = source.to_owned()
+                }
+                ("time_field", ConstValue::String(field)) => {
+                    timeseries.time_field = field.to_owned()
+                }
+                _ => {}
+            }
+        }
+
+        if let TypeKind::Object(o) = &typ.kind {
+            for f in o.fields.iter() {
+                let field = f.node.name.to_string();
+                if TIMESERIES_RESERVED_FIELDS.contains(&field.as_str()) {
+                    continue;
+                }
+
+                let rollup = f
+                    .node
+                    .directives
+                    .iter()
+                    .find(|d| d.node.name.to_string() == "rollup");
+
+                match rollup {
+                    Some(d) => {
+                        let mut function = None;
+                        let mut source_field = None;
+                        for (name, value) in d.node.arguments.iter() {
+                            match (name.node.as_str(), &value.node) {
+                                ("function", ConstValue::Enum(name)) => {
+                                    function = RollupFunction::from_name(name.as_str())
+                                }
+                                ("function", ConstValue::String(name)) => {
+                                    function = RollupFunction::from_name(name)
+                                }
+                                ("field", ConstValue::String(name)) => {
+                                    source_field = Some(name.to_owned())
+                                }
+                                _ => {}
+                            }
+                        }
+
+                        let function = function.unwrap_or_else(|| {
+                            panic!("FieldDefinition({field}) has a @rollup directive with an unsupported function.")
+                        });
+
+                        // Rolled up fields default to the same-named source field.
+                        let source_field = match function {
+                            RollupFunction::Count => None,
+                            _ => Some(source_field.unwrap_or(field.clone())),
+                        };
+
+                        timeseries.rollups.push(Rollup {
+                            field,
+                            function,
+                            source_field,
+                        });
+                    }
+                    None => timeseries.dimensions.push(field),
+                }
+            }
+        }
+
+        Some(timeseries)
+    }
+
+    /// Return the start of the bucket of the given interval in which a time falls.
+    pub fn bucket(interval: &str, time: i64) -> Option<i64> {
+        let secs = interval_seconds(interval)?;
+        Some(time.div_euclid(secs) * secs)
+    }
+
+    /// Return the ID of the record holding the rollups of a given bucket, for a given
+    /// set of dimension values.
+    pub fn bucket_id(&self, interval: &str, bucket: i64, dimensions: &[String]) -> u64 {
+        let digest = Sha256::digest(
+            format!("{}:{interval}:{bucket}:{}", self.name, dimensions.join(":"))
+                .as_bytes(),
+        );
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(bytes)
+    }
+}
+
+/// Arguments of a `@retain` directive, which limits how long the records of a
+/// `TypeDefinition` are kept.
+///
+/// Records are expired once the value of their `field` is older than either a number
+/// of blocks before the last block indexed, or a duration before the current time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Retention {
+    /// Name of the field holding the block height or time of a record.
+    pub field: String,
+
+    /// Number of blocks for which records are retained.
+    pub blocks: Option<u64>,
+
+    /// Duration (e.g., `12h` or `7d`) for which records are retained.
+    pub duration: Option<String>,
+}
+
+impl Retention {
+    /// Parse the `@retain` directive of a given `TypeDefinition`, if it has one.
+    pub fn from_typedef(typ: &TypeDefinition) -> Option<Self> {
+        let d = typ
+            .directives
+            .iter()
+            .find(|d| d.node.name.to_string() == "retain")?;
+
+        let mut retention = Retention::default();
+        for (name, value) in d.node.arguments.iter() {
+            match (name.node.as_str(), &value.node) {
+                ("field", ConstValue::String(field)) => {
+                    retention.field = field.to_owned()
+                }
+                ("blocks", ConstValue::Number(blocks)) => {
+                    retention.blocks = blocks.as_u64()
+                }
+                ("duration", ConstValue::String(duration)) => {
+                    retention.duration = Some(duration.to_owned())
+                }
+                _ => {}
+            }
+        }
+
+        Some(retention)
+    }
+
+    /// Return the value of `field` below which records are expired, given the height of
+    /// the last block indexed and the current time, in seconds since the Unix epoch.
+    pub fn cutoff(&self, block_height: u64, now: i64) -> Option<i64> {
+        match (self.blocks, &self.duration) {
+            (Some(blocks), _) => block_height
+                .checked_sub(blocks)
+                .and_then(|cutoff| i64::try_from(cutoff).ok()),
+            (None, Some(duration)) => now.checked_sub(interval_seconds(duration)?),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Return the number of seconds in a time-series interval (e.g., `15m`, `1h` or `1d`).
+pub fn interval_seconds(interval: &str) -> Option<i64> {
+    let unit = interval.chars().last()?;
+    let count = interval[..interval.len() - unit.len_utf8()]
+        .parse::<i64>()
+        .ok()
+        .filter(|c| *c > 0)?;
+    let secs = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    count.checked_mul(secs)
+}
@@ -7,7 +7,8 @@ use crate::{
     fully_qualified_namespace,
     graphql::{
         extract_foreign_key_info, field_id, field_type_name, is_list_type,
-        list_field_type_name, GraphQLSchema, GraphQLSchemaValidator, BASE_SCHEMA,
+        list_field_type_name, GraphQLSchema, GraphQLSchemaValidator, Retention,
+        Timeseries, BASE_SCHEMA,
     },
     ExecutionSource,
 };
@@ -166,6 +167,18 @@ pub struct ParsedGraphQLSchema {
     /// All unique names of types for which tables should _not_ be created.
     virtual_type_names: HashSet<String>,
 
+    /// All unique names of types whose every version is kept, rather than only the latest.
+    historical_type_names: HashSet<String>,
+
+    /// Names of the fields using the `@search` directive, indexed by object name.
+    search_field_names: HashMap<String, Vec<String>>,
+
+    /// Time-series rollups declared using the `@timeseries` directive, indexed by object name.
+    timeseries: HashMap<String, Timeseries>,
+
+    /// Retention policies declared using the `@retain` directive, indexed by object name.
+    retention: HashMap<String, Retention>,
+
     /// All unique names of types that have already been parsed.
     parsed_typedef_names: HashSet<String>,
 
@@ -224,6 +237,10 @@ impl Default for ParsedGraphQLSchema {
             union_names: HashSet::new(),
             objects: HashMap::new(),
             virtual_type_names: HashSet::new(),
+            historical_type_names: HashSet::new(),
+            search_field_names: HashMap::new(),
+            timeseries: HashMap::new(),
+            retention: HashMap::new(),
             parsed_typedef_names: HashSet::new(),
             field_type_mappings: HashMap::new(),
             object_field_mappings: HashMap::new(),
@@ -260,6 +277,10 @@ impl ParsedGraphQLSchema {
         let mut enum_names = HashSet::new();
         let mut union_names = HashSet::new();
         let mut virtual_type_names = HashSet::new();
+        let mut historical_type_names = HashSet::new();
+        let mut search_field_names: HashMap<String, Vec<String>> = HashMap::new();
+        let mut timeseries = HashMap::new();
+        let mut retention = HashMap::new();
         let mut field_type_mappings = HashMap::new();
         let mut objects = HashMap::new();
         let mut field_defs = HashMap::new();
@@ -288,12 +309,54 @@ impl ParsedGraphQLSchema {
                             objects.insert(obj_name.clone(), o.clone());
                             parsed_typedef_names.insert(t.node.name.to_string());
 
+                            let is_historical = t
+                                .node
+                                .directives
+                                .iter()
+                                .any(|d| d.node.name.to_string() == "historical");
+
+                            if is_historical {
+                                historical_type_names.insert(obj_name.clone());
+                            }
+
+                            if let Some(ts) = Timeseries::from_typedef(&t.node) {
+                                timeseries.insert(obj_name.clone(), ts);
+                            }
+
+                            if let Some(r) = Retention::from_typedef(&t.node) {
+                                retention.insert(obj_name.clone(), r);
+                            }
+
+                            GraphQLSchemaValidator::check_index_directives_are_well_formed(
+                                &t.node,
+                                &o.fields
+                                    .iter()
+                                    .map(|f| f.node.name.to_string())
+                                    .collect(),
+                            );
+
                             let mut field_mapping = BTreeMap::new();
                             for field in &o.fields {
                                 let field_name = field.node.name.to_string();
                                 let field_typ_name = field.node.ty.to_string();
                                 let fid = field_id(&obj_name, &field_name);
 
+                                GraphQLSchemaValidator::check_search_directive_is_well_formed(
+                                    &field.node,
+                                );
+
+                                if field
+                                    .node
+                                    .directives
+                                    .iter()
+                                    .any(|d| d.node.name.to_string() == "search")
+                                {
+                                    search_field_names
+                                        .entry(obj_name.clone())
+                                        .or_default()
+                                        .push(field_name.clone());
+                                }
+
                                 if is_list_type(&field.node) {
                                     list_field_types
                                         .insert(field_typ_name.replace('!', ""));
@@ -448,6 +511,28 @@ impl ParsedGraphQLSchema {
             }
         }
 
+        let non_source_type_names = virtual_type_names
+            .iter()
+            .chain(union_names.iter())
+            .chain(timeseries.keys())
+            .cloned()
+            .collect::<HashSet<String>>();
+        for ts in timeseries.values() {
+            GraphQLSchemaValidator::check_timeseries_is_well_formed(
+                ts,
+                &object_field_mappings,
+                &non_source_type_names,
+            );
+        }
+
+        for (name, r) in retention.iter() {
+            GraphQLSchemaValidator::check_retain_directive_is_well_formed(
+                name,
+                r,
+                &object_field_mappings[name],
+            );
+        }
+
         let typedef_names_to_types = type_defs
             .iter()
             .filter(|(_, t)| !matches!(&t.kind, TypeKind::Enum(_)))
@@ -470,6 +555,10 @@ impl ParsedGraphQLSchema {
             object_field_mappings,
             enum_names,
             virtual_type_names,
+            historical_type_names,
+            search_field_names,
+            timeseries,
+            retention,
             parsed_typedef_names,
             field_type_mappings,
             scalar_names,
@@ -614,6 +703,34 @@ impl ParsedGraphQLSchema {
         self.virtual_type_names.contains(name) && !self.is_enum_typedef(name)
     }
 
+    /// Whether every version of the given type is kept, rather than only the latest.
+    pub fn is_historical_typedef(&self, name: &str) -> bool {
+        self.historical_type_names.contains(name) && !self.is_virtual_typedef(name)
+    }
+
+    /// Names of the fields of the given type that use the `@search` directive.
+    pub fn search_fields(&self, name: &str) -> &[String] {
+        match self.search_field_names.get(name) {
+            Some(fields) if !self.is_virtual_typedef(name) => fields,
+            _ => &[],
+        }
+    }
+
+    /// Time-series rollups declared using the `@timeseries` directive, indexed by object name.
+    pub fn timeseries(&self) -> &HashMap<String, Timeseries> {
+        &self.timeseries
+    }
+
+    /// Whether the given type is a time-series entity maintained by the indexer.
+    pub fn is_timeseries_typedef(&self, name: &str) -> bool {
+        self.timeseries.contains_key(name)
+    }
+
+    /// Retention policies declared using the `@retain` directive, indexed by object name.
+    pub fn retention(&self) -> &HashMap<String, Retention> {
+        &self.retention
+    }
+
     /// Whether the given field type name is an enum type.
     pub fn is_enum_typedef(&self, name: &str) -> bool {
         self.enum_names.contains(name)
@@ -678,6 +795,7 @@ impl ParsedGraphQLSchema {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graphql::{Rollup, RollupFunction};
 
     #[test]
     fn test_parser_caches_all_related_typedefs_when_instantiated() {
@@ -693,10 +811,10 @@ type Account {
     label: AccountLabel
 }
 
-type User {
+type User @historical {
     id: ID!
     account: Account!
-    username: Charfield!
+    username: Charfield! @search
 }
 
 type Loser {
@@ -728,10 +846,119 @@ union Person = User | Loser
         assert!(parsed.is_possible_foreign_key("Account"));
         assert!(parsed.is_virtual_typedef("Metadata"));
         assert!(parsed.is_enum_typedef("AccountLabel"));
+        assert!(parsed.is_historical_typedef("User"));
+        assert!(!parsed.is_historical_typedef("Account"));
+        assert_eq!(parsed.search_fields("User"), ["username".to_string()]);
+        assert!(parsed.search_fields("Account").is_empty());
         assert!(parsed
             .field_type_optionality()
             .contains_key("Account.label"));
 
         assert!(parsed.is_union_typedef("Person"));
     }
+
+    fn timeseries_schema(time_field_type: &str) -> GraphQLSchema {
+        GraphQLSchema::new(format!(
+            r#"
+type Swap {{
+    id: ID!
+    pool: Charfield!
+    amount: UInt8!
+    price: UInt8!
+    timestamp: {time_field_type}!
+}}
+
+type SwapCandle @timeseries(interval: ["1h", "1d"], source: "Swap", time_field: "timestamp") {{
+    id: ID!
+    interval: Charfield!
+    bucket: Int8!
+    pool: Charfield!
+    volume: UInt16! @rollup(function: Sum, field: "amount")
+    swaps: UInt8! @rollup(function: Count)
+    open: UInt8! @rollup(function: First, field: "price")
+    close: UInt8! @rollup(function: Last, field: "price")
+    high: UInt8! @rollup(function: Max, field: "price")
+}}
+"#
+        ))
+    }
+
+    #[test]
+    fn test_parser_caches_timeseries_rollups() {
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&timeseries_schema("Int8")),
+        )
+        .unwrap();
+
+        assert!(parsed.is_timeseries_typedef("SwapCandle"));
+        assert!(!parsed.is_timeseries_typedef("Swap"));
+
+        let ts = &parsed.timeseries()["SwapCandle"];
+        assert_eq!(ts.source, "Swap");
+        assert_eq!(ts.time_field, "timestamp");
+        assert_eq!(ts.intervals, vec!["1h".to_string(), "1d".to_string()]);
+        assert_eq!(ts.dimensions, vec!["pool".to_string()]);
+        assert_eq!(
+            ts.rollups[0],
+            Rollup {
+                field: "volume".to_string(),
+                function: RollupFunction::Sum,
+                source_field: Some("amount".to_string()),
+            }
+        );
+        assert_eq!(ts.rollups[1].source_field, None);
+        assert_eq!(Timeseries::bucket("1h", 7_250), Some(7_200));
+        assert_eq!(Timeseries::bucket("1d", -1), Some(-86_400));
+        assert_eq!(Timeseries::bucket("1y", 7_250), None);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "TypeDefinition(SwapCandle) uses Field(timestamp) of TypeDefinition(Swap) as its time field, which must be an integer or timestamp field."
+    )]
+    fn test_parser_rejects_timeseries_with_non_integer_time_field() {
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&timeseries_schema("Charfield")),
+        );
+    }
+
+    #[test]
+    fn test_parser_caches_retention_policies() {
+        let schema = r#"
+type Transfer @retain(blocks: 1000, field: "block_height") {
+    id: ID!
+    amount: UInt8!
+    block_height: UInt4!
+}
+
+type Price @retain(duration: "7d", field: "timestamp") {
+    id: ID!
+    value: UInt8!
+    timestamp: Int8!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let transfer = &parsed.retention()["Transfer"];
+        assert_eq!(transfer.field, "block_height");
+        assert_eq!(transfer.cutoff(5_000, 0), Some(4_000));
+        assert_eq!(transfer.cutoff(500, 0), None);
+
+        let price = &parsed.retention()["Price"];
+        assert_eq!(price.cutoff(5_000, 1_000_000), Some(395_200));
+        assert!(!parsed.retention().contains_key("IndexMetadataEntity"));
+    }
 }
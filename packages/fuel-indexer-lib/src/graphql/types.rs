@@ -37,3 +37,66 @@ impl ObjectCol {
         "Object"
     }
 }
+
+/// Represents the column holding the first block at which a version of a
+/// historical entity is valid.
+pub struct FromBlockCol;
+impl FromBlockCol {
+    pub fn to_lowercase_string() -> String {
+        "from_block".to_string()
+    }
+
+    pub fn to_lowercase_str() -> &'static str {
+        "from_block"
+    }
+}
+
+/// Represents the column holding the block at which a version of a historical
+/// entity is replaced, if it has been.
+pub struct ToBlockCol;
+impl ToBlockCol {
+    pub fn to_lowercase_string() -> String {
+        "to_block".to_string()
+    }
+
+    pub fn to_lowercase_str() -> &'static str {
+        "to_block"
+    }
+}
+
+/// Represents the generated column holding the full-text search document of
+/// a `TypeDefinition`'s `@search` fields.
+pub struct SearchCol;
+impl SearchCol {
+    pub fn to_lowercase_string() -> String {
+        "search_vector".to_string()
+    }
+
+    pub fn to_lowercase_str() -> &'static str {
+        "search_vector"
+    }
+}
+
+/// Represents the field of a time-series entity holding the interval of its bucket.
+pub struct IntervalCol;
+impl IntervalCol {
+    pub fn to_lowercase_string() -> String {
+        "interval".to_string()
+    }
+
+    pub fn to_lowercase_str() -> &'static str {
+        "interval"
+    }
+}
+
+/// Represents the field of a time-series entity holding the start of its bucket.
+pub struct BucketCol;
+impl BucketCol {
+    pub fn to_lowercase_string() -> String {
+        "bucket".to_string()
+    }
+
+    pub fn to_lowercase_str() -> &'static str {
+        "bucket"
+    }
+}
@@ -1,6 +1,11 @@
-use crate::graphql::constants::*;
+use crate::graphql::{
+    constants::*,
+    index_directives, interval_seconds, is_list_type,
+    types::{BucketCol, IdCol, IntervalCol},
+    Retention, RollupFunction, Timeseries,
+};
 use async_graphql_parser::types::{FieldDefinition, TypeDefinition, TypeKind};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// General container used to store a set of GraphQL schema validation functions.
 pub struct GraphQLSchemaValidator;
@@ -76,6 +81,152 @@ impl GraphQLSchemaValidator {
         }
     }
 
+    /// Ensure that a `TypeDefinition`'s `@index` directives index fields of that type using a
+    /// supported index method, and that unique indexes use the `BTree` method.
+    pub fn check_index_directives_are_well_formed(
+        typ: &TypeDefinition,
+        field_names: &HashSet<String>,
+    ) {
+        let name = typ.name.to_string();
+        for index in index_directives(typ) {
+            if index.fields.is_empty() {
+                panic!(
+                    "TypeDefinition({name}) has an @index directive without any fields."
+                );
+            }
+
+            for field in index.fields.iter() {
+                if !field_names.contains(field) {
+                    panic!("TypeDefinition({name}) has an @index directive on Field({field}), which it does not have.");
+                }
+            }
+
+            let method = index.method.unwrap_or("btree".to_string());
+            if !INDEX_METHODS.contains(method.as_str()) {
+                panic!("TypeDefinition({name}) has an @index directive with unsupported method '{method}'.");
+            }
+
+            if index.unique && method != "btree" {
+                panic!("TypeDefinition({name}) has a unique @index directive using the '{method}' method. Unique indexes must use the 'btree' method.");
+            }
+        }
+    }
+
+    /// Ensure a `FieldDefinition` using the `@search` directive can be searched.
+    pub fn check_search_directive_is_well_formed(f: &FieldDefinition) {
+        let name = f.name.to_string();
+        let is_search = f
+            .directives
+            .iter()
+            .any(|d| d.node.name.to_string() == "search");
+        if is_search && is_list_type(f) {
+            panic!("FieldDefinition({name}) is a list type, which cannot use the @search directive.");
+        }
+    }
+
+    /// Ensure a time-series `TypeDefinition` declares its reserved fields, and rolls up
+    /// an existing, regular source `TypeDefinition` using fields of compatible types.
+    pub fn check_timeseries_is_well_formed(
+        timeseries: &Timeseries,
+        object_field_mappings: &HashMap<String, BTreeMap<String, String>>,
+        non_source_type_names: &HashSet<String>,
+    ) {
+        let name = &timeseries.name;
+        let fields = &object_field_mappings[name];
+        let source = &timeseries.source;
+
+        let source_fields = match object_field_mappings.get(source) {
+            Some(fields) if !non_source_type_names.contains(source) => fields,
+            _ => panic!("TypeDefinition({name}) is a time series of TypeDefinition({source}), which is not a regular object type."),
+        };
+
+        let is_time_type = |typ: Option<&String>| {
+            typ.map(|t| TIME_FIELD_TYPES.contains(t.as_str()))
+                .unwrap_or(false)
+        };
+
+        let time_field = &timeseries.time_field;
+        if !is_time_type(source_fields.get(time_field)) {
+            panic!("TypeDefinition({name}) uses Field({time_field}) of TypeDefinition({source}) as its time field, which must be an integer or timestamp field.");
+        }
+
+        if timeseries.intervals.is_empty() {
+            panic!("TypeDefinition({name}) has a @timeseries directive without any intervals.");
+        }
+
+        for interval in timeseries.intervals.iter() {
+            if interval_seconds(interval).is_none() {
+                panic!("TypeDefinition({name}) has a @timeseries directive with unsupported interval '{interval}'.");
+            }
+        }
+
+        for (field, typ) in [
+            (IdCol::to_lowercase_str(), IdCol::to_uppercase_str()),
+            (IntervalCol::to_lowercase_str(), "Charfield"),
+        ] {
+            if fields.get(field).map(|t| t.as_str()) != Some(typ) {
+                panic!("TypeDefinition({name}) is a time series, which must have a Field({field}) of type {typ}.");
+            }
+        }
+
+        let bucket = BucketCol::to_lowercase_str();
+        if !is_time_type(fields.get(bucket)) {
+            panic!("TypeDefinition({name}) is a time series, which must have a Field({bucket}) of an integer or timestamp type.");
+        }
+
+        for rollup in timeseries.rollups.iter() {
+            let field = &rollup.field;
+            let source_field = match &rollup.source_field {
+                Some(source_field) => source_field,
+                None => continue,
+            };
+
+            let source_type = source_fields.get(source_field).unwrap_or_else(|| {
+                panic!("FieldDefinition({field}) rolls up Field({source_field}), which TypeDefinition({source}) does not have.")
+            });
+
+            let is_numeric = ROLLUP_NUMERIC_TYPES.contains(source_type.as_str())
+                && ROLLUP_NUMERIC_TYPES.contains(fields[field].as_str());
+            match rollup.function {
+                RollupFunction::Sum | RollupFunction::Min | RollupFunction::Max
+                    if !is_numeric =>
+                {
+                    panic!("FieldDefinition({field}) rolls up Field({source_field}) using the {:?} function, which only supports numeric fields.", rollup.function);
+                }
+                _ => {}
+            }
+        }
+
+        for field in timeseries.dimensions.iter() {
+            if source_fields.get(field) != fields.get(field) {
+                panic!("FieldDefinition({field}) of TypeDefinition({name}) is a dimension, which must have the same type as Field({field}) of TypeDefinition({source}).");
+            }
+        }
+    }
+
+    /// Ensure a `TypeDefinition`'s `@retain` directive retains its records for either a
+    /// number of blocks or a duration, according to an integer or timestamp field.
+    pub fn check_retain_directive_is_well_formed(
+        name: &str,
+        retention: &Retention,
+        fields: &BTreeMap<String, String>,
+    ) {
+        let field = &retention.field;
+        let is_time_field = fields
+            .get(field)
+            .map(|t| TIME_FIELD_TYPES.contains(t.as_str()))
+            .unwrap_or(false);
+        if !is_time_field {
+            panic!("TypeDefinition({name}) has a @retain directive on Field({field}), which must be an integer or timestamp field.");
+        }
+
+        match (retention.blocks, &retention.duration) {
+            (Some(blocks), None) if blocks > 0 => {}
+            (None, Some(duration)) if interval_seconds(duration).is_some() => {}
+            _ => panic!("TypeDefinition({name}) has a @retain directive, which must have either a positive number of blocks or a supported duration."),
+        }
+    }
+
     /// Ensure a `FieldDefinition` is not a reference to a nested list.
     pub fn ensure_fielddef_is_not_nested_list(f: &FieldDefinition) {
         let name = f.name.to_string();
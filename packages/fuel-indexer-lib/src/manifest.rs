@@ -1,7 +1,9 @@
-use crate::ExecutionSource;
+use crate::{config::CommitPolicy, ExecutionSource};
 use anyhow::Result;
+use chrono::{DateTime, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::{
+    fmt,
     fs::File,
     io::{Read, Write},
     path::{Path, PathBuf},
@@ -21,6 +23,12 @@ pub enum ManifestError {
     NativeModuleError,
     #[error("File IO error: {0} {1:?}.")]
     FileError(String, #[source] std::io::Error),
+    #[error("Invalid {0} '{1}': expected a 32-byte hex string.")]
+    InvalidSubscriptionId(&'static str, String),
+    #[error("Invalid start_time '{0}': expected a UNIX timestamp, an RFC 3339 timestamp or a YYYY-MM-DD date.")]
+    InvalidStartTime(String),
+    #[error("Invalid start options: {0}.")]
+    InvalidStartOptions(String),
 }
 
 /// Specifies which type of module is used to create this indexer.
@@ -84,9 +92,25 @@ pub struct Manifest {
     )]
     pub contract_id: ContractIds,
     pub start_block: Option<u64>,
+    #[serde(default)]
+    pub start_block_id: Option<String>,
+    #[serde(default)]
+    pub start_time: Option<String>,
+    #[serde(default)]
+    pub start_at_contract_deploy: Option<bool>,
     pub end_block: Option<u64>,
     #[serde(default)]
     pub resumable: Option<bool>,
+    #[serde(default)]
+    pub commit_policy: Option<CommitPolicy>,
+    #[serde(default)]
+    pub partitionable: Option<bool>,
+    #[serde(default)]
+    pub addresses: Option<Vec<String>>,
+    #[serde(default)]
+    pub asset_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub predicate_roots: Option<Vec<String>>,
 }
 
 impl Manifest {
@@ -147,6 +171,78 @@ impl Manifest {
         }
     }
 
+    /// Return the parsed `addresses` subscription, if the manifest specifies one.
+    pub fn subscribed_addresses(&self) -> ManifestResult<Option<Vec<[u8; 32]>>> {
+        parse_subscription_ids("address", &self.addresses)
+    }
+
+    /// Return the parsed `asset_ids` subscription, if the manifest specifies one.
+    pub fn subscribed_asset_ids(&self) -> ManifestResult<Option<Vec<[u8; 32]>>> {
+        parse_subscription_ids("asset ID", &self.asset_ids)
+    }
+
+    /// Return the parsed `predicate_roots` subscription, if the manifest specifies one.
+    pub fn subscribed_predicate_roots(&self) -> ManifestResult<Option<Vec<[u8; 32]>>> {
+        parse_subscription_ids("predicate root", &self.predicate_roots)
+    }
+
+    /// Return where the indexer should start if the manifest specifies this with
+    /// something other than a block height.
+    ///
+    /// The returned position still has to be resolved to a height using the block source.
+    pub fn start_position(&self) -> ManifestResult<Option<StartPosition>> {
+        let start_at_contract_deploy = self.start_at_contract_deploy.unwrap_or(false);
+
+        let num_options = [
+            self.start_block_id.is_some(),
+            self.start_time.is_some(),
+            start_at_contract_deploy,
+        ]
+        .iter()
+        .filter(|x| **x)
+        .count();
+
+        if num_options > 1 {
+            return Err(ManifestError::InvalidStartOptions(
+                "only one of start_block_id, start_time and start_at_contract_deploy may be set".to_string(),
+            ));
+        }
+
+        if let Some(id) = &self.start_block_id {
+            let ids = parse_subscription_ids("start_block_id", &Some(vec![id.clone()]))?
+                .unwrap_or_default();
+            return Ok(ids.first().copied().map(StartPosition::BlockId));
+        }
+
+        if let Some(time) = &self.start_time {
+            return parse_start_time(time).map(|t| Some(StartPosition::Time(t)));
+        }
+
+        if start_at_contract_deploy {
+            let contract_ids = match &self.contract_id {
+                ContractIds::Single(Some(id)) => vec![id.clone()],
+                ContractIds::Multiple(ids) if !ids.is_empty() => ids.clone(),
+                _ => {
+                    return Err(ManifestError::InvalidStartOptions(
+                        "start_at_contract_deploy requires a contract_id".to_string(),
+                    ))
+                }
+            };
+            return Ok(Some(StartPosition::ContractDeploy(contract_ids)));
+        }
+
+        Ok(None)
+    }
+
+    /// Ensure that every subscription key and start option in this manifest is well-formed.
+    fn validate(&self) -> ManifestResult<()> {
+        self.subscribed_addresses()?;
+        self.subscribed_asset_ids()?;
+        self.subscribed_predicate_roots()?;
+        self.start_position()?;
+        Ok(())
+    }
+
     /// Write this manifest to a given path.
     pub fn write(&self, path: &PathBuf) -> ManifestResult<()> {
         let mut file = File::create(path).map_err(|err| {
@@ -165,6 +261,7 @@ impl TryFrom<&str> for Manifest {
 
     fn try_from(val: &str) -> ManifestResult<Self> {
         let manifest: Manifest = serde_yaml::from_str(val)?;
+        manifest.validate()?;
         Ok(manifest)
     }
 }
@@ -182,10 +279,74 @@ impl TryFrom<&Vec<u8>> for Manifest {
 
     fn try_from(val: &Vec<u8>) -> ManifestResult<Self> {
         let manifest: Manifest = serde_yaml::from_slice(val)?;
+        manifest.validate()?;
         Ok(manifest)
     }
 }
 
+/// Parse a list of 32-byte hex identifiers (with or without a `0x` prefix).
+fn parse_subscription_ids(
+    kind: &'static str,
+    ids: &Option<Vec<String>>,
+) -> ManifestResult<Option<Vec<[u8; 32]>>> {
+    let ids = match ids {
+        Some(ids) => ids,
+        None => return Ok(None),
+    };
+
+    ids.iter()
+        .map(|id| {
+            let mut bytes = [0u8; 32];
+            hex::decode_to_slice(id.trim_start_matches("0x"), &mut bytes)
+                .map_err(|_| ManifestError::InvalidSubscriptionId(kind, id.clone()))?;
+            Ok(bytes)
+        })
+        .collect::<ManifestResult<Vec<_>>>()
+        .map(Some)
+}
+
+/// Parse a `start_time` as a UNIX timestamp (in seconds).
+fn parse_start_time(time: &str) -> ManifestResult<i64> {
+    if let Ok(secs) = time.parse::<i64>() {
+        return Ok(secs);
+    }
+
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(time) {
+        return Ok(datetime.timestamp());
+    }
+
+    NaiveDate::parse_from_str(time, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.timestamp())
+        .ok_or_else(|| ManifestError::InvalidStartTime(time.to_string()))
+}
+
+/// A starting point for an indexer that is not given as a block height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartPosition {
+    /// Start at the block with this ID.
+    BlockId([u8; 32]),
+
+    /// Start at the first block produced at or after this UNIX timestamp.
+    Time(i64),
+
+    /// Start at the block in which the first of these contracts was deployed.
+    ContractDeploy(Vec<String>),
+}
+
+impl fmt::Display for StartPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BlockId(id) => write!(f, "start_block_id: 0x{}", hex::encode(id)),
+            Self::Time(time) => write!(f, "start_time: {time}"),
+            Self::ContractDeploy(ids) => {
+                write!(f, "start_at_contract_deploy: {}", ids.join(","))
+            }
+        }
+    }
+}
+
 /// Represents contract IDs in a `Manifest` struct.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(untagged)]
@@ -249,3 +410,86 @@ impl FromStr for ContractIds {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+namespace: test_namespace
+identifier: index1
+graphql_schema: schema.graphql
+module:
+  wasm: index1.wasm
+contract_id: ~
+"#;
+
+    #[test]
+    fn test_manifest_parses_subscription_keys() {
+        let manifest = Manifest::try_from(
+            format!(
+                "{MANIFEST}addresses:\n  - \"0x{}\"\nasset_ids:\n  - \"{}\"\n",
+                "01".repeat(32),
+                "ab".repeat(32)
+            )
+            .as_str(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.subscribed_addresses().unwrap(),
+            Some(vec![[1u8; 32]])
+        );
+        assert_eq!(
+            manifest.subscribed_asset_ids().unwrap(),
+            Some(vec![[0xabu8; 32]])
+        );
+        assert_eq!(manifest.subscribed_predicate_roots().unwrap(), None);
+    }
+
+    #[test]
+    fn test_manifest_rejects_malformed_subscription_keys() {
+        let result = Manifest::try_from(
+            format!("{MANIFEST}predicate_roots:\n  - \"0x1234\"\n").as_str(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(ManifestError::InvalidSubscriptionId("predicate root", _))
+        ));
+    }
+
+    #[test]
+    fn test_manifest_parses_start_positions() {
+        let manifest =
+            Manifest::try_from(format!("{MANIFEST}start_time: 2026-01-01\n").as_str())
+                .unwrap();
+        assert_eq!(
+            manifest.start_position().unwrap(),
+            Some(StartPosition::Time(1767225600))
+        );
+
+        let manifest = Manifest::try_from(
+            format!("{MANIFEST}start_time: \"2026-01-01T00:00:00+01:00\"\n").as_str(),
+        )
+        .unwrap();
+        assert_eq!(
+            manifest.start_position().unwrap(),
+            Some(StartPosition::Time(1767222000))
+        );
+
+        let result = Manifest::try_from(
+            format!("{MANIFEST}start_at_contract_deploy: true\n").as_str(),
+        );
+        assert!(matches!(result, Err(ManifestError::InvalidStartOptions(_))));
+
+        let result = Manifest::try_from(
+            format!(
+                "{MANIFEST}start_time: \"1\"\nstart_block_id: \"{}\"\n",
+                "00".repeat(32)
+            )
+            .as_str(),
+        );
+        assert!(matches!(result, Err(ManifestError::InvalidStartOptions(_))));
+    }
+}
@@ -3,9 +3,18 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
 use quote::quote;
-use syn::{parse_macro_input, ItemFn};
+use syn::{parse_macro_input, Ident, ItemFn};
 
-fn process_with_prometheus_metrics(input: TokenStream) -> TokenStream {
+fn process_with_prometheus_metrics(
+    attrs: TokenStream,
+    input: TokenStream,
+) -> TokenStream {
+    // Operations are recorded for Postgres, unless another database is given.
+    let database = if attrs.is_empty() {
+        Ident::new("postgres", proc_macro2::Span::call_site())
+    } else {
+        parse_macro_input!(attrs as Ident)
+    };
     let ast = parse_macro_input!(input as ItemFn);
     let fn_name = &ast.sig.ident;
     let label = fn_name.to_string();
@@ -37,7 +46,7 @@ fn process_with_prometheus_metrics(input: TokenStream) -> TokenStream {
 
                 METRICS
                     .db
-                    .postgres
+                    .#database
                     .record(#label, start_time.elapsed().as_millis() as f64);
                 res
             };
@@ -50,6 +59,6 @@ fn process_with_prometheus_metrics(input: TokenStream) -> TokenStream {
 
 #[proc_macro_error]
 #[proc_macro_attribute]
-pub fn metrics(_attrs: TokenStream, input: TokenStream) -> TokenStream {
-    process_with_prometheus_metrics(input)
+pub fn metrics(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    process_with_prometheus_metrics(attrs, input)
 }
@@ -68,19 +68,48 @@ lazy_static! {
         "Identity"
     ]);
 
-    /// Fuel-specific receipt-related type names.
+    /// Fuel-specific receipt- and transaction-related type names.
     pub static ref FUEL_PRIMITIVES: HashSet<&'static str> = HashSet::from([
         "BlockData",
         "Call",
+        "ChangeOutput",
+        "CoinOutput",
+        "ContractCreated",
+        "ContractOutput",
+        "Input",
+        "InputCoin",
+        "InputContract",
+        "InputMessage",
         "Log",
         "LogData",
         "MessageOut",
+        "MessageOutput",
+        "Output",
         "Panic",
         "Return",
+        "ReturnData",
         "Revert",
         "ScriptResult",
+        "TransactionData",
         "Transfer",
         "TransferOut",
+        "VariableOutput",
+    ]);
+
+    /// Transaction-, input- and output-level types, dispatched once per occurrence.
+    pub static ref FUEL_TRANSACTION_TYPES: HashSet<&'static str> = HashSet::from([
+        "ChangeOutput",
+        "CoinOutput",
+        "ContractCreated",
+        "ContractOutput",
+        "Input",
+        "InputCoin",
+        "InputContract",
+        "InputMessage",
+        "MessageOutput",
+        "Output",
+        "TransactionData",
+        "VariableOutput",
     ]);
 
     /// ABI types not allowed in the contract ABI.
@@ -106,6 +135,7 @@ lazy_static! {
         "MessageOut",
         "Panic",
         "Return",
+        "ReturnData",
         "Revert",
         "ScriptResult",
         "Transfer",
@@ -186,6 +216,7 @@ lazy_static! {
         "MessageId",
         "Outputs",
         "ReceiptsRoot",
+        "ReturnData",
         "Script",
         "ScriptData",
         "ScriptResult",
@@ -157,11 +157,18 @@ pub fn rust_type_token(ty: &TypeDeclaration) -> proc_macro2::TokenStream {
             "LogData" => quote! { LogData },
             "MessageOut" => quote! { MessageOut },
             "Return" => quote! { Return },
+            "ReturnData" => quote! { ReturnData },
             "ScriptResult" => quote! { ScriptResult },
             "Transfer" => quote! { Transfer },
             "TransferOut" => quote! { TransferOut },
             "Panic" => quote! { Panic },
             "Revert" => quote! { Revert },
+            // Transaction-level types are fully qualified, since users commonly define GraphQL
+            // entities of the same name (e.g., `InputCoin`), which would shadow the glob import.
+            o if FUEL_TRANSACTION_TYPES.contains(o) => {
+                let ident = format_ident! { "{}", o };
+                quote! { fuel::#ident }
+            }
             o if o.starts_with("str[") => quote! { String },
             o => {
                 proc_macro_error::abort_call_site!(
@@ -176,7 +183,13 @@ pub fn rust_type_token(ty: &TypeDeclaration) -> proc_macro2::TokenStream {
 /// Whether or not the given token is a Fuel primitive
 pub fn is_fuel_primitive(ty: &proc_macro2::TokenStream) -> bool {
     let ident_str = ty.to_string();
-    FUEL_PRIMITIVES.contains(ident_str.as_str())
+    let ident_str = ident_str.rsplit("::").next().unwrap_or_default().trim();
+    FUEL_PRIMITIVES.contains(ident_str)
+}
+
+/// Whether or not the given type name is dispatched once per occurrence in a transaction
+pub fn is_fuel_transaction_type(ty: &str) -> bool {
+    FUEL_TRANSACTION_TYPES.contains(ty)
 }
 
 /// Whether or not the given token is a Rust primitive
@@ -265,6 +278,51 @@ pub fn const_item(id: &str, value: &str) -> proc_macro2::TokenStream {
     }
 }
 
+/// Generate a constant holding the 32-byte identifiers of a manifest subscription key.
+pub fn subscription_ids_item(
+    id: &str,
+    ids: &Option<Vec<[u8; 32]>>,
+) -> proc_macro2::TokenStream {
+    match ids {
+        Some(ids) => {
+            let ident = format_ident! {"{}", id};
+            let ids = ids.iter().map(|id| quote! { [#(#id),*] });
+
+            quote! {
+                const #ident: &[[u8; 32]] = &[#(#ids),*];
+            }
+        }
+        None => quote! {},
+    }
+}
+
+/// Generate tokens that skip the current event if none of the given identifier
+/// fields are part of the subscription held in the constant `id`.
+///
+/// Nothing is generated if the manifest does not specify this subscription key.
+pub fn subscription_check(
+    id: &str,
+    ids: &Option<Vec<[u8; 32]>>,
+    fields: &[proc_macro2::TokenStream],
+    message: &str,
+) -> proc_macro2::TokenStream {
+    if ids.is_none() {
+        return quote! {};
+    }
+
+    let ident = format_ident! {"{}", id};
+
+    quote! {
+        if ![#(<[u8; 32]>::try_from(#fields).expect("Could not convert identifier into bytes")),*]
+            .iter()
+            .any(|id| #ident.contains(id))
+        {
+            debug!(#message);
+            continue;
+        }
+    }
+}
+
 /// Generate tokens for retrieving necessary indexer data through FFI.
 pub fn field_extractor(
     field_name: proc_macro2::Ident,
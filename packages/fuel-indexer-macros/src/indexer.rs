@@ -253,6 +253,20 @@ fn process_fn_items(
         None => quote! {},
     };
 
+    // Logged lines are tagged with the height of the block being handled.
+    let set_block_height = match manifest.execution_source() {
+        ExecutionSource::Wasm => quote! {
+            Logger::set_block_height(block.height);
+        },
+        ExecutionSource::Native => quote! {
+            unsafe {
+                if let Some(d) = &db {
+                    d.lock().await.set_block_height(block.height);
+                }
+            }
+        },
+    };
+
     let subscribed_contract_ids = match &manifest.contract_id {
         ContractIds::Single(_) => quote! {},
         ContractIds::Multiple(contract_ids) => {
@@ -302,13 +316,108 @@ fn process_fn_items(
         }
     };
 
+    let is_subscribed_to_contract = match &manifest.contract_id {
+        ContractIds::Single(contract_id) => match contract_id {
+            Some(contract_id) => {
+                quote! {
+                    let manifest_contract_id = Bech32ContractId::from_str(#contract_id).expect("Failed to parse manifest 'contract_id' as Bech32ContractId");
+                    let is_subscribed_to_contract = |id: &ContractId| {
+                        let id_bytes = <[u8; 32]>::try_from(*id).expect("Could not convert contract ID into bytes");
+                        Bech32ContractId::new("fuel", id_bytes) == manifest_contract_id
+                    };
+                }
+            }
+            None => quote! {
+                let is_subscribed_to_contract = |_id: &ContractId| true;
+            },
+        },
+        ContractIds::Multiple(_) => {
+            quote! {
+                let is_subscribed_to_contract = |id: &ContractId| {
+                    let id_bytes = <[u8; 32]>::try_from(*id).expect("Could not convert contract ID into bytes");
+                    contract_ids.contains(&Bech32ContractId::new("fuel", id_bytes))
+                };
+            }
+        }
+    };
+
+    let addresses = manifest
+        .subscribed_addresses()
+        .unwrap_or_else(|e| proc_macro_error::abort_call_site!("{}", e));
+    let asset_ids = manifest
+        .subscribed_asset_ids()
+        .unwrap_or_else(|e| proc_macro_error::abort_call_site!("{}", e));
+    let predicate_roots = manifest
+        .subscribed_predicate_roots()
+        .unwrap_or_else(|e| proc_macro_error::abort_call_site!("{}", e));
+
+    let subscription_ids = [
+        subscription_ids_item("SUBSCRIBED_ADDRESSES", &addresses),
+        subscription_ids_item("SUBSCRIBED_ASSET_IDS", &asset_ids),
+        subscription_ids_item("SUBSCRIBED_PREDICATE_ROOTS", &predicate_roots),
+    ];
+
+    let check_if_subscribed_to_address =
+        |fields: &[proc_macro2::TokenStream], event: &str| {
+            subscription_check(
+                "SUBSCRIBED_ADDRESSES",
+                &addresses,
+                fields,
+                &format!(
+                    "Not subscribed to this address. Will skip this {event}. <('-'<)"
+                ),
+            )
+        };
+
+    let check_if_subscribed_to_asset =
+        |fields: &[proc_macro2::TokenStream], event: &str| {
+            subscription_check(
+                "SUBSCRIBED_ASSET_IDS",
+                &asset_ids,
+                fields,
+                &format!("Not subscribed to this asset. Will skip this {event}. <('-'<)"),
+            )
+        };
+
+    // Only inputs spent by a predicate have a predicate root, which is the coin
+    // owner or the message recipient respectively.
+    let check_if_subscribed_to_predicate =
+        |root: proc_macro2::TokenStream, predicate: proc_macro2::TokenStream| {
+            match &predicate_roots {
+                Some(_) => quote! {
+                    let root_bytes = <[u8; 32]>::try_from(#root).expect("Could not convert predicate root into bytes");
+                    if #predicate.is_empty() || !SUBSCRIBED_PREDICATE_ROOTS.contains(&root_bytes) {
+                        debug!("Not subscribed to this predicate. Will skip this input. <('-'<)");
+                        continue;
+                    }
+                },
+                None => quote! {},
+            }
+        };
+
+    let has_coin_subscription = addresses.is_some() || asset_ids.is_some();
+
+    let has_contract_subscription =
+        !matches!(&manifest.contract_id, ContractIds::Single(None));
+
+    // Without any subscription key, every transaction is considered subscribed.
+    let tx_subscribed_by_default =
+        !has_contract_subscription && !has_coin_subscription && predicate_roots.is_none();
+
     let (asyncness, awaitness) = manifest.execution_source().async_awaitness();
 
+    let mut uses_transaction_types = false;
+
+    // Every type that at least one handler takes as an argument, exported so that
+    // the executor can strip events that no handler would ever see.
+    let mut handled_types = HashSet::new();
+
     for item in contents {
         match item {
             Item::Fn(fn_item) => {
                 let mut input_checks = Vec::new();
                 let mut arg_list = Vec::new();
+                let mut occurrence_arg = None;
 
                 for inp in &fn_item.sig.inputs {
                     match inp {
@@ -345,7 +454,21 @@ fn process_fn_items(
 
                                 input_checks.push(quote! { self.#name.len() > 0 });
 
-                                arg_list.push(quote! { self.#name[0].clone() });
+                                handled_types.insert(path_ident.clone());
+
+                                if is_fuel_transaction_type(&path_ident) {
+                                    if occurrence_arg.is_some() {
+                                        proc_macro_error::abort_call_site!(
+                                            "Handler '{}' may only take one transaction-, input- or output-level argument.",
+                                            fn_item.sig.ident
+                                        )
+                                    }
+                                    uses_transaction_types = true;
+                                    occurrence_arg = Some(name);
+                                    arg_list.push(quote! { occurrence.clone() });
+                                } else {
+                                    arg_list.push(quote! { self.#name[0].clone() });
+                                }
                             } else {
                                 proc_macro_error::abort_call_site!(
                                     "Arguments must be types defined in the ABI."
@@ -357,11 +480,20 @@ fn process_fn_items(
 
                 let fn_name = &fn_item.sig.ident;
 
-                abi_dispatchers.push(quote! {
-                    if ( #(#input_checks)&&* ) {
-                        #fn_name(#(#arg_list),*)#awaitness;
-                    }
-                });
+                match occurrence_arg {
+                    Some(occurrences) => abi_dispatchers.push(quote! {
+                        if ( #(#input_checks)&&* ) {
+                            for occurrence in self.#occurrences.iter() {
+                                #fn_name(#(#arg_list),*)#awaitness;
+                            }
+                        }
+                    }),
+                    None => abi_dispatchers.push(quote! {
+                        if ( #(#input_checks)&&* ) {
+                            #fn_name(#(#arg_list),*)#awaitness;
+                        }
+                    }),
+                }
 
                 handler_fns.push(fn_item);
             }
@@ -374,6 +506,192 @@ fn process_fn_items(
         }
     }
 
+    let transaction_type_fields = FUEL_TRANSACTION_TYPES
+        .iter()
+        .map(|ty| {
+            let name = decoded_ident(ty);
+            quote! { self.#name.clear(); }
+        })
+        .collect::<Vec<proc_macro2::TokenStream>>();
+
+    let marks_tx_subscribed = |is_subscription_key: bool| {
+        if is_subscription_key {
+            quote! { tx_is_subscribed = true; }
+        } else {
+            quote! {}
+        }
+    };
+
+    let input_coin_checks = [
+        check_if_subscribed_to_address(&[quote! { coin.owner }], "input"),
+        check_if_subscribed_to_asset(&[quote! { coin.asset_id }], "input"),
+        check_if_subscribed_to_predicate(
+            quote! { coin.owner },
+            quote! { coin.predicate },
+        ),
+        marks_tx_subscribed(has_coin_subscription || predicate_roots.is_some()),
+    ];
+
+    // Contract inputs and outputs only mark the transaction when the manifest
+    // subscribes to contracts, so other subscription keys still restrict it.
+    let contract_checks = marks_tx_subscribed(has_contract_subscription);
+
+    let input_message_checks = [
+        check_if_subscribed_to_address(
+            &[quote! { message.sender }, quote! { message.recipient }],
+            "input",
+        ),
+        check_if_subscribed_to_predicate(
+            quote! { message.recipient },
+            quote! { message.predicate },
+        ),
+        marks_tx_subscribed(addresses.is_some() || predicate_roots.is_some()),
+    ];
+
+    let output_coin_checks = |output: proc_macro2::TokenStream| {
+        [
+            check_if_subscribed_to_address(&[quote! { #output.to }], "output"),
+            check_if_subscribed_to_asset(&[quote! { #output.asset_id }], "output"),
+            marks_tx_subscribed(has_coin_subscription),
+        ]
+    };
+    let coin_output_checks = output_coin_checks(quote! { coin });
+    let change_output_checks = output_coin_checks(quote! { change });
+    let variable_output_checks = output_coin_checks(quote! { variable });
+
+    let message_output_checks = [
+        check_if_subscribed_to_address(&[quote! { message.recipient }], "output"),
+        marks_tx_subscribed(addresses.is_some()),
+    ];
+
+    let transfer_checks =
+        check_if_subscribed_to_asset(&[quote! { asset_id }], "receipt event");
+
+    let transfer_out_checks = [
+        check_if_subscribed_to_address(&[quote! { to }], "receipt event"),
+        check_if_subscribed_to_asset(&[quote! { asset_id }], "receipt event"),
+    ];
+
+    let message_out_checks = check_if_subscribed_to_address(
+        &[quote! { sender }, quote! { recipient }],
+        "receipt event",
+    );
+
+    let transaction_type_decoders = if uses_transaction_types {
+        quote! {
+            decoder.clear_transaction_types();
+
+            let (inputs, outputs) = match &tx.transaction {
+                fuel::Transaction::Create(t) => (t.inputs.clone(), t.outputs.clone()),
+                fuel::Transaction::Script(t) => (t.inputs.clone(), t.outputs.clone()),
+                fuel::Transaction::Mint(t) => (Vec::new(), t.outputs.clone()),
+            };
+
+            // `ContractOutput`s only reference their contract through the index of the matching `InputContract`.
+            let input_contract_ids = inputs
+                .iter()
+                .map(|input| match input {
+                    fuel::Input::Contract(contract) => Some(contract.contract_id),
+                    _ => None,
+                })
+                .collect::<Vec<Option<ContractId>>>();
+
+            #[allow(unused_mut)]
+            let mut tx_is_subscribed = #tx_subscribed_by_default;
+
+            for input in inputs {
+                match &input {
+                    fuel::Input::Coin(coin) => {
+                        #(#input_coin_checks)*
+                        decoder.decode_type(fuel::InputCoin::type_id(), serialize(coin));
+                    }
+                    fuel::Input::Contract(contract) => {
+                        if !is_subscribed_to_contract(&contract.contract_id) {
+                            debug!("Not subscribed to this contract. Will skip this input. <('-'<)");
+                            continue;
+                        }
+                        #contract_checks
+                        decoder.decode_type(fuel::InputContract::type_id(), serialize(contract));
+                    }
+                    fuel::Input::Message(message) => {
+                        #(#input_message_checks)*
+                        decoder.decode_type(fuel::InputMessage::type_id(), serialize(message));
+                    }
+                }
+                decoder.decode_type(fuel::Input::type_id(), serialize(&input));
+            }
+
+            for output in outputs {
+                match &output {
+                    fuel::Output::CoinOutput(coin) => {
+                        #(#coin_output_checks)*
+                        decoder.decode_type(fuel::CoinOutput::type_id(), serialize(coin));
+                    }
+                    fuel::Output::ContractOutput(contract) => {
+                        let contract_id = input_contract_ids
+                            .get(contract.input_index as usize)
+                            .copied()
+                            .flatten();
+                        match contract_id {
+                            Some(id) if is_subscribed_to_contract(&id) => {
+                                decoder.decode_type(fuel::ContractOutput::type_id(), serialize(contract));
+                            }
+                            _ => {
+                                debug!("Not subscribed to this contract. Will skip this output. <('-'<)");
+                                continue;
+                            }
+                        }
+                    }
+                    fuel::Output::ChangeOutput(change) => {
+                        #(#change_output_checks)*
+                        decoder.decode_type(fuel::ChangeOutput::type_id(), serialize(change));
+                    }
+                    fuel::Output::VariableOutput(variable) => {
+                        #(#variable_output_checks)*
+                        decoder.decode_type(fuel::VariableOutput::type_id(), serialize(variable));
+                    }
+                    fuel::Output::Message(message) => {
+                        #(#message_output_checks)*
+                        decoder.decode_type(fuel::MessageOutput::type_id(), serialize(message));
+                    }
+                    fuel::Output::ContractCreated(created) => {
+                        if !is_subscribed_to_contract(&created.contract_id) {
+                            debug!("Not subscribed to this contract. Will skip this output. <('-'<)");
+                            continue;
+                        }
+                        #contract_checks
+                        decoder.decode_type(fuel::ContractCreated::type_id(), serialize(created));
+                    }
+                    fuel::Output::Unknown => {
+                        continue;
+                    }
+                }
+                decoder.decode_type(fuel::Output::type_id(), serialize(&output));
+            }
+
+            if tx_is_subscribed {
+                decoder.decode_type(fuel::TransactionData::type_id(), serialize(&tx));
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let is_subscribed_to_contract = if uses_transaction_types {
+        is_subscribed_to_contract
+    } else {
+        quote! {}
+    };
+
+    let handled_types_tokens = match manifest.execution_source() {
+        ExecutionSource::Wasm => {
+            let mut handled_types = handled_types.into_iter().collect::<Vec<String>>();
+            handled_types.sort();
+            const_item("HANDLED_TYPES", &handled_types.join(","))
+        }
+        ExecutionSource::Native => quote! {},
+    };
+
     let decoder_struct = quote! {
         #[derive(Default)]
         struct Decoders {
@@ -429,6 +747,10 @@ fn process_fn_items(
                 self.blockdata_decoded.push(data);
             }
 
+            pub fn clear_transaction_types(&mut self) {
+                #(#transaction_type_fields)*
+            }
+
             pub fn decode_return_type(&mut self, sel: u64, data: Vec<u8>) {
                 let ty_id = self.selector_to_type_id(sel);
                 self.decode_type(ty_id, data);
@@ -457,10 +779,16 @@ fn process_fn_items(
         quote! {
             #subscribed_contract_ids
 
+            #(#subscription_ids)*
+
+            #is_subscribed_to_contract
+
             for block in blocks {
 
                 #start_block
 
+                #set_block_height
+
                 let mut decoder = Decoders::default();
 
                 let ty_id = BlockData::type_id();
@@ -469,6 +797,8 @@ fn process_fn_items(
 
                 for tx in block.transactions {
 
+                    #transaction_type_decoders
+
                     let mut return_types = Vec::new();
                     let mut callees = HashSet::new();
 
@@ -493,8 +823,11 @@ fn process_fn_items(
                             }
                             fuel::Receipt::LogData { rb, data, ptr, len, id, .. } => {
                                 #check_if_subscribed_to_contract
-                                decoder.decode_logdata(rb as usize, data);
+                                decoder.decode_logdata(rb as usize, data.clone());
 
+                                let ty_id = LogData::type_id();
+                                let data = serialize(&LogData{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), data, rb, len, ptr });
+                                decoder.decode_type(ty_id, data);
                             }
                             fuel::Receipt::Return { id, val, pc, is } => {
                                 #check_if_subscribed_to_contract
@@ -504,14 +837,20 @@ fn process_fn_items(
                                     decoder.decode_type(ty_id, data);
                                 }
                             }
-                            fuel::Receipt::ReturnData { data, id, .. } => {
+                            fuel::Receipt::ReturnData { id, ptr, len, digest, data, pc, is } => {
                                 #check_if_subscribed_to_contract
                                 if callees.contains(&id) {
                                     let selector = return_types.pop().expect("No return type available. <('-'<)");
-                                    decoder.decode_return_type(selector, data);
+                                    decoder.decode_return_type(selector, data.clone());
+
+                                    let ty_id = ReturnData::type_id();
+                                    let data = serialize(&ReturnData{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), ptr, len, digest: <[u8; 32]>::try_from(digest).unwrap().into(), data, pc, is });
+                                    decoder.decode_type(ty_id, data);
                                 }
                             }
                             fuel::Receipt::MessageOut { sender, recipient, amount, nonce, len, digest, data, .. } => {
+                                #message_out_checks
+
                                 // TODO: Temporary conversion; remove once we update back to latest fuel-types version.
                                 let nonce_bytes = <[u8; 32]>::try_from(nonce).expect("Could not convert nonce to bytes");
                                 let nonce = Nonce::from(nonce_bytes);
@@ -549,12 +888,14 @@ fn process_fn_items(
                             }
                             fuel::Receipt::Transfer { id, to, asset_id, amount, pc, is, .. } => {
                                 #check_if_subscribed_to_contract
+                                #transfer_checks
                                 let ty_id = Transfer::type_id();
                                 let data = serialize(&Transfer{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), to: <[u8; 32]>::try_from(to).unwrap().into(), asset_id: <[u8; 32]>::try_from(asset_id).unwrap().into(), amount, pc, is });
                                 decoder.decode_type(ty_id, data);
                             }
                             fuel::Receipt::TransferOut { id, to, asset_id, amount, pc, is, .. } => {
                                 #check_if_subscribed_to_contract
+                                #(#transfer_out_checks)*
                                 let ty_id = TransferOut::type_id();
                                 let data = serialize(&TransferOut{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), to: <[u8; 32]>::try_from(to).unwrap().into(), asset_id: <[u8; 32]>::try_from(asset_id).unwrap().into(), amount, pc, is });
                                 decoder.decode_type(ty_id, data);
@@ -587,6 +928,8 @@ fn process_fn_items(
         quote! {
             #decoder_struct
 
+            #handled_types_tokens
+
             #(#handler_fns)*
         },
     )
@@ -774,3 +1117,46 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
 
     proc_macro::TokenStream::from(output)
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_native_handler_block_dispatches_typed_logdata_and_returndata_receipts() {
+        let manifest = Manifest::try_from(
+            r#"
+namespace: test_namespace
+identifier: native_receipts
+graphql_schema: schema.graphql
+module: native
+contract_id: ~
+"#,
+        )
+        .unwrap();
+
+        let indexer_module: ItemMod = syn::parse_str(
+            r#"
+mod indexer {
+    async fn handle_logdata(logdata: LogData) {}
+
+    async fn handle_returndata(returndata: ReturnData) {}
+}"#,
+        )
+        .unwrap();
+
+        let (handler_block, fn_items) = process_fn_items(&manifest, None, indexer_module);
+        let handler_block = handler_block_native(handler_block).to_string();
+        let fn_items = fn_items.to_string();
+
+        // Native handlers are awaited within an async `handle_events`, and receive the
+        // same typed receipts as WASM handlers.
+        assert!(handler_block.contains("async fn handle_events"));
+        assert!(handler_block.contains("let ty_id = LogData :: type_id ()"));
+        assert!(handler_block.contains("let ty_id = ReturnData :: type_id ()"));
+        assert!(handler_block.contains("decoder . dispatch () . await"));
+        assert!(fn_items.contains("async fn handle_logdata (logdata : LogData)"));
+        assert!(fn_items.contains("async fn handle_returndata (returndata : ReturnData)"));
+    }
+}
@@ -45,5 +45,115 @@ fn native_prelude() -> proc_macro2::TokenStream {
             core::{codec::ABIDecoder, Configurables, traits::{Parameterize, Tokenizable}},
             types::{StringToken},
         };
+
+        /// Logger for this indexer. Lines are written to the service's own log, and kept
+        /// along with the indexer's other logged lines by the indexer service.
+        pub struct Logger;
+
+        impl Logger {
+            pub fn error(log: &str) {
+                fuel_indexer_utils::plugin::native::Logger::error(log);
+                Self::record("error", log);
+            }
+
+            pub fn warn(log: &str) {
+                fuel_indexer_utils::plugin::native::Logger::warn(log);
+                Self::record("warn", log);
+            }
+
+            pub fn info(log: &str) {
+                fuel_indexer_utils::plugin::native::Logger::info(log);
+                Self::record("info", log);
+            }
+
+            pub fn debug(log: &str) {
+                fuel_indexer_utils::plugin::native::Logger::debug(log);
+                Self::record("debug", log);
+            }
+
+            pub fn trace(log: &str) {
+                fuel_indexer_utils::plugin::native::Logger::trace(log);
+                Self::record("trace", log);
+            }
+
+            fn record(level: &str, log: &str) {
+                unsafe {
+                    if let Some(d) = &db {
+                        // Handlers only hold the database across their own `load`s and
+                        // `save`s, so it's free whenever they log.
+                        match d.try_lock() {
+                            Ok(mut d) => d.record_log(level, log.to_string()),
+                            Err(_) => fuel_indexer_utils::plugin::native::Logger::warn("Database is busy, logged line was not kept."),
+                        }
+                    }
+                }
+            }
+        }
+
+        /// Key/value store provided by the indexer service, for state that doesn't belong
+        /// in an entity. Writes are part of the same transaction as entity writes, and a
+        /// failed read or write fails the blocks being handled.
+        pub struct Store;
+
+        impl Store {
+            /// Get the value stored under `key`, if any.
+            pub async fn get<K: Serialize, V: fuel_indexer_utils::plugin::serde::de::DeserializeOwned>(key: &K) -> Option<V> {
+                unsafe {
+                    match &db {
+                        Some(d) => {
+                            d.lock().await.get_kv(&serialize(key)).await.map(|bytes| deserialize(&bytes).expect("Bad serialization."))
+                        }
+                        None => None,
+                    }
+                }
+            }
+
+            /// Store `value` under `key`, replacing any existing value.
+            pub async fn put<K: Serialize, V: Serialize>(key: &K, value: &V) {
+                unsafe {
+                    if let Some(d) = &db {
+                        d.lock().await.put_kv(&serialize(key), &serialize(value)).await;
+                    }
+                }
+            }
+
+            /// Remove the value stored under `key`, if any.
+            pub async fn delete<K: Serialize>(key: &K) {
+                unsafe {
+                    if let Some(d) = &db {
+                        d.lock().await.delete_kv(&serialize(key)).await;
+                    }
+                }
+            }
+        }
+
+        /// Metrics defined by this indexer. Updates are only applied once the blocks
+        /// being handled are committed.
+        pub struct Metrics;
+
+        impl Metrics {
+            /// Increment the counter `name` by `value`.
+            pub async fn increment_counter(name: &str, value: u64) {
+                Self::put(MetricUpdate::Counter { name: name.to_string(), value }).await
+            }
+
+            /// Set the gauge `name` to `value`.
+            pub async fn set_gauge(name: &str, value: f64) {
+                Self::put(MetricUpdate::Gauge { name: name.to_string(), value }).await
+            }
+
+            /// Record `value` in the histogram `name`.
+            pub async fn observe_histogram(name: &str, value: f64) {
+                Self::put(MetricUpdate::Histogram { name: name.to_string(), value }).await
+            }
+
+            async fn put(update: MetricUpdate) {
+                unsafe {
+                    if let Some(d) = &db {
+                        d.lock().await.record_metric(update);
+                    }
+                }
+            }
+        }
     }
 }
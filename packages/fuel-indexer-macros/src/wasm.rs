@@ -1,3 +1,5 @@
+use crate::helpers::const_item;
+use fuel_indexer_types::ffi::FFI_VERSION;
 use quote::quote;
 
 /// Generate the handler block for the wasm execution environment.
@@ -5,14 +7,20 @@ pub fn handler_block_wasm(
     handler_block: proc_macro2::TokenStream,
 ) -> proc_macro2::TokenStream {
     let wasm_prelude = wasm_prelude();
+    let ffi_version = const_item("FFI_VERSION", &FFI_VERSION.to_string());
 
     quote! {
 
         #wasm_prelude
 
+        #ffi_version
+
         #[no_mangle]
         fn handle_events(blob: *mut u8, len: usize) {
             use fuel_indexer_utils::plugin::deserialize;
+            Logger::init();
+            set_panic_hook();
+
             let bytes = unsafe { Vec::from_raw_parts(blob, len, len) };
             let blocks: Vec<BlockData> = match deserialize(&bytes) {
                 Ok(blocks) => blocks,
@@ -2,9 +2,15 @@ use lazy_static::lazy_static;
 use prometheus::{self, register_int_counter, IntCounter};
 use prometheus_client::{
     encoding::EncodeLabelSet,
-    metrics::{family::Family, histogram::Histogram},
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::Gauge,
+        histogram::{exponential_buckets, Histogram},
+    },
     registry::Registry,
 };
+use std::sync::atomic::AtomicU64;
 
 lazy_static! {
     pub static ref TIMING_HISTOGRAM_BUCKETS: Vec<f64> =
@@ -46,18 +52,46 @@ impl Postgres {
     }
 }
 
+pub struct Sqlite {
+    pub registry: Registry,
+    requests: Family<Label, Histogram>,
+}
+
+impl Metric for Sqlite {
+    fn init() -> Self {
+        let mut registry = Registry::default();
+        let requests = Family::<Label, Histogram>::new_with_constructor(|| {
+            Histogram::new(TIMING_HISTOGRAM_BUCKETS.iter().cloned())
+        });
+        registry.register("sqlite_operation_duration", "", requests.clone());
+
+        Self { registry, requests }
+    }
+}
+
+impl Sqlite {
+    pub fn record(&self, query: &str, time: f64) {
+        let histogram = self.requests.get_or_create(&Label {
+            path: query.to_string(),
+        });
+        histogram.observe(time);
+    }
+}
+
 pub struct Database {
     pub write_ops: IntCounter,
     pub read_ops: IntCounter,
     pub bytes_written: IntCounter,
     pub bytes_read: IntCounter,
     pub postgres: Postgres,
+    pub sqlite: Sqlite,
 }
 
 impl Metric for Database {
     fn init() -> Self {
         Self {
             postgres: Postgres::init(),
+            sqlite: Sqlite::init(),
             write_ops: register_int_counter!("write_ops", "Count of write operations.")
                 .unwrap(),
             read_ops: register_int_counter!("read_ops", "Count of read operations.")
@@ -101,9 +135,150 @@ impl Web {
     }
 }
 
+/// Labels of a metric defined by an indexer.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct IndexerLabel {
+    namespace: String,
+    identifier: String,
+    name: String,
+}
+
+impl IndexerLabel {
+    fn new(namespace: &str, identifier: &str, name: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            identifier: identifier.to_string(),
+            name: name.to_string(),
+        }
+    }
+}
+
+/// Metrics defined by indexers themselves, labelled with the namespace and
+/// identifier of the indexer that defined them.
+pub struct Indexer {
+    pub registry: Registry,
+    counters: Family<IndexerLabel, Counter>,
+    gauges: Family<IndexerLabel, Gauge<f64, AtomicU64>>,
+    histograms: Family<IndexerLabel, Histogram>,
+}
+
+impl Metric for Indexer {
+    fn init() -> Self {
+        let mut registry = Registry::default();
+        let counters = Family::<IndexerLabel, Counter>::default();
+        let gauges = Family::<IndexerLabel, Gauge<f64, AtomicU64>>::default();
+        let histograms = Family::<IndexerLabel, Histogram>::new_with_constructor(|| {
+            Histogram::new(exponential_buckets(1., 10., 12))
+        });
+        registry.register(
+            "indexer_counter",
+            "Counters incremented by indexers, labelled with the name each indexer gave them.",
+            counters.clone(),
+        );
+        registry.register(
+            "indexer_gauge",
+            "Gauges set by indexers, labelled with the name each indexer gave them.",
+            gauges.clone(),
+        );
+        registry.register(
+            "indexer_histogram",
+            "Values observed by indexers, labelled with the name each indexer gave them.",
+            histograms.clone(),
+        );
+
+        Self {
+            registry,
+            counters,
+            gauges,
+            histograms,
+        }
+    }
+}
+
+impl Indexer {
+    pub fn increment_counter(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        name: &str,
+        value: u64,
+    ) {
+        self.counters
+            .get_or_create(&IndexerLabel::new(namespace, identifier, name))
+            .inc_by(value);
+    }
+
+    pub fn set_gauge(&self, namespace: &str, identifier: &str, name: &str, value: f64) {
+        self.gauges
+            .get_or_create(&IndexerLabel::new(namespace, identifier, name))
+            .set(value);
+    }
+
+    pub fn observe_histogram(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        name: &str,
+        value: f64,
+    ) {
+        self.histograms
+            .get_or_create(&IndexerLabel::new(namespace, identifier, name))
+            .observe(value);
+    }
+}
+
+/// Labels of a table pruned according to its indexer's retention policies.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct PruningLabel {
+    namespace: String,
+    identifier: String,
+    table: String,
+}
+
+/// Metrics of the runs that delete the expired rows of indexers' tables.
+pub struct Pruning {
+    pub registry: Registry,
+    runs: Family<PruningLabel, Counter>,
+    rows: Family<PruningLabel, Counter>,
+}
+
+impl Metric for Pruning {
+    fn init() -> Self {
+        let mut registry = Registry::default();
+        let runs = Family::<PruningLabel, Counter>::default();
+        let rows = Family::<PruningLabel, Counter>::default();
+        registry.register("pruning_runs", "Count of pruning runs.", runs.clone());
+        registry.register(
+            "pruned_rows",
+            "Count of expired rows removed.",
+            rows.clone(),
+        );
+
+        Self {
+            registry,
+            runs,
+            rows,
+        }
+    }
+}
+
+impl Pruning {
+    pub fn record(&self, namespace: &str, identifier: &str, table: &str, rows: u64) {
+        let label = PruningLabel {
+            namespace: namespace.to_string(),
+            identifier: identifier.to_string(),
+            table: table.to_string(),
+        };
+        self.runs.get_or_create(&label).inc();
+        self.rows.get_or_create(&label).inc_by(rows);
+    }
+}
+
 pub struct Metrics {
     pub web: Web,
     pub db: Database,
+    pub indexer: Indexer,
+    pub pruning: Pruning,
 }
 
 impl Metric for Metrics {
@@ -111,6 +286,8 @@ impl Metric for Metrics {
         Self {
             web: Web::init(),
             db: Database::init(),
+            indexer: Indexer::init(),
+            pruning: Pruning::init(),
         }
     }
 }
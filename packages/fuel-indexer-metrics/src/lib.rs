@@ -26,6 +26,18 @@ pub fn encode_metrics_response() -> impl IntoResponse {
         return error_body();
     }
 
+    if encode(&mut encoded, &METRICS.db.sqlite.registry).is_err() {
+        return error_body();
+    }
+
+    if encode(&mut encoded, &METRICS.indexer.registry).is_err() {
+        return error_body();
+    }
+
+    if encode(&mut encoded, &METRICS.pruning.registry).is_err() {
+        return error_body();
+    }
+
     Response::builder()
         .status(StatusCode::OK)
         .body(Body::from(encoded))
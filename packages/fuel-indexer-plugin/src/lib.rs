@@ -8,7 +8,11 @@ extern crate alloc;
 
 pub mod types {
     pub use fuel_indexer_schema::FtColumn;
-    pub use fuel_indexer_types::fuel::{BlockData, TxId};
+    pub use fuel_indexer_types::fuel::{
+        BlockData, ChangeOutput, CoinOutput, ContractCreated, ContractOutput, Input,
+        InputCoin, InputContract, InputMessage, MessageOutput, Output, TransactionData,
+        TxId, VariableOutput,
+    };
 
     // Traits needed to access client type fields. Could also include this as a sub-module
     // of `fuel_indexer_types::fuel`.
@@ -27,6 +31,9 @@ pub mod utils {
 pub use bincode;
 pub use fuel_indexer_lib::utils::{deserialize, serialize};
 
+// Re-exported so that indexers can log through the `log` facade without depending on it.
+pub use log;
+
 // Specifically we import `serde` here for the `Serialize` and `Deserialize` traits
 // else the user would have to explicity import these in their indexer modules.
 pub use serde;
@@ -4,6 +4,7 @@ use alloc::vec::Vec;
 use fuel_indexer_lib::utils::{deserialize, serialize};
 use fuel_indexer_schema::FtColumn;
 use fuel_indexer_types::ffi::*;
+use serde::{de::DeserializeOwned, Serialize};
 
 pub use bincode;
 pub use hex::FromHex;
@@ -15,12 +16,32 @@ extern "C" {
     fn ff_get_object(type_id: i64, ptr: *const u8, len: *mut u8) -> *mut u8;
     fn ff_put_object(type_id: i64, ptr: *const u8, len: u32);
     fn ff_log_data(ptr: *const u8, len: u32, log_level: u32);
+    fn ff_set_block_height(height: u64);
+    fn ff_kv_get(key_ptr: *const u8, key_len: u32, len: *mut u8) -> *mut u8;
+    fn ff_kv_put(key_ptr: *const u8, key_len: u32, val_ptr: *const u8, val_len: u32);
+    fn ff_kv_delete(key_ptr: *const u8, key_len: u32);
+    fn ff_put_metric(ptr: *const u8, len: u32);
+    fn ff_put_panic(ptr: *const u8, len: u32);
 }
 
-// TODO: more to do here, hook up to 'impl log::Log for Logger'
 pub struct Logger;
 
+static LOGGER: Logger = Logger;
+
 impl Logger {
+    /// Route records logged through the `log` facade (e.g., `log::info!`) to the
+    /// indexer service.
+    pub fn init() {
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(log::LevelFilter::Trace);
+        }
+    }
+
+    /// Tag subsequently logged lines with the height of the block being handled.
+    pub fn set_block_height(height: u64) {
+        unsafe { ff_set_block_height(height) }
+    }
+
     pub fn error(log: &str) {
         unsafe { ff_log_data(log.as_ptr(), log.len() as u32, LOG_LEVEL_ERROR) }
     }
@@ -42,6 +63,25 @@ impl Logger {
     }
 }
 
+impl log::Log for Logger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        let log = alloc::format!("{}", record.args());
+        match record.level() {
+            log::Level::Error => Self::error(&log),
+            log::Level::Warn => Self::warn(&log),
+            log::Level::Info => Self::info(&log),
+            log::Level::Debug => Self::debug(&log),
+            log::Level::Trace => Self::trace(&log),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
 pub trait Entity: Sized + PartialEq + Eq + std::fmt::Debug {
     const TYPE_ID: i64;
 
@@ -80,6 +120,126 @@ pub trait Entity: Sized + PartialEq + Eq + std::fmt::Debug {
     }
 }
 
+/// Report panics raised by indexer code to the indexer service, which otherwise only
+/// learns that the module trapped.
+pub fn set_panic_hook() {
+    static SET_HOOK: std::sync::Once = std::sync::Once::new();
+
+    SET_HOOK.call_once(|| {
+        std::panic::set_hook(alloc::boxed::Box::new(|info| {
+            let payload = info.payload();
+            let message = match payload.downcast_ref::<&str>() {
+                Some(message) => message.to_string(),
+                None => match payload.downcast_ref::<alloc::string::String>() {
+                    Some(message) => message.clone(),
+                    None => alloc::format!("{info}"),
+                },
+            };
+
+            let panic = WasmPanic {
+                message,
+                file: info.location().map(|l| l.file().to_string()),
+                line: info.location().map(|l| l.line()),
+                column: info.location().map(|l| l.column()),
+            };
+
+            unsafe {
+                let buf = serialize(&panic);
+                ff_put_panic(buf.as_ptr(), buf.len() as u32)
+            }
+        }));
+    });
+}
+
+/// Key/value store provided by the indexer service, for state that doesn't belong
+/// in an entity (e.g., counters, or data carried over between blocks).
+///
+/// Keys and values are serialized with `bincode`. Writes are part of the same
+/// transaction as entity writes, so they're committed or reverted together.
+pub struct Store;
+
+impl Store {
+    /// Get the value stored under `key`, if any.
+    pub fn get<K: Serialize, V: DeserializeOwned>(key: &K) -> Option<V> {
+        unsafe {
+            let key = serialize(key);
+            let mut buflen = 0u32.to_le_bytes();
+
+            let ptr = ff_kv_get(key.as_ptr(), key.len() as u32, buflen.as_mut_ptr());
+
+            if !ptr.is_null() {
+                let len = u32::from_le_bytes(buflen) as usize;
+                let bytes = Vec::from_raw_parts(ptr, len, len);
+                Some(deserialize(&bytes).expect("Bad serialization."))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Store `value` under `key`, replacing any existing value.
+    pub fn put<K: Serialize, V: Serialize>(key: &K, value: &V) {
+        unsafe {
+            let key = serialize(key);
+            let value = serialize(value);
+            ff_kv_put(
+                key.as_ptr(),
+                key.len() as u32,
+                value.as_ptr(),
+                value.len() as u32,
+            )
+        }
+    }
+
+    /// Remove the value stored under `key`, if any.
+    pub fn delete<K: Serialize>(key: &K) {
+        unsafe {
+            let key = serialize(key);
+            ff_kv_delete(key.as_ptr(), key.len() as u32)
+        }
+    }
+}
+
+/// Metrics defined by an indexer, exported by the indexer service under the
+/// indexer's namespace and identifier.
+///
+/// Metrics don't need to be registered before they're used. Updates are only
+/// applied once the blocks being handled are committed.
+pub struct Metrics;
+
+impl Metrics {
+    /// Increment the counter `name` by `value`.
+    pub fn increment_counter(name: &str, value: u64) {
+        Self::put(MetricUpdate::Counter {
+            name: name.to_string(),
+            value,
+        })
+    }
+
+    /// Set the gauge `name` to `value`.
+    pub fn set_gauge(name: &str, value: f64) {
+        Self::put(MetricUpdate::Gauge {
+            name: name.to_string(),
+            value,
+        })
+    }
+
+    /// Record `value` in the histogram `name`.
+    pub fn observe_histogram(name: &str, value: f64) {
+        Self::put(MetricUpdate::Histogram {
+            name: name.to_string(),
+            value,
+        })
+    }
+
+    fn put(update: MetricUpdate) {
+        unsafe {
+            let buf = serialize(&update);
+            ff_put_metric(buf.as_ptr(), buf.len() as u32)
+        }
+    }
+}
+
 #[no_mangle]
 fn alloc_fn(size: u32) -> *const u8 {
     let vec = Vec::with_capacity(size as usize);
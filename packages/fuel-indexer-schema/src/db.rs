@@ -8,6 +8,8 @@
 //! to WebAssembly (WASM).
 
 pub mod manager;
+pub mod migration;
+pub mod snapshot;
 pub mod tables;
 
 use fuel_indexer_database::IndexerDatabaseError;
@@ -43,4 +45,12 @@ pub enum IndexerSchemaDbError {
     ParsedError(#[from] fuel_indexer_lib::graphql::ParsedError),
     #[error("Manifest Error: {0:?}")]
     ManifestError(#[from] fuel_indexer_lib::manifest::ManifestError),
+    #[error("Schema can't be migrated in place. {0}")]
+    IncompatibleSchemaMigration(String),
+    #[error("Snapshot can't be restored. {0}")]
+    IncompatibleSnapshot(String),
+    #[error("Indexer({0}) already exists.")]
+    IndexerExists(String),
+    #[error("Hex error: {0:?}")]
+    HexError(#[from] hex::FromHexError),
 }
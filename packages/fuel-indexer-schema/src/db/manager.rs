@@ -3,7 +3,10 @@
 //! A wrapper object that encapsulates `IndexerSchema` and provides stateful database
 //! connectivity.
 
-use crate::db::{tables::IndexerSchema, IndexerSchemaDbResult};
+use crate::db::{
+    migration::SchemaMigration, tables::IndexerSchema, IndexerSchemaDbError,
+    IndexerSchemaDbResult,
+};
 use fuel_indexer_database::{queries, IndexerConnection, IndexerConnectionPool};
 use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
 use tracing::info;
@@ -21,6 +24,9 @@ impl SchemaManager {
     }
 
     /// Create a new schema for the given indexer.
+    ///
+    /// If the indexer already has a schema of another version, the existing schema is
+    /// migrated in place instead. Migrations with incompatible changes are refused.
     pub async fn new_schema(
         &self,
         namespace: &str,
@@ -32,7 +38,17 @@ impl SchemaManager {
         let schema = GraphQLSchema::new(schema.to_string());
         let version = schema.version();
 
-        if !queries::schema_exists(conn, namespace, identifier, version).await? {
+        if queries::schema_exists(conn, namespace, identifier, version).await? {
+            return Ok(());
+        }
+
+        if queries::graph_root_latest(conn, namespace, identifier)
+            .await
+            .is_ok()
+        {
+            self.migrate_schema(namespace, identifier, &schema, exec_source, conn)
+                .await?;
+        } else {
             info!("Creating schema for Indexer({namespace}.{identifier}) with Version({version}).");
             let _ = IndexerSchema::new(
                 namespace,
@@ -47,6 +63,67 @@ impl SchemaManager {
         Ok(())
     }
 
+    /// Plan a migration of the given indexer's existing schema to a new schema,
+    /// without applying it.
+    pub async fn plan_migration(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        schema: &GraphQLSchema,
+        exec_source: ExecutionSource,
+    ) -> IndexerSchemaDbResult<SchemaMigration> {
+        let current = self.load_schema(namespace, identifier).await?;
+        let next = IndexerSchema::new(
+            namespace,
+            identifier,
+            schema,
+            self.pool.database_type(),
+            exec_source,
+        )?;
+
+        Ok(SchemaMigration::plan(&current, &next))
+    }
+
+    /// Migrate the given indexer's existing schema to a new schema in place.
+    ///
+    /// Returns an `IndexerSchemaDbError::IncompatibleSchemaMigration` if the migration
+    /// includes changes that can't be applied in place.
+    pub async fn migrate_schema(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        schema: &GraphQLSchema,
+        exec_source: ExecutionSource,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<SchemaMigration> {
+        let migration = self
+            .plan_migration(namespace, identifier, schema, exec_source.clone())
+            .await?;
+
+        if !migration.is_compatible() {
+            return Err(IndexerSchemaDbError::IncompatibleSchemaMigration(
+                migration.report(),
+            ));
+        }
+
+        info!("Migrating schema for Indexer({namespace}.{identifier}). {migration}");
+        for stmnt in migration.statements() {
+            info!("Migration statement: {stmnt}");
+        }
+
+        let _ = IndexerSchema::new(
+            namespace,
+            identifier,
+            schema,
+            self.pool.database_type(),
+            exec_source,
+        )?
+        .apply_migration(&migration, conn)
+        .await?;
+
+        Ok(migration)
+    }
+
     /// Load an existing schema for the given indexer.
     pub async fn load_schema(
         &self,
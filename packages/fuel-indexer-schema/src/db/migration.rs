@@ -0,0 +1,886 @@
+//! # fuel_indexer_schema::db::migration
+//!
+//! Plans in-place migrations between two versions of an indexer's schema.
+//!
+//! Only additive and otherwise compatible changes (new tables, new nullable columns,
+//! new indexes) can be migrated in place. Anything else is reported as an
+//! `IncompatibleSchemaChange`, and requires the indexer to be re-indexed from scratch.
+
+use crate::db::tables::IndexerSchema;
+use fuel_indexer_database::{
+    types::{Column, Constraint, Persistence, SqlFragment, SqlNamed, Table},
+    DbType,
+};
+use fuel_indexer_lib::graphql::types::SearchCol;
+use serde::Serialize;
+use std::{collections::HashMap, fmt};
+
+/// A change between two versions of a schema that can be applied in place.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum SchemaChange {
+    /// A new table is created.
+    CreateTable { table: String },
+
+    /// A new nullable column is added to an existing table.
+    AddColumn { table: String, column: String },
+
+    /// The `NOT NULL` constraint of an existing column is removed.
+    DropNotNull { table: String, column: String },
+
+    /// A new index is created on an existing table.
+    CreateIndex { table: String, index: String },
+
+    /// A new foreign key is added to an existing table.
+    AddForeignKey { table: String, foreign_key: String },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::CreateTable { table } => write!(f, "Create table '{table}'."),
+            SchemaChange::AddColumn { table, column } => {
+                write!(f, "Add column '{column}' to table '{table}'.")
+            }
+            SchemaChange::DropNotNull { table, column } => {
+                write!(f, "Make column '{column}' of table '{table}' nullable.")
+            }
+            SchemaChange::CreateIndex { table, index } => {
+                write!(f, "Create index '{index}' on table '{table}'.")
+            }
+            SchemaChange::AddForeignKey { table, foreign_key } => {
+                write!(f, "Add foreign key '{foreign_key}' to table '{table}'.")
+            }
+        }
+    }
+}
+
+/// A change between two versions of a schema that can't be applied in place.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum IncompatibleSchemaChange {
+    /// An existing table is removed.
+    TableRemoved { table: String },
+
+    /// An existing column is removed.
+    ColumnRemoved { table: String, column: String },
+
+    /// The type of an existing column is changed.
+    ColumnTypeChanged {
+        table: String,
+        column: String,
+        from: String,
+        to: String,
+    },
+
+    /// A new column can't be null, so existing rows would have no value for it.
+    NonNullableColumnAdded { table: String, column: String },
+
+    /// An existing column is made non-nullable.
+    ColumnMadeNonNullable { table: String, column: String },
+
+    /// An existing column is made nullable, which the database can't do in place.
+    ColumnMadeNullable { table: String, column: String },
+
+    /// An existing column is made unique, which existing rows might violate.
+    UniqueConstraintAdded { table: String, column: String },
+
+    /// An existing column is no longer unique.
+    UniqueConstraintRemoved { table: String, column: String },
+
+    /// An existing index is removed.
+    IndexRemoved { table: String, index: String },
+
+    /// The columns, method, or uniqueness of an existing index are changed.
+    IndexChanged { table: String, index: String },
+
+    /// A foreign key is added to an existing column, which existing rows might violate.
+    ForeignKeyAdded { table: String, foreign_key: String },
+
+    /// An existing foreign key is removed.
+    ForeignKeyRemoved { table: String, foreign_key: String },
+
+    /// An existing table starts or stops keeping the history of its entities.
+    HistoricalChanged { table: String, historical: bool },
+
+    /// The fields of an existing table's full-text search document are changed.
+    SearchFieldsChanged { table: String },
+}
+
+impl fmt::Display for IncompatibleSchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IncompatibleSchemaChange::TableRemoved { table } => {
+                write!(f, "Table '{table}' was removed.")
+            }
+            IncompatibleSchemaChange::ColumnRemoved { table, column } => {
+                write!(f, "Column '{column}' was removed from table '{table}'.")
+            }
+            IncompatibleSchemaChange::ColumnTypeChanged {
+                table,
+                column,
+                from,
+                to,
+            } => write!(
+                f,
+                "Column '{column}' of table '{table}' changed type from '{from}' to '{to}'."
+            ),
+            IncompatibleSchemaChange::NonNullableColumnAdded { table, column } => write!(
+                f,
+                "Column '{column}' was added to table '{table}' but isn't nullable."
+            ),
+            IncompatibleSchemaChange::ColumnMadeNonNullable { table, column } => {
+                write!(f, "Column '{column}' of table '{table}' was made non-nullable.")
+            }
+            IncompatibleSchemaChange::ColumnMadeNullable { table, column } => write!(
+                f,
+                "Column '{column}' of table '{table}' was made nullable, which this database can't do in place."
+            ),
+            IncompatibleSchemaChange::UniqueConstraintAdded { table, column } => {
+                write!(f, "Column '{column}' of table '{table}' was made unique.")
+            }
+            IncompatibleSchemaChange::UniqueConstraintRemoved { table, column } => {
+                write!(f, "Column '{column}' of table '{table}' is no longer unique.")
+            }
+            IncompatibleSchemaChange::IndexRemoved { table, index } => {
+                write!(f, "Index '{index}' was removed from table '{table}'.")
+            }
+            IncompatibleSchemaChange::IndexChanged { table, index } => {
+                write!(f, "Index '{index}' of table '{table}' was changed.")
+            }
+            IncompatibleSchemaChange::ForeignKeyAdded { table, foreign_key } => write!(
+                f,
+                "Foreign key '{foreign_key}' was added to an existing column of table '{table}'."
+            ),
+            IncompatibleSchemaChange::ForeignKeyRemoved { table, foreign_key } => {
+                write!(f, "Foreign key '{foreign_key}' was removed from table '{table}'.")
+            }
+            IncompatibleSchemaChange::HistoricalChanged { table, historical } => {
+                if *historical {
+                    write!(f, "Table '{table}' was made historical.")
+                } else {
+                    write!(f, "Table '{table}' is no longer historical.")
+                }
+            }
+            IncompatibleSchemaChange::SearchFieldsChanged { table } => {
+                write!(f, "Search fields of table '{table}' were changed.")
+            }
+        }
+    }
+}
+
+/// A planned migration between two versions of an indexer's schema.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SchemaMigration {
+    /// Version of the schema being migrated from.
+    pub from_version: String,
+
+    /// Version of the schema being migrated to.
+    pub to_version: String,
+
+    /// Changes that are applied in place.
+    pub changes: Vec<SchemaChange>,
+
+    /// Changes that prevent the migration from being applied.
+    pub incompatible: Vec<IncompatibleSchemaChange>,
+
+    /// SQL statements that apply the migration, in order.
+    pub statements: Vec<String>,
+}
+
+impl SchemaMigration {
+    /// Plan a migration from the `current` version of a schema to the `next` one.
+    pub fn plan(current: &IndexerSchema, next: &IndexerSchema) -> Self {
+        let mut migration = SchemaMigration {
+            from_version: current.version().to_string(),
+            to_version: next.version().to_string(),
+            ..SchemaMigration::default()
+        };
+
+        let current_tables = scalar_tables(current);
+        let next_tables = scalar_tables(next);
+
+        for (name, _) in current_tables.iter() {
+            if !next_tables.contains_key(name) {
+                migration
+                    .incompatible
+                    .push(IncompatibleSchemaChange::TableRemoved {
+                        table: name.to_string(),
+                    });
+            }
+        }
+
+        let mut table_stmnts = Vec::new();
+        let mut column_stmnts = Vec::new();
+        let mut constraint_stmnts = Vec::new();
+
+        for (name, table) in next_tables.iter() {
+            match current_tables.get(name) {
+                None => {
+                    migration.changes.push(SchemaChange::CreateTable {
+                        table: name.to_string(),
+                    });
+                    table_stmnts.push(table.create());
+                    table_stmnts.push(table.create_history());
+                    constraint_stmnts
+                        .extend(table.constraints().iter().map(|c| c.create()));
+                }
+                Some(current_table) => migration.plan_table(
+                    next.db_type(),
+                    current_table,
+                    table,
+                    &mut column_stmnts,
+                    &mut constraint_stmnts,
+                ),
+            }
+        }
+
+        // Tables are created before any constraint so that foreign keys between new
+        // tables can be added in any order.
+        migration.statements = table_stmnts
+            .into_iter()
+            .chain(column_stmnts)
+            .chain(constraint_stmnts)
+            .filter(|stmnt| !stmnt.is_empty())
+            .collect();
+
+        migration
+    }
+
+    /// Plan the changes between two versions of the same table.
+    fn plan_table(
+        &mut self,
+        db_type: &DbType,
+        current: &Table,
+        next: &Table,
+        column_stmnts: &mut Vec<String>,
+        constraint_stmnts: &mut Vec<String>,
+    ) {
+        let table = next.sql_name();
+        let current_columns = columns_by_name(current);
+        let next_columns = columns_by_name(next);
+
+        // Existing entities have no history to start from, and dropping a history
+        // would lose the versions it keeps.
+        if current.is_historical() != next.is_historical() {
+            self.incompatible
+                .push(IncompatibleSchemaChange::HistoricalChanged {
+                    table: table.clone(),
+                    historical: next.is_historical(),
+                });
+        }
+
+        for column in current.columns() {
+            if !next_columns.contains_key(column.name.as_str()) {
+                self.incompatible
+                    .push(IncompatibleSchemaChange::ColumnRemoved {
+                        table: table.clone(),
+                        column: column.name.clone(),
+                    });
+            }
+        }
+
+        for column in next.columns() {
+            let Some(current_column) = current_columns.get(column.name.as_str()) else {
+                if column.nullable {
+                    self.changes.push(SchemaChange::AddColumn {
+                        table: table.clone(),
+                        column: column.name.clone(),
+                    });
+                    column_stmnts.push(next.add_column(column));
+                    if next.is_historical() {
+                        column_stmnts.push(next.add_history_column(column));
+                    }
+                } else {
+                    self.incompatible.push(
+                        IncompatibleSchemaChange::NonNullableColumnAdded {
+                            table: table.clone(),
+                            column: column.name.clone(),
+                        },
+                    );
+                }
+                continue;
+            };
+
+            if current_column.coltype != column.coltype
+                || current_column.array_coltype != column.array_coltype
+            {
+                self.incompatible
+                    .push(IncompatibleSchemaChange::ColumnTypeChanged {
+                        table: table.clone(),
+                        column: column.name.clone(),
+                        from: column_type_name(current_column),
+                        to: column_type_name(column),
+                    });
+                continue;
+            }
+
+            match (current_column.nullable, column.nullable) {
+                (true, false) => {
+                    self.incompatible.push(
+                        IncompatibleSchemaChange::ColumnMadeNonNullable {
+                            table: table.clone(),
+                            column: column.name.clone(),
+                        },
+                    );
+                }
+                (false, true) => match db_type {
+                    DbType::Postgres => {
+                        self.changes.push(SchemaChange::DropNotNull {
+                            table: table.clone(),
+                            column: column.name.clone(),
+                        });
+                        column_stmnts.push(next.drop_not_null(column));
+                        if next.is_historical() {
+                            column_stmnts.push(next.drop_history_not_null(column));
+                        }
+                    }
+                    // SQLite can't alter an existing column.
+                    DbType::Sqlite => {
+                        self.incompatible.push(
+                            IncompatibleSchemaChange::ColumnMadeNullable {
+                                table: table.clone(),
+                                column: column.name.clone(),
+                            },
+                        );
+                    }
+                },
+                _ => {}
+            }
+
+            match (current_column.unique, column.unique) {
+                (false, true) => {
+                    self.incompatible.push(
+                        IncompatibleSchemaChange::UniqueConstraintAdded {
+                            table: table.clone(),
+                            column: column.name.clone(),
+                        },
+                    );
+                }
+                (true, false) => {
+                    self.incompatible.push(
+                        IncompatibleSchemaChange::UniqueConstraintRemoved {
+                            table: table.clone(),
+                            column: column.name.clone(),
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        // The search document of existing rows is generated when its column is added,
+        // after any new column it's made of, but a generated column can't be redefined
+        // in place.
+        if current.search_fields() != next.search_fields() {
+            let search_column = next.add_search_column();
+            if current.search_fields().is_empty() {
+                if !search_column.is_empty() {
+                    self.changes.push(SchemaChange::AddColumn {
+                        table: table.clone(),
+                        column: SearchCol::to_lowercase_string(),
+                    });
+                    column_stmnts.push(search_column);
+                    if next.is_historical() {
+                        column_stmnts.push(next.add_history_search_column());
+                    }
+                }
+            } else if *db_type == DbType::Postgres {
+                self.incompatible
+                    .push(IncompatibleSchemaChange::SearchFieldsChanged {
+                        table: table.clone(),
+                    });
+            }
+        }
+
+        let current_constraints = current
+            .constraints()
+            .iter()
+            .map(|c| (c.sql_name(), c))
+            .collect::<HashMap<String, &Constraint>>();
+        let next_constraints = next
+            .constraints()
+            .iter()
+            .map(|c| (c.sql_name(), c))
+            .collect::<HashMap<String, &Constraint>>();
+
+        // Unique indexes that come and go with a column's `@unique` directive are
+        // reported along with the column itself.
+        let unique_changed = |column: &str| match (
+            current_columns.get(column),
+            next_columns.get(column),
+        ) {
+            (Some(current), Some(next)) => current.unique != next.unique,
+            _ => false,
+        };
+
+        for constraint in current.constraints() {
+            let name = constraint.sql_name();
+            let columns = constraint_columns(constraint);
+
+            // Constraints of removed columns go along with the column.
+            if columns.iter().any(|c| !next_columns.contains_key(c))
+                || (columns.len() == 1 && unique_changed(columns[0]))
+            {
+                continue;
+            }
+
+            if let Some(next_constraint) = next_constraints.get(&name) {
+                if let (Constraint::Index(current_idx), Constraint::Index(next_idx)) =
+                    (constraint, next_constraint)
+                {
+                    if current_idx.method != next_idx.method
+                        || current_idx.unique != next_idx.unique
+                        || current_idx.column_names() != next_idx.column_names()
+                    {
+                        self.incompatible
+                            .push(IncompatibleSchemaChange::IndexChanged {
+                                table: table.clone(),
+                                index: name,
+                            });
+                    }
+                }
+                continue;
+            }
+
+            self.incompatible.push(match constraint {
+                Constraint::Index(_) => IncompatibleSchemaChange::IndexRemoved {
+                    table: table.clone(),
+                    index: name,
+                },
+                Constraint::Fk(_) => IncompatibleSchemaChange::ForeignKeyRemoved {
+                    table: table.clone(),
+                    foreign_key: name,
+                },
+            });
+        }
+
+        for constraint in next.constraints() {
+            let name = constraint.sql_name();
+            if current_constraints.contains_key(&name) {
+                continue;
+            }
+
+            let columns = constraint_columns(constraint);
+            let is_new_column = columns.iter().any(|c| !current_columns.contains_key(c));
+
+            match constraint {
+                Constraint::Index(idx) => {
+                    // Existing rows can only violate a unique index if all of its columns
+                    // already exist. New columns are null for all of them, and nulls are
+                    // never equal to each other.
+                    if idx.unique && !is_new_column {
+                        if columns.len() == 1 && unique_changed(columns[0]) {
+                            continue;
+                        }
+
+                        self.incompatible.push(
+                            IncompatibleSchemaChange::UniqueConstraintAdded {
+                                table: table.clone(),
+                                column: columns.join(", "),
+                            },
+                        );
+                        continue;
+                    }
+
+                    self.changes.push(SchemaChange::CreateIndex {
+                        table: table.clone(),
+                        index: name,
+                    });
+                    constraint_stmnts.push(idx.create());
+                }
+                Constraint::Fk(fk) => {
+                    if !is_new_column {
+                        self.incompatible.push(
+                            IncompatibleSchemaChange::ForeignKeyAdded {
+                                table: table.clone(),
+                                foreign_key: name,
+                            },
+                        );
+                        continue;
+                    }
+
+                    self.changes.push(SchemaChange::AddForeignKey {
+                        table: table.clone(),
+                        foreign_key: name,
+                    });
+                    // SQLite's foreign keys are added along with the column.
+                    constraint_stmnts.push(fk.create());
+                }
+            }
+        }
+    }
+
+    /// Whether the migration can be applied in place.
+    pub fn is_compatible(&self) -> bool {
+        self.incompatible.is_empty()
+    }
+
+    /// Whether the migration leaves the database untouched.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.incompatible.is_empty()
+    }
+
+    /// SQL statements that apply the migration, in order.
+    pub fn statements(&self) -> &Vec<String> {
+        &self.statements
+    }
+
+    /// Return a human-readable report of the migration.
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "Migration of schema Version({}) to Version({}):",
+            self.from_version, self.to_version
+        );
+
+        if self.is_empty() {
+            report.push_str("\n  No changes.");
+        }
+
+        for change in self.changes.iter() {
+            report.push_str(&format!("\n  + {change}"));
+        }
+
+        for change in self.incompatible.iter() {
+            report.push_str(&format!("\n  ! {change}"));
+        }
+
+        if !self.is_compatible() {
+            report.push_str(
+                "\nThese changes can't be applied in place. The indexer must be replaced and re-indexed from scratch.",
+            );
+        }
+
+        report
+    }
+}
+
+impl fmt::Display for SchemaMigration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.report())
+    }
+}
+
+/// Return the tables of a schema that are persisted to the database, by name.
+fn scalar_tables(schema: &IndexerSchema) -> std::collections::BTreeMap<String, &Table> {
+    schema
+        .tables()
+        .iter()
+        .filter(|t| t.persistence() == Persistence::Scalar)
+        .map(|t| (t.sql_name(), t))
+        .collect()
+}
+
+/// Return the columns of a table by name.
+fn columns_by_name(table: &Table) -> HashMap<&str, &Column> {
+    table
+        .columns()
+        .iter()
+        .map(|c| (c.name.as_str(), c))
+        .collect()
+}
+
+/// Return the names of the columns a constraint is applied to.
+fn constraint_columns(constraint: &Constraint) -> Vec<&str> {
+    match constraint {
+        Constraint::Index(idx) => idx.column_names(),
+        Constraint::Fk(fk) => vec![fk.column_name.as_str()],
+    }
+}
+
+/// Return the name of a column's type, including the type of its elements for arrays.
+fn column_type_name(column: &Column) -> String {
+    match column.array_coltype {
+        Some(array_coltype) => format!("{}({})", column.coltype, array_coltype),
+        None => column.coltype.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+
+    fn indexer_schema(schema: &str, db_type: DbType) -> IndexerSchema {
+        IndexerSchema::new(
+            "test_namespace",
+            "index1",
+            &GraphQLSchema::new(schema.to_string()),
+            db_type,
+            ExecutionSource::Wasm,
+        )
+        .unwrap()
+    }
+
+    const SCHEMA: &str = r#"
+type Account {
+    id: ID!
+    balance: UInt8!
+}
+"#;
+
+    #[test]
+    fn test_can_plan_additive_migration() {
+        let current = indexer_schema(SCHEMA, DbType::Postgres);
+        let next = indexer_schema(
+            r#"
+type Account {
+    id: ID!
+    balance: UInt8!
+    label: Charfield @unique
+}
+
+type Transfer {
+    id: ID!
+    account: Account!
+}
+"#,
+            DbType::Postgres,
+        );
+
+        let migration = SchemaMigration::plan(&current, &next);
+
+        assert!(migration.is_compatible());
+        assert_eq!(
+            migration.changes,
+            vec![
+                SchemaChange::AddColumn {
+                    table: "account".to_string(),
+                    column: "label".to_string(),
+                },
+                SchemaChange::CreateIndex {
+                    table: "account".to_string(),
+                    index: "account_label_idx".to_string(),
+                },
+                SchemaChange::CreateTable {
+                    table: "transfer".to_string(),
+                },
+                SchemaChange::CreateTable {
+                    table: "transfers_accounts".to_string(),
+                },
+            ]
+        );
+        // New tables are created before the columns of existing tables are added.
+        assert!(migration.statements()[0]
+            .starts_with("CREATE TABLE test_namespace_index1.transfer"));
+        assert!(migration.statements()[1]
+            .starts_with("CREATE TABLE test_namespace_index1.transfers_accounts"));
+        assert!(migration.statements()[2]
+            .starts_with("ALTER TABLE test_namespace_index1.account ADD COLUMN label"));
+        assert!(migration
+            .statements()
+            .iter()
+            .any(|s| s.starts_with("CREATE UNIQUE INDEX account_label_idx")));
+        assert!(migration.statements().iter().any(|s| s
+            .starts_with("ALTER TABLE test_namespace_index1.transfer ADD CONSTRAINT")));
+    }
+
+    #[test]
+    fn test_refuses_incompatible_migration() {
+        let current = indexer_schema(SCHEMA, DbType::Postgres);
+        let next = indexer_schema(
+            r#"
+type Account {
+    id: ID!
+    balance: UInt4!
+    nonce: UInt8!
+}
+"#,
+            DbType::Postgres,
+        );
+
+        let migration = SchemaMigration::plan(&current, &next);
+
+        assert!(!migration.is_compatible());
+        assert_eq!(
+            migration.incompatible,
+            vec![
+                IncompatibleSchemaChange::ColumnTypeChanged {
+                    table: "account".to_string(),
+                    column: "balance".to_string(),
+                    from: "UInt8".to_string(),
+                    to: "UInt4".to_string(),
+                },
+                IncompatibleSchemaChange::NonNullableColumnAdded {
+                    table: "account".to_string(),
+                    column: "nonce".to_string(),
+                },
+            ]
+        );
+        assert!(migration.report().contains("can't be applied in place"));
+    }
+
+    #[test]
+    fn test_plans_composite_index_changes() {
+        let current = indexer_schema(
+            r#"
+type Account @index(fields: ["balance", "nonce"]) {
+    id: ID!
+    balance: UInt8!
+    nonce: UInt8!
+}
+"#,
+            DbType::Postgres,
+        );
+        let next = indexer_schema(
+            r#"
+type Account @index(fields: ["balance", "nonce"], method: HASH) @index(fields: ["id", "nonce"], unique: true) @index(fields: ["nonce", "label"], unique: true) {
+    id: ID!
+    balance: UInt8!
+    nonce: UInt8!
+    label: Charfield
+}
+"#,
+            DbType::Postgres,
+        );
+
+        let migration = SchemaMigration::plan(&current, &next);
+
+        assert_eq!(
+            migration.incompatible,
+            vec![
+                IncompatibleSchemaChange::IndexChanged {
+                    table: "account".to_string(),
+                    index: "account_balance_nonce_idx".to_string(),
+                },
+                IncompatibleSchemaChange::UniqueConstraintAdded {
+                    table: "account".to_string(),
+                    column: "id, nonce".to_string(),
+                },
+            ]
+        );
+        assert!(migration.changes.contains(&SchemaChange::CreateIndex {
+            table: "account".to_string(),
+            index: "account_nonce_label_idx".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_migrates_history_of_historical_tables() {
+        let current = indexer_schema(
+            r#"
+type Account @historical {
+    id: ID!
+    balance: UInt8!
+}
+"#,
+            DbType::Postgres,
+        );
+        let next = indexer_schema(
+            r#"
+type Account @historical {
+    id: ID!
+    balance: UInt8!
+    label: Charfield
+}
+"#,
+            DbType::Postgres,
+        );
+
+        let migration = SchemaMigration::plan(&current, &next);
+
+        assert!(migration.is_compatible());
+        assert_eq!(
+            migration.statements(),
+            &vec![
+                "ALTER TABLE test_namespace_index1.account ADD COLUMN label varchar(255);"
+                    .to_string(),
+                "ALTER TABLE test_namespace_index1.account_history ADD COLUMN label varchar(255);"
+                    .to_string(),
+            ]
+        );
+
+        let migration =
+            SchemaMigration::plan(&indexer_schema(SCHEMA, DbType::Postgres), &next);
+
+        assert!(migration.incompatible.contains(
+            &IncompatibleSchemaChange::HistoricalChanged {
+                table: "account".to_string(),
+                historical: true,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_adds_search_column_to_existing_table() {
+        let next = indexer_schema(
+            r#"
+type Account {
+    id: ID!
+    balance: UInt8!
+    label: Charfield @search
+}
+"#,
+            DbType::Postgres,
+        );
+
+        let migration =
+            SchemaMigration::plan(&indexer_schema(SCHEMA, DbType::Postgres), &next);
+
+        assert!(migration.is_compatible());
+        assert_eq!(
+            migration.statements(),
+            &vec![
+                "ALTER TABLE test_namespace_index1.account ADD COLUMN label varchar(255);"
+                    .to_string(),
+                "ALTER TABLE test_namespace_index1.account ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (to_tsvector('english', coalesce(label::text, ''))) STORED;"
+                    .to_string(),
+                "CREATE INDEX account_search_vector_idx ON test_namespace_index1.account USING gin (search_vector);"
+                    .to_string(),
+            ]
+        );
+
+        let migration = SchemaMigration::plan(
+            &next,
+            &indexer_schema(
+                r#"
+type Account {
+    id: ID!
+    balance: UInt8!
+    label: Charfield @search
+    bio: Charfield @search
+}
+"#,
+                DbType::Postgres,
+            ),
+        );
+
+        assert!(migration.incompatible.contains(
+            &IncompatibleSchemaChange::SearchFieldsChanged {
+                table: "account".to_string(),
+            }
+        ));
+    }
+
+    #[test]
+    fn test_plans_migration_per_database_type() {
+        let current = indexer_schema(SCHEMA, DbType::Sqlite);
+        let next = indexer_schema(
+            r#"
+type Account {
+    id: ID!
+    balance: UInt8
+    label: Charfield
+}
+"#,
+            DbType::Sqlite,
+        );
+
+        let migration = SchemaMigration::plan(&current, &next);
+
+        assert_eq!(
+            migration.incompatible,
+            vec![IncompatibleSchemaChange::ColumnMadeNullable {
+                table: "account".to_string(),
+                column: "balance".to_string(),
+            }]
+        );
+        assert_eq!(
+            migration.statements(),
+            &vec![
+                "ALTER TABLE test_namespace_index1__account ADD COLUMN label text;"
+                    .to_string()
+            ]
+        );
+    }
+}
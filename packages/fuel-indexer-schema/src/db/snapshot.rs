@@ -0,0 +1,753 @@
+//! # fuel_indexer_schema::db::snapshot
+//!
+//! Portable archives of a single indexer's data, used to bootstrap the indexer in
+//! another database without re-indexing from its start block.
+//!
+//! A snapshot holds the indexer's entity tables, its graph registry rows, every
+//! version of its assets, its key/value store, and its checkpoint. Restoring it
+//! registers the indexer anew, so that indexing resumes from the snapshot height.
+
+use crate::db::{tables::IndexerSchema, IndexerSchemaDbError, IndexerSchemaDbResult};
+use fuel_indexer_database::{
+    queries,
+    types::{
+        history_table_name, Column, ColumnType, GraphRoot, IndexerAssetType,
+        IndexerCheckpoint, Persistence, RootColumn, SqlNamed, Table, TypeId,
+    },
+    DbType, IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_lib::{
+    graphql::{types::FromBlockCol, GraphQLSchema},
+    manifest::Manifest,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use tracing::info;
+
+/// Version of the snapshot format, bumped whenever archives of the previous
+/// version can no longer be restored.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Number of rows inserted by each statement when restoring a table.
+const RESTORE_BATCH_SIZE: usize = 500;
+
+/// Number of rows fetched by each query when reading a table.
+const READ_BATCH_SIZE: usize = 5000;
+
+/// Root of the indexer's graph in the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotGraphRoot {
+    /// Version of the GraphQL schema.
+    pub version: String,
+
+    /// GraphQL schema of the indexer.
+    pub schema: String,
+
+    /// Columns of the root, as `(column_name, graphql_type)` pairs.
+    pub columns: Vec<(String, String)>,
+}
+
+/// Type ID of the indexer's schema in the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotTypeId {
+    /// Database ID of the type.
+    pub id: i64,
+
+    /// Name of the type in the GraphQL schema.
+    pub graphql_name: String,
+
+    /// Name of the type's table.
+    pub table_name: String,
+}
+
+/// Column of the indexer's schema in the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotColumn {
+    /// Database ID of the `TypeId` associated with this column.
+    pub type_id: i64,
+
+    /// Position of the column.
+    pub position: i32,
+
+    /// Name of the column.
+    pub name: String,
+
+    /// SQL type of the column.
+    pub coltype: String,
+
+    /// Whether this column is nullable.
+    pub nullable: bool,
+
+    /// GraphQL type of the column.
+    pub graphql_type: String,
+
+    /// Whether this column is unique.
+    pub unique: bool,
+
+    /// How this column is persisted to the database.
+    pub persistence: String,
+}
+
+/// Version of one of the indexer's assets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotAsset {
+    /// Type of the asset (i.e., `wasm`, `manifest`, or `schema`).
+    pub asset_type: String,
+
+    /// Version of the asset.
+    pub version: i32,
+
+    /// Hex encoded bytes of the asset.
+    pub bytes: String,
+}
+
+/// Rows of one of the indexer's tables.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotTable {
+    /// Name of the table, without the indexer's namespace.
+    pub name: String,
+
+    /// Names of the columns of the table.
+    pub columns: Vec<String>,
+
+    /// Values of each row, as text. `Object` columns are hex encoded.
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+/// Portable archive of a single indexer's data.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexerSnapshot {
+    /// Version of the snapshot format.
+    pub format_version: u32,
+
+    /// Namespace of the indexer.
+    pub namespace: String,
+
+    /// Identifier of the indexer.
+    pub identifier: String,
+
+    /// Type of the database from which the snapshot was taken.
+    pub db_type: String,
+
+    /// Public key associated with the indexer.
+    pub pubkey: Option<String>,
+
+    /// Root of the indexer's graph.
+    pub graph_root: SnapshotGraphRoot,
+
+    /// Type IDs of the indexer's schema.
+    pub type_ids: Vec<SnapshotTypeId>,
+
+    /// Columns of the indexer's schema.
+    pub columns: Vec<SnapshotColumn>,
+
+    /// Every version of the indexer's assets, oldest first.
+    pub assets: Vec<SnapshotAsset>,
+
+    /// Last block handled by the indexer.
+    pub checkpoint: Option<IndexerCheckpoint>,
+
+    /// Hex encoded entries of the indexer's key/value store.
+    pub kv: Vec<(String, String)>,
+
+    /// Rows of the indexer's entity and history tables.
+    pub tables: Vec<SnapshotTable>,
+}
+
+impl IndexerSnapshot {
+    /// Take a snapshot of an indexer.
+    ///
+    /// Everything is read in a single transaction, so that the tables are consistent
+    /// with the checkpoint.
+    pub async fn take(
+        pool: &IndexerConnectionPool,
+        namespace: &str,
+        identifier: &str,
+    ) -> IndexerSchemaDbResult<Self> {
+        let schema = IndexerSchema::load(pool, namespace, identifier).await?;
+        let mut conn = pool.acquire().await?;
+
+        match conn {
+            IndexerConnection::Postgres(_) => {
+                queries::execute_query(
+                    &mut conn,
+                    "BEGIN ISOLATION LEVEL REPEATABLE READ".to_string(),
+                )
+                .await?;
+            }
+            IndexerConnection::Sqlite(_) => {
+                queries::start_transaction(&mut conn).await?;
+            }
+        }
+
+        match Self::read(&mut conn, &schema, namespace, identifier).await {
+            Ok(snapshot) => {
+                queries::commit_transaction(&mut conn).await?;
+                info!(
+                    "Took snapshot of Indexer({namespace}.{identifier}) at block {:?}.",
+                    snapshot.checkpoint.as_ref().map(|c| c.block_height)
+                );
+                Ok(snapshot)
+            }
+            Err(e) => {
+                queries::revert_transaction(&mut conn).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Read the data of an indexer into a snapshot.
+    async fn read(
+        conn: &mut IndexerConnection,
+        schema: &IndexerSchema,
+        namespace: &str,
+        identifier: &str,
+    ) -> IndexerSchemaDbResult<Self> {
+        let indexer = queries::get_indexer(conn, namespace, identifier)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let root = queries::graph_root_latest(conn, namespace, identifier).await?;
+        let root_columns = queries::root_columns_list_by_id(conn, root.id)
+            .await?
+            .into_iter()
+            .map(|c| (c.column_name, c.graphql_type))
+            .collect();
+
+        let type_ids =
+            queries::type_id_list_by_name(conn, namespace, &root.version, identifier)
+                .await?;
+        let mut columns = Vec::new();
+        for type_id in type_ids.iter() {
+            columns.extend(
+                queries::list_column_by_id(conn, type_id.id)
+                    .await?
+                    .into_iter()
+                    .map(|c| SnapshotColumn {
+                        type_id: c.type_id,
+                        position: c.position,
+                        name: c.name,
+                        coltype: c.coltype.to_string(),
+                        nullable: c.nullable,
+                        graphql_type: c.graphql_type,
+                        unique: c.unique,
+                        persistence: c.persistence.to_string(),
+                    }),
+            );
+        }
+
+        let mut assets = Vec::new();
+        for asset_type in [
+            IndexerAssetType::Wasm,
+            IndexerAssetType::Manifest,
+            IndexerAssetType::Schema,
+        ] {
+            assets.extend(
+                queries::assets_for_indexer(conn, &indexer.id, asset_type.clone())
+                    .await?
+                    .into_iter()
+                    .map(|a| SnapshotAsset {
+                        asset_type: asset_type.as_ref().to_string(),
+                        version: a.version,
+                        bytes: hex::encode(a.bytes),
+                    }),
+            );
+        }
+
+        let checkpoint =
+            queries::last_checkpoint_for_indexer(conn, namespace, identifier).await?;
+
+        let kv = queries::kv_entries(conn, namespace, identifier)
+            .await?
+            .into_iter()
+            .map(|(k, v)| (hex::encode(k), hex::encode(v)))
+            .collect();
+
+        let mut tables = Vec::new();
+        for table in schema.tables() {
+            if table.persistence() != Persistence::Scalar {
+                continue;
+            }
+
+            tables.push(
+                read_table(
+                    conn,
+                    schema.db_type(),
+                    &table.sql_name(),
+                    &table.qualified_name(),
+                    table.columns(),
+                )
+                .await?,
+            );
+
+            if table.is_historical() {
+                tables.push(
+                    read_table(
+                        conn,
+                        schema.db_type(),
+                        &history_name(table),
+                        &table.history_qualified_name(),
+                        &table.history_columns(),
+                    )
+                    .await?,
+                );
+            }
+        }
+
+        Ok(Self {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            namespace: namespace.to_string(),
+            identifier: identifier.to_string(),
+            db_type: db_type_name(schema.db_type()).to_string(),
+            pubkey: indexer.pubkey,
+            graph_root: SnapshotGraphRoot {
+                version: root.version,
+                schema: root.schema,
+                columns: root_columns,
+            },
+            type_ids: type_ids
+                .into_iter()
+                .map(|t| SnapshotTypeId {
+                    id: t.id,
+                    graphql_name: t.graphql_name,
+                    table_name: t.table_name,
+                })
+                .collect(),
+            columns,
+            assets,
+            checkpoint,
+            kv,
+            tables,
+        })
+    }
+
+    /// Restore the snapshot into a database in which the indexer doesn't exist yet.
+    ///
+    /// The latest manifest is made resumable, so that the indexer resumes from the
+    /// snapshot height once it's started.
+    pub async fn restore(
+        &self,
+        pool: &IndexerConnectionPool,
+    ) -> IndexerSchemaDbResult<()> {
+        if self.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(IndexerSchemaDbError::IncompatibleSnapshot(format!(
+                "Snapshot format version {} is unsupported (expected {SNAPSHOT_FORMAT_VERSION}).",
+                self.format_version
+            )));
+        }
+
+        let db_type = pool.database_type();
+        if self.db_type != db_type_name(&db_type) {
+            return Err(IndexerSchemaDbError::IncompatibleSnapshot(format!(
+                "Snapshot of a {} database can't be restored into a {} database.",
+                self.db_type,
+                db_type_name(&db_type)
+            )));
+        }
+
+        let mut conn = pool.acquire().await?;
+        if queries::get_indexer(&mut conn, &self.namespace, &self.identifier)
+            .await?
+            .is_some()
+        {
+            return Err(IndexerSchemaDbError::IndexerExists(format!(
+                "{}.{}",
+                self.namespace, self.identifier
+            )));
+        }
+
+        queries::start_transaction(&mut conn).await?;
+
+        match self.write(&mut conn, db_type).await {
+            Ok(()) => {
+                queries::commit_transaction(&mut conn).await?;
+                info!(
+                    "Restored Indexer({}.{}) from snapshot at block {:?}.",
+                    self.namespace,
+                    self.identifier,
+                    self.checkpoint.as_ref().map(|c| c.block_height)
+                );
+                Ok(())
+            }
+            Err(e) => {
+                queries::revert_transaction(&mut conn).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Write the data of the snapshot to the database.
+    async fn write(
+        &self,
+        conn: &mut IndexerConnection,
+        db_type: DbType,
+    ) -> IndexerSchemaDbResult<()> {
+        let namespace = self.namespace.as_str();
+        let identifier = self.identifier.as_str();
+
+        queries::register_indexer(conn, namespace, identifier, self.pubkey.as_deref())
+            .await?;
+
+        let latest_manifest = self
+            .assets
+            .iter()
+            .filter(|a| a.asset_type == IndexerAssetType::Manifest.as_ref())
+            .map(|a| a.version)
+            .max();
+
+        let mut manifest = None;
+        for asset in self.assets.iter() {
+            let asset_type =
+                IndexerAssetType::from_str(&asset.asset_type).map_err(|_| {
+                    IndexerSchemaDbError::IncompatibleSnapshot(format!(
+                        "Unknown asset type: {}.",
+                        asset.asset_type
+                    ))
+                })?;
+
+            let mut bytes = hex::decode(&asset.bytes)?;
+            if asset_type == IndexerAssetType::Manifest
+                && Some(asset.version) == latest_manifest
+            {
+                let mut latest = Manifest::try_from(&bytes)?;
+                latest.resumable = Some(true);
+                bytes = latest.clone().try_into()?;
+                manifest = Some(latest);
+            }
+
+            queries::register_indexer_asset(
+                conn,
+                namespace,
+                identifier,
+                bytes,
+                asset_type,
+                self.pubkey.as_deref(),
+            )
+            .await?;
+        }
+
+        let manifest = manifest.ok_or_else(|| {
+            IndexerSchemaDbError::IncompatibleSnapshot(
+                "Snapshot has no manifest.".to_string(),
+            )
+        })?;
+
+        queries::new_graph_root(
+            conn,
+            GraphRoot {
+                version: self.graph_root.version.clone(),
+                schema_name: namespace.to_string(),
+                schema_identifier: identifier.to_string(),
+                schema: self.graph_root.schema.clone(),
+                ..GraphRoot::default()
+            },
+        )
+        .await?;
+
+        if !self.graph_root.columns.is_empty() {
+            let root = queries::graph_root_latest(conn, namespace, identifier).await?;
+            queries::new_root_columns(
+                conn,
+                self.graph_root
+                    .columns
+                    .iter()
+                    .map(|(column_name, graphql_type)| RootColumn {
+                        root_id: root.id,
+                        column_name: column_name.clone(),
+                        graphql_type: graphql_type.clone(),
+                        ..RootColumn::default()
+                    })
+                    .collect(),
+            )
+            .await?;
+        }
+
+        queries::type_id_insert(
+            conn,
+            self.type_ids
+                .iter()
+                .map(|t| TypeId {
+                    id: t.id,
+                    version: self.graph_root.version.clone(),
+                    namespace: namespace.to_string(),
+                    identifier: identifier.to_string(),
+                    graphql_name: t.graphql_name.clone(),
+                    table_name: t.table_name.clone(),
+                })
+                .collect(),
+        )
+        .await?;
+
+        let mut columns = Vec::new();
+        for c in self.columns.iter() {
+            columns.push(Column {
+                type_id: c.type_id,
+                position: c.position,
+                name: c.name.clone(),
+                coltype: ColumnType::from(c.coltype.as_str()),
+                nullable: c.nullable,
+                graphql_type: c.graphql_type.clone(),
+                unique: c.unique,
+                persistence: Persistence::from_str(&c.persistence).map_err(|_| {
+                    IndexerSchemaDbError::IncompatibleSnapshot(format!(
+                        "Unknown column persistence: {}.",
+                        c.persistence
+                    ))
+                })?,
+                db_type: db_type.clone(),
+                ..Column::default()
+            });
+        }
+        queries::new_column_insert(conn, columns).await?;
+
+        let schema = IndexerSchema::new(
+            namespace,
+            identifier,
+            &GraphQLSchema::new(self.graph_root.schema.clone()),
+            db_type.clone(),
+            manifest.execution_source(),
+        )?;
+        schema.create_tables(conn).await?;
+
+        for table in schema.tables() {
+            if table.persistence() != Persistence::Scalar {
+                continue;
+            }
+
+            self.write_table(
+                conn,
+                &db_type,
+                &table.sql_name(),
+                &table.qualified_name(),
+                table.columns(),
+            )
+            .await?;
+
+            if table.is_historical() {
+                self.write_table(
+                    conn,
+                    &db_type,
+                    &history_name(table),
+                    &table.history_qualified_name(),
+                    &table.history_columns(),
+                )
+                .await?;
+            }
+        }
+
+        for (key, value) in self.kv.iter() {
+            queries::put_kv(
+                conn,
+                namespace,
+                identifier,
+                &hex::decode(key)?,
+                &hex::decode(value)?,
+            )
+            .await?;
+        }
+
+        if let Some(checkpoint) = &self.checkpoint {
+            queries::put_checkpoint(conn, namespace, identifier, checkpoint).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Insert the rows of one of the snapshot's tables.
+    async fn write_table(
+        &self,
+        conn: &mut IndexerConnection,
+        db_type: &DbType,
+        name: &str,
+        qualified_name: &str,
+        columns: &[Column],
+    ) -> IndexerSchemaDbResult<()> {
+        let Some(table) = self.tables.iter().find(|t| t.name == name) else {
+            return Ok(());
+        };
+
+        // The archive lists its own columns, which have to be the ones of the schema.
+        let objects = table
+            .columns
+            .iter()
+            .map(|name| {
+                columns
+                    .iter()
+                    .find(|c| c.name == *name)
+                    .map(|c| c.coltype == ColumnType::Object)
+                    .ok_or_else(|| {
+                        IndexerSchemaDbError::IncompatibleSnapshot(format!(
+                            "Column {name} of table {} isn't part of the schema.",
+                            table.name
+                        ))
+                    })
+            })
+            .collect::<IndexerSchemaDbResult<Vec<bool>>>()?;
+
+        for rows in table.rows.chunks(RESTORE_BATCH_SIZE) {
+            let values = rows
+                .iter()
+                .map(|row| {
+                    let literals = row
+                        .iter()
+                        .zip(objects.iter())
+                        .map(|(value, object)| {
+                            sql_literal(value.as_deref(), *object, db_type)
+                        })
+                        .collect::<IndexerSchemaDbResult<Vec<String>>>()?;
+                    Ok(format!("({})", literals.join(", ")))
+                })
+                .collect::<IndexerSchemaDbResult<Vec<String>>>()?;
+
+            let query = format!(
+                "INSERT INTO {qualified_name} ({}) VALUES {}",
+                table.columns.join(", "),
+                values.join(", ")
+            );
+            queries::execute_query(conn, query).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Read every row of one of an indexer's tables, one page at a time.
+async fn read_table(
+    conn: &mut IndexerConnection,
+    db_type: &DbType,
+    name: &str,
+    qualified_name: &str,
+    columns: &[Column],
+) -> IndexerSchemaDbResult<SnapshotTable> {
+    let values = columns
+        .iter()
+        .map(|c| match (db_type, c.coltype) {
+            (DbType::Postgres, ColumnType::Object) => {
+                format!("encode({}, 'hex')", c.name)
+            }
+            (DbType::Postgres, _) => format!("{}::text", c.name),
+            // SQLite's `hex` returns an empty string, rather than `NULL`, for `NULL`.
+            (DbType::Sqlite, ColumnType::Object) => {
+                format!("CASE WHEN {0} IS NULL THEN NULL ELSE hex({0}) END", c.name)
+            }
+            (DbType::Sqlite, _) => format!("CAST({} AS TEXT)", c.name),
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let array = match db_type {
+        DbType::Postgres => "json_build_array",
+        DbType::Sqlite => "json_array",
+    };
+
+    // Versions of an entity share its `id` in history tables, so pages are ordered by
+    // every column that makes a row unique, in order for them not to overlap.
+    let order = if columns
+        .iter()
+        .any(|c| c.name == FromBlockCol::to_lowercase_str())
+    {
+        format!("id, {}", FromBlockCol::to_lowercase_str())
+    } else {
+        "id".to_string()
+    };
+
+    let mut rows = Vec::new();
+    loop {
+        let result = queries::run_query(
+            conn,
+            format!(
+                "SELECT {array}({values}) FROM {qualified_name} ORDER BY {order} LIMIT {READ_BATCH_SIZE} OFFSET {}",
+                rows.len()
+            ),
+        )
+        .await?;
+
+        let page = result
+            .as_array()
+            .map(|page| {
+                page.iter()
+                    .map(|row| {
+                        row.as_array()
+                            .map(|values| {
+                                values
+                                    .iter()
+                                    .map(|v| v.as_str().map(|s| s.to_string()))
+                                    .collect()
+                            })
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<Vec<Option<String>>>>()
+            })
+            .unwrap_or_default();
+
+        let done = page.len() < READ_BATCH_SIZE;
+        rows.extend(page);
+        if done {
+            break;
+        }
+    }
+
+    Ok(SnapshotTable {
+        name: name.to_string(),
+        columns: columns.iter().map(|c| c.name.clone()).collect(),
+        rows,
+    })
+}
+
+/// Return the name of a table's history table, without the indexer's namespace.
+fn history_name(table: &Table) -> String {
+    history_table_name(&table.sql_name())
+}
+
+/// Return the name under which a type of database is recorded in snapshots.
+fn db_type_name(db_type: &DbType) -> &'static str {
+    match db_type {
+        DbType::Postgres => "postgres",
+        DbType::Sqlite => "sqlite",
+    }
+}
+
+/// Return the SQL literal of a value of a snapshot's table.
+fn sql_literal(
+    value: Option<&str>,
+    object: bool,
+    db_type: &DbType,
+) -> IndexerSchemaDbResult<String> {
+    let Some(value) = value else {
+        return Ok("NULL".to_string());
+    };
+
+    if !object {
+        return Ok(format!("'{}'", value.replace('\'', "''")));
+    }
+
+    // Re-encoding the bytes ensures that only hex digits end up in the statement.
+    let hex = hex::encode(hex::decode(value)?);
+    Ok(match db_type {
+        DbType::Postgres => format!("decode('{hex}', 'hex')"),
+        DbType::Sqlite => format!("X'{hex}'"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_literals_of_snapshot_values() {
+        assert_eq!(sql_literal(None, false, &DbType::Postgres).unwrap(), "NULL");
+        assert_eq!(
+            sql_literal(Some("it's"), false, &DbType::Sqlite).unwrap(),
+            "'it''s'"
+        );
+        assert_eq!(
+            sql_literal(Some("00FF"), true, &DbType::Postgres).unwrap(),
+            "decode('00ff', 'hex')"
+        );
+        assert_eq!(
+            sql_literal(Some("00ff"), true, &DbType::Sqlite).unwrap(),
+            "X'00ff'"
+        );
+        assert!(sql_literal(Some("'); DROP"), true, &DbType::Sqlite).is_err());
+    }
+}
@@ -5,7 +5,7 @@
 //!
 //! Also used to load tables from the database when web requests are made.
 
-use crate::db::IndexerSchemaDbResult;
+use crate::db::{migration::SchemaMigration, IndexerSchemaDbResult};
 use fuel_indexer_database::{
     queries, types::*, DbType, IndexerConnection, IndexerConnectionPool,
 };
@@ -46,18 +46,17 @@ impl IndexerSchema {
         db_type: DbType,
         exec_source: ExecutionSource,
     ) -> IndexerSchemaDbResult<Self> {
+        let parsed =
+            ParsedGraphQLSchema::new(namespace, identifier, exec_source, Some(schema))?;
+        let tables = Self::build_tables(&parsed, &db_type);
+
         Ok(IndexerSchema {
             db_type,
             namespace: namespace.to_string(),
             identifier: identifier.to_string(),
             schema: schema.to_owned(),
-            parsed: ParsedGraphQLSchema::new(
-                namespace,
-                identifier,
-                exec_source,
-                Some(schema),
-            )?,
-            tables: Vec::new(),
+            parsed,
+            tables,
         })
     }
 
@@ -66,31 +65,44 @@ impl IndexerSchema {
         &self.parsed
     }
 
-    /// Generate table SQL for each indexable object in the given GraphQL schema.
-    ///
-    /// Ideally all of these queries should return the objects that they persist to the
-    /// DB (e.g., `INSERT .. RETURNING *`).
-    ///
-    /// TODO: We should also be caching as much of this `IndexerSchema` as possible
-    pub async fn commit(
-        mut self,
-        schema: &GraphQLSchema,
-        exec_source: ExecutionSource,
-        conn: &mut IndexerConnection,
-    ) -> IndexerSchemaDbResult<Self> {
-        let parsed_schema = ParsedGraphQLSchema::new(
-            &self.namespace,
-            &self.identifier,
-            exec_source,
-            Some(schema),
-        )?;
+    /// Return the type of database the schema is persisted to.
+    pub fn db_type(&self) -> &DbType {
+        &self.db_type
+    }
 
-        let mut statements = Vec::new();
-        self.schema = schema.to_owned();
-        self.parsed = parsed_schema;
+    /// Return the version of the GraphQL schema.
+    pub fn version(&self) -> &str {
+        self.schema.version()
+    }
+
+    /// Return the tables generated from the GraphQL schema.
+    pub fn tables(&self) -> &Vec<Table> {
+        &self.tables
+    }
+
+    /// Build the tables, including join tables, for each indexable object in the
+    /// given GraphQL schema.
+    fn build_tables(parsed: &ParsedGraphQLSchema, db_type: &DbType) -> Vec<Table> {
+        let mut tables = parsed
+            .non_enum_typdefs()
+            .iter()
+            .map(|(_, t)| Table::from_typedef(t, parsed, db_type))
+            .collect::<Vec<Table>>();
+
+        let mut join_tables = parsed
+            .join_table_meta()
+            .iter()
+            .map(|(_, item)| Table::from_join_meta(item.to_owned(), parsed, db_type))
+            .collect::<Vec<Table>>();
 
+        tables.append(&mut join_tables);
+        tables
+    }
+
+    /// Persist the graph root, type IDs, and columns of the schema to the registry.
+    async fn register(&self, conn: &mut IndexerConnection) -> IndexerSchemaDbResult<()> {
         let root = GraphRoot {
-            version: schema.version().to_owned(),
+            version: self.schema.version().to_owned(),
             schema_name: self.namespace.to_owned(),
             schema_identifier: self.identifier.to_owned(),
             schema: self.schema.to_string(),
@@ -99,17 +111,6 @@ impl IndexerSchema {
 
         queries::new_graph_root(conn, root).await?;
 
-        // TODO: Abstract this into a SQLSchema (or named something else)?
-        match self.db_type {
-            DbType::Postgres => {
-                let create = format!(
-                    "CREATE SCHEMA IF NOT EXISTS {};",
-                    self.parsed.fully_qualified_namespace(),
-                );
-                statements.push(create);
-            }
-        }
-
         let mut type_ids = self
             .parsed
             .type_defs()
@@ -133,23 +134,8 @@ impl IndexerSchema {
 
         queries::type_id_insert(conn, type_ids).await?;
 
-        let mut tables = self
-            .parsed
-            .non_enum_typdefs()
-            .iter()
-            .map(|(_, t)| Table::from_typedef(t, &self.parsed))
-            .collect::<Vec<Table>>();
-
-        let mut join_tables = self
-            .parsed
-            .join_table_meta()
-            .iter()
-            .map(|(_, item)| Table::from_join_meta(item.to_owned(), &self.parsed))
-            .collect::<Vec<Table>>();
-
-        tables.append(&mut join_tables);
-
-        let columns = tables
+        let columns = self
+            .tables
             .iter()
             .flat_map(|t| t.columns())
             .map(|c| c.to_owned())
@@ -157,7 +143,60 @@ impl IndexerSchema {
 
         queries::new_column_insert(conn, columns).await?;
 
-        let table_stmnts = tables
+        Ok(())
+    }
+
+    /// Generate table SQL for each indexable object in the given GraphQL schema.
+    ///
+    /// Ideally all of these queries should return the objects that they persist to the
+    /// DB (e.g., `INSERT .. RETURNING *`).
+    ///
+    /// TODO: We should also be caching as much of this `IndexerSchema` as possible
+    pub async fn commit(
+        mut self,
+        schema: &GraphQLSchema,
+        exec_source: ExecutionSource,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<Self> {
+        let parsed_schema = ParsedGraphQLSchema::new(
+            &self.namespace,
+            &self.identifier,
+            exec_source,
+            Some(schema),
+        )?;
+
+        self.schema = schema.to_owned();
+        self.parsed = parsed_schema;
+        self.tables = Self::build_tables(&self.parsed, &self.db_type);
+
+        self.register(conn).await?;
+        self.create_tables(conn).await?;
+
+        Ok(self)
+    }
+
+    /// Create the tables of the schema, along with the indexer's key/value store.
+    pub async fn create_tables(
+        &self,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<()> {
+        let mut statements = Vec::new();
+
+        // TODO: Abstract this into a SQLSchema (or named something else)?
+        match self.db_type {
+            DbType::Postgres => {
+                let create = format!(
+                    "CREATE SCHEMA IF NOT EXISTS {};",
+                    self.parsed.fully_qualified_namespace(),
+                );
+                statements.push(create);
+            }
+            // SQLite has no schemas. Tables are prefixed with the namespace instead.
+            DbType::Sqlite => {}
+        }
+
+        let table_stmnts = self
+            .tables
             .iter()
             .filter_map(|t| {
                 let stmnt = t.create();
@@ -169,10 +208,20 @@ impl IndexerSchema {
             .collect::<Vec<String>>();
         statements.extend(table_stmnts);
 
-        let constraint_stmnts = tables
+        let history_stmnts = self
+            .tables
+            .iter()
+            .map(|t| t.create_history())
+            .filter(|stmnt| !stmnt.is_empty())
+            .collect::<Vec<String>>();
+        statements.extend(history_stmnts);
+
+        let constraint_stmnts = self
+            .tables
             .iter()
             .flat_map(|t| t.constraints())
             .map(|c| c.create())
+            .filter(|stmnt| !stmnt.is_empty())
             .collect::<Vec<String>>();
 
         statements.extend(constraint_stmnts);
@@ -181,7 +230,28 @@ impl IndexerSchema {
             queries::execute_query(conn, stmnt.to_owned()).await?;
         }
 
-        self.tables = tables;
+        // Every indexer gets a key/value store alongside its entity tables. It isn't
+        // part of the GraphQL schema, so it's never exposed through the API.
+        queries::create_kv_store(conn, &self.namespace, &self.identifier).await?;
+
+        Ok(())
+    }
+
+    /// Apply a migration from the previous version of the schema to this one.
+    ///
+    /// The schema's registry entries are replaced with those of this version, and the
+    /// migration's statements are executed against the existing tables.
+    pub async fn apply_migration(
+        self,
+        migration: &SchemaMigration,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<Self> {
+        queries::type_id_delete(conn, &self.namespace, &self.identifier).await?;
+        self.register(conn).await?;
+
+        for stmnt in migration.statements() {
+            queries::execute_query(conn, stmnt.to_owned()).await?;
+        }
 
         Ok(self)
     }
@@ -213,11 +283,8 @@ impl IndexerSchema {
             Some(&schema),
         )?;
 
-        let tables = parsed
-            .non_enum_typdefs()
-            .iter()
-            .map(|(_, t)| Table::from_typedef(t, &parsed))
-            .collect::<Vec<Table>>();
+        let db_type = pool.database_type();
+        let tables = Self::build_tables(&parsed, &db_type);
 
         Ok(IndexerSchema {
             namespace: root.schema_name,
@@ -225,7 +292,7 @@ impl IndexerSchema {
             schema,
             tables,
             parsed,
-            db_type: DbType::Postgres,
+            db_type,
         })
     }
 }
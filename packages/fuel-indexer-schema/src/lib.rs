@@ -46,6 +46,9 @@ pub enum IndexerSchemaError {
 /// fetched from the database.
 ///
 /// Each `FtColumn` corresponds to a Fuel-specific GraphQL scalar type.
+///
+/// `FtColumn`s are passed between indexer modules and the indexer service, so changing
+/// the order of these variants, or their encoding, requires bumping `FFI_VERSION`.
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Hash)]
 pub enum FtColumn {
     Address(Option<Address>),
@@ -277,6 +280,55 @@ impl FtColumn {
             },
         }
     }
+
+    /// Return the value of an integer column as an `i64`, if it has one that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            FtColumn::Int1(Some(val)) => Some(i64::from(*val)),
+            FtColumn::UInt1(Some(val)) => Some(i64::from(*val)),
+            FtColumn::Int4(Some(val)) => Some(i64::from(*val)),
+            FtColumn::UInt4(Some(val)) => Some(i64::from(*val)),
+            FtColumn::BlockHeight(Some(val)) => Some(i64::from(u32::from(*val))),
+            FtColumn::Int8(Some(val)) | FtColumn::Timestamp(Some(val)) => Some(*val),
+            FtColumn::UInt8(Some(val)) | FtColumn::ID(Some(val)) => {
+                i64::try_from(*val).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Return query fragments for `INSERT` statements against a SQLite database.
+    ///
+    /// SQLite integers are signed 64-bit values, so integers that may not fit are written
+    /// as text. SQLite has no array types either, so arrays are written as JSON.
+    pub fn sqlite_query_fragment(&self) -> String {
+        match self {
+            FtColumn::ID(Some(val)) | FtColumn::UInt8(Some(val)) => format!("'{val}'"),
+            FtColumn::Int16(Some(val)) => format!("'{val}'"),
+            FtColumn::UInt16(Some(val)) => format!("'{val}'"),
+            FtColumn::Array(Some(arr)) if !arr.is_empty() => {
+                assert!(
+                    arr.len() < MAX_ARRAY_LENGTH,
+                    "Array length exceeds maximum allowed length."
+                );
+
+                let result = arr
+                    .iter()
+                    .map(|e| match e {
+                        // Without `json()`, JSON elements would be stored as strings.
+                        FtColumn::Json(Some(_)) | FtColumn::Virtual(Some(_)) => {
+                            format!("json({})", e.sqlite_query_fragment())
+                        }
+                        _ => e.sqlite_query_fragment(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                format!("json_array({result})")
+            }
+            _ => self.query_fragment(),
+        }
+    }
 }
 
 mod tests {
@@ -379,6 +431,46 @@ mod tests {
         insta::assert_yaml_snapshot!(identity_none.query_fragment());
     }
 
+    #[test]
+    fn test_sqlite_fragments() {
+        use super::*;
+
+        let id = FtColumn::ID(Some(u64::MAX));
+        let uint16 = FtColumn::UInt16(Some(123456));
+        let int4 = FtColumn::Int4(Some(-42));
+        let array = FtColumn::Array(Some(vec![
+            FtColumn::UInt8(Some(1)),
+            FtColumn::UInt8(Some(2)),
+        ]));
+        let json_array = FtColumn::Array(Some(vec![FtColumn::Json(Some(Json(
+            r#"{"hello":"world"}"#.to_string(),
+        )))]));
+
+        assert_eq!(id.sqlite_query_fragment(), "'18446744073709551615'");
+        assert_eq!(uint16.sqlite_query_fragment(), "'123456'");
+        assert_eq!(int4.sqlite_query_fragment(), "-42");
+        assert_eq!(array.sqlite_query_fragment(), "json_array('1','2')");
+        assert_eq!(
+            json_array.sqlite_query_fragment(),
+            r#"json_array(json('{"hello":"world"}'))"#
+        );
+        assert_eq!(FtColumn::Array(None).sqlite_query_fragment(), "NULL");
+    }
+
+    #[test]
+    fn test_integer_columns_as_i64() {
+        use super::*;
+
+        assert_eq!(FtColumn::Int4(Some(-42)).as_i64(), Some(-42));
+        assert_eq!(
+            FtColumn::Timestamp(Some(1_700_000_000)).as_i64(),
+            Some(1_700_000_000)
+        );
+        assert_eq!(FtColumn::UInt8(Some(u64::MAX)).as_i64(), None);
+        assert_eq!(FtColumn::UInt8(None).as_i64(), None);
+        assert_eq!(FtColumn::Charfield(Some("1".to_string())).as_i64(), None);
+    }
+
     #[test]
     #[should_panic(expected = "Schema fields of type `ID` cannot be nullable.")]
     fn test_panic_on_none_id_fragment() {
@@ -202,6 +202,46 @@ mod fuel_indexer_test {
         entity.save();
     }
 
+    fn fuel_indexer_test_returndata(returndata: ReturnData) {
+        info!("fuel_indexer_test_returndata handling ReturnData event.");
+
+        let ReturnData {
+            contract_id,
+            len,
+            digest,
+            ..
+        } = returndata;
+
+        let entity = ReturnDataEntity {
+            id: id8(contract_id),
+            contract_id,
+            len,
+            digest,
+        };
+
+        entity.save();
+    }
+
+    fn fuel_indexer_test_input_contract(input: InputContract) {
+        info!("fuel_indexer_test_input_contract handling InputContract event.");
+
+        let InputContract {
+            contract_id,
+            balance_root,
+            state_root,
+            ..
+        } = input;
+
+        let entity = InputContractEntity {
+            id: id8(state_root),
+            contract_id,
+            balance_root,
+            state_root,
+        };
+
+        entity.save();
+    }
+
     fn fuel_indexer_test_multiargs(
         pung: Pung,
         pong: Pong,
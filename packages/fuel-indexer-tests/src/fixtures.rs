@@ -5,7 +5,8 @@ use fuel_indexer_database::IndexerConnectionPool;
 use fuel_indexer_lib::{
     config::{
         auth::AuthenticationStrategy, defaults as config_defaults, AuthenticationConfig,
-        DatabaseConfig, FuelClientConfig, GraphQLConfig, IndexerConfig, RateLimitConfig,
+        BlockStoreConfig, CommitPolicy, DatabaseConfig, FuelClientConfig, GraphQLConfig,
+        IndexerConfig, RateLimitConfig,
     },
     defaults::SERVICE_REQUEST_CHANNEL_SIZE,
     utils::{derive_socket_addr, ServiceRequest},
@@ -66,7 +67,10 @@ impl TestPostgresDb {
             host,
             port,
             ..
-        } = connection_config;
+        } = connection_config
+        else {
+            unimplemented!("Only Postgres is supported by the test database.");
+        };
         let test_db_config = DatabaseConfig::Postgres {
             user,
             password,
@@ -94,6 +98,9 @@ impl TestPostgresDb {
                         fuel_indexer_postgres::run_migration(&mut conn).await?;
                         p
                     }
+                    IndexerConnectionPool::Sqlite(_) => {
+                        unimplemented!("Only Postgres is supported by the test database.")
+                    }
                 },
                 Err(e) => return Err(TestError::PoolCreationError(e)),
             };
@@ -297,6 +304,10 @@ pub async fn api_server_app_postgres(
         authentication: AuthenticationConfig::default(),
         rate_limit: RateLimitConfig::default(),
         replace_indexer: config_defaults::REPLACE_INDEXER,
+        commit_policy: CommitPolicy::default(),
+        block_store: BlockStoreConfig::default(),
+        backfill_partitions: config_defaults::BACKFILL_PARTITIONS,
+        prune_interval_secs: config_defaults::PRUNE_INTERVAL_SECS,
     };
 
     let pool = IndexerConnectionPool::connect(&config.database.to_string())
@@ -338,6 +349,10 @@ pub async fn authenticated_api_server_app_postgres(database_url: Option<&str>) -
         },
         rate_limit: RateLimitConfig::default(),
         replace_indexer: config_defaults::REPLACE_INDEXER,
+        commit_policy: CommitPolicy::default(),
+        block_store: BlockStoreConfig::default(),
+        backfill_partitions: config_defaults::BACKFILL_PARTITIONS,
+        prune_interval_secs: config_defaults::PRUNE_INTERVAL_SECS,
     };
 
     let (tx, _rx) = channel::<ServiceRequest>(SERVICE_REQUEST_CHANNEL_SIZE);
@@ -373,6 +388,10 @@ pub async fn indexer_service_postgres(
         authentication: AuthenticationConfig::default(),
         rate_limit: RateLimitConfig::default(),
         replace_indexer: config_defaults::REPLACE_INDEXER,
+        commit_policy: CommitPolicy::default(),
+        block_store: BlockStoreConfig::default(),
+        backfill_partitions: config_defaults::BACKFILL_PARTITIONS,
+        prune_interval_secs: config_defaults::PRUNE_INTERVAL_SECS,
     };
 
     modify_config.map(|f| f(&mut config));
@@ -868,3 +868,61 @@ async fn test_can_return_query_response_with_alias_and_ascending_offset_and_limi
     );
     assert_eq!(data[0]["page_info"]["pages"].as_i64(), Some(3));
 }
+
+#[actix_web::test]
+#[cfg(all(feature = "e2e", feature = "postgres"))]
+async fn test_can_export_filtered_entities_as_json_lines_and_csv_postgres() {
+    let (fuel_node_handle, _test_db, mut srvc, api_app) = setup_test_components().await;
+
+    let server = axum::Server::bind(&GraphQLConfig::default().into())
+        .serve(api_app.into_make_service());
+
+    let server_handle = tokio::spawn(server);
+    let mut manifest: Manifest =
+        serde_yaml::from_str(assets::FUEL_INDEXER_TEST_MANIFEST).expect("Bad yaml file.");
+
+    update_test_manifest_asset_paths(&mut manifest);
+
+    srvc.register_indexer_from_manifest(manifest)
+        .await
+        .expect("Failed to initialize indexer.");
+
+    let contract = connect_to_deployed_contract().await.unwrap();
+    let app = test::init_service(app(contract)).await;
+    let req = test::TestRequest::post().uri("/ping").to_request();
+    let _ = app.call(req).await;
+
+    sleep(Duration::from_secs(defaults::INDEXED_EVENT_WAIT)).await;
+    fuel_node_handle.abort();
+
+    let client = http_client();
+    let export = |format: &'static str| {
+        client
+            .get("http://127.0.0.1:29987/api/index/fuel_indexer_test/index1/export")
+            .query(&[
+                ("entity", "FilterEntity"),
+                ("format", format),
+                ("filter", r#"{ "foola": { "in": ["beep", "boop"] } }"#),
+            ])
+            .send()
+    };
+
+    let jsonl = export("jsonl").await.unwrap().text().await.unwrap();
+    let csv = export("csv").await.unwrap().text().await.unwrap();
+
+    server_handle.abort();
+
+    let records = jsonl
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<Value>>();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["id"].as_u64(), Some(1));
+    assert_eq!(records[0]["foola"].as_str(), Some("beep"));
+    assert_eq!(records[1]["foola"].as_str(), Some("boop"));
+
+    let lines = csv.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].split(',').any(|column| column == "foola"));
+    assert!(lines[1].split(',').any(|value| value == "beep"));
+}
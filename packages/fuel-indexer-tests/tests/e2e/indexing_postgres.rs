@@ -137,6 +137,61 @@ async fn test_can_trigger_and_index_callreturn_postgres() {
     assert_eq!(Identity::Address(from_buff), Identity::Address(addr_buff));
 }
 
+#[actix_web::test]
+async fn test_can_trigger_and_index_returndata_event_postgres() {
+    let (node_handle, test_db, mut srvc) = setup_test_components().await;
+
+    let mut manifest = Manifest::try_from(assets::FUEL_INDEXER_TEST_MANIFEST).unwrap();
+    update_test_manifest_asset_paths(&mut manifest);
+
+    srvc.register_indexer_from_manifest(manifest).await.unwrap();
+
+    let contract = connect_to_deployed_contract().await.unwrap();
+    let app = test::init_service(app(contract)).await;
+    let req = test::TestRequest::post().uri("/callreturn").to_request();
+    let _ = app.call(req).await;
+
+    sleep(Duration::from_secs(defaults::INDEXED_EVENT_WAIT)).await;
+    node_handle.abort();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let row =
+        sqlx::query("SELECT * FROM fuel_indexer_test_index1.returndataentity LIMIT 1")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+
+    assert_eq!(row.get::<&str, usize>(1), EXPECTED_CONTRACT_ID);
+    assert!(row.get::<BigDecimal, usize>(2).to_u64().unwrap() > 0);
+}
+
+#[actix_web::test]
+async fn test_can_trigger_and_index_input_contract_postgres() {
+    let (node_handle, test_db, mut srvc) = setup_test_components().await;
+
+    let mut manifest = Manifest::try_from(assets::FUEL_INDEXER_TEST_MANIFEST).unwrap();
+    update_test_manifest_asset_paths(&mut manifest);
+
+    srvc.register_indexer_from_manifest(manifest).await.unwrap();
+
+    let contract = connect_to_deployed_contract().await.unwrap();
+    let app = test::init_service(app(contract)).await;
+    let req = test::TestRequest::post().uri("/ping").to_request();
+    let _ = app.call(req).await;
+
+    sleep(Duration::from_secs(defaults::INDEXED_EVENT_WAIT)).await;
+    node_handle.abort();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let row =
+        sqlx::query("SELECT * FROM fuel_indexer_test_index1.inputcontractentity LIMIT 1")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+
+    assert_eq!(row.get::<&str, usize>(1), EXPECTED_CONTRACT_ID);
+}
+
 #[actix_web::test]
 async fn test_can_trigger_and_index_blocks_and_transactions_postgres() {
     let (node_handle, test_db, mut srvc) = setup_test_components().await;
@@ -1194,3 +1249,41 @@ async fn test_can_trigger_and_index_list_types() {
         expected_virtual_optional_inner
     );
 }
+
+#[actix_web::test]
+async fn test_executor_records_checkpoint_postgres() {
+    let (node_handle, test_db, mut srvc) = setup_test_components().await;
+
+    let mut manifest = Manifest::try_from(assets::FUEL_INDEXER_TEST_MANIFEST).unwrap();
+    update_test_manifest_asset_paths(&mut manifest);
+
+    srvc.register_indexer_from_manifest(manifest).await.unwrap();
+
+    let contract = connect_to_deployed_contract().await.unwrap();
+    let app = test::init_service(app(contract)).await;
+    let req = test::TestRequest::post().uri("/ping").to_request();
+    let _ = app.call(req).await;
+
+    sleep(Duration::from_secs(defaults::INDEXED_EVENT_WAIT)).await;
+    node_handle.abort();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let checkpoint = sqlx::query(
+        "SELECT block_height FROM index_checkpoint c
+        JOIN index_registry r ON c.index_id = r.id
+        WHERE r.namespace = 'fuel_indexer_test' AND r.identifier = 'index1'",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+    let latest = sqlx::query(
+        "SELECT MAX(height) AS height FROM fuel_indexer_test_index1.blockentity",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+
+    let height = checkpoint.get::<i64, usize>(0);
+    assert!(height > 0);
+    assert!(height as u64 >= latest.get::<BigDecimal, usize>(0).to_u64().unwrap());
+}
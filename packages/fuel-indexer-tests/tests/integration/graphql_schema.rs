@@ -92,6 +92,8 @@ fn test_query_builder_parses_correctly() {
                     filter_type: FilterType::IdSelection(ParsedValue::Number(1234)),
                 }],
                 sorts: vec![],
+                ranks: vec![],
+                group_by: vec![],
                 offset: None,
                 limit: None,
             },
@@ -122,6 +124,8 @@ fn test_query_builder_parses_correctly() {
                     filter_type: FilterType::IdSelection(ParsedValue::Number(84848)),
                 }],
                 sorts: vec![],
+                ranks: vec![],
+                group_by: vec![],
                 offset: None,
                 limit: None,
             },
@@ -1,4 +1,5 @@
 mod database;
 mod graphql_schema;
 mod service;
+mod sqlite;
 mod web_api_postgres;
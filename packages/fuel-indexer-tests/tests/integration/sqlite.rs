@@ -0,0 +1,779 @@
+use axum::response::IntoResponse;
+use fuel_indexer::{
+    Database, Executor, FtColumn, IndexerConfig, IndexerError, WasmIndexExecutor,
+};
+use fuel_indexer_database::{
+    queries,
+    types::{IndexerAssetType, IndexerCheckpoint, IndexerLog},
+    DbType, IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_graphql::graphql::GraphqlQueryBuilder;
+use fuel_indexer_lib::{
+    fully_qualified_namespace, manifest::Manifest, type_id, utils::serialize,
+};
+use fuel_indexer_schema::db::{
+    manager::SchemaManager, snapshot::IndexerSnapshot, tables::IndexerSchema,
+};
+use fuel_indexer_types::{
+    ffi::{MetricUpdate, WasmPanic, FFI_VERSION},
+    fuel::{BlockData, Consensus, Header},
+};
+use serde_json::json;
+
+const MANIFEST: &str = r#"
+namespace: test_namespace
+identifier: sqlite_indexer
+abi: ~
+graphql_schema: schema.graphql
+contract_id: ~
+module:
+  wasm: sqlite_indexer.wasm
+"#;
+
+const GRAPHQL_SCHEMA: &str = r#"
+type Transfer {
+    id: ID!
+    amount: UInt8!
+}
+"#;
+
+/// Register the indexer of `MANIFEST`, along with its manifest and schema.
+/// The value of the indexer metric `metric` named `name`, as exported to Prometheus.
+async fn indexer_metric(metric: &str, name: &str) -> Option<String> {
+    let response = fuel_indexer_metrics::encode_metrics_response().into_response();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let label = format!("name=\"{name}\"");
+
+    String::from_utf8(body.to_vec())
+        .unwrap()
+        .lines()
+        .find(|line| line.starts_with(metric) && line.contains(&label))
+        .and_then(|line| line.split(' ').last())
+        .map(|value| value.to_string())
+}
+
+async fn register_indexer(
+    pool: &IndexerConnectionPool,
+    conn: &mut IndexerConnection,
+    manifest: &Manifest,
+) {
+    let (namespace, identifier) = (&manifest.namespace, &manifest.identifier);
+    queries::register_indexer(conn, namespace, identifier, None)
+        .await
+        .expect("Failed to register indexer");
+    queries::register_indexer_asset(
+        conn,
+        namespace,
+        identifier,
+        Manifest::try_into(manifest.clone()).unwrap(),
+        IndexerAssetType::Manifest,
+        None,
+    )
+    .await
+    .expect("Failed to register manifest");
+    SchemaManager::new(pool.clone())
+        .new_schema(
+            namespace,
+            identifier,
+            GRAPHQL_SCHEMA,
+            manifest.execution_source(),
+            conn,
+        )
+        .await
+        .expect("Failed to create schema");
+}
+
+#[tokio::test]
+async fn test_sqlite_registers_indexer_stores_objects_and_answers_queries() {
+    let pool = IndexerConnectionPool::connect("sqlite::memory:")
+        .await
+        .expect("Connection pool error");
+
+    // In-memory databases only live as long as one of their connections.
+    let mut conn = pool
+        .acquire()
+        .await
+        .expect("Failed to acquire indexer connection");
+    queries::run_migration(&mut conn)
+        .await
+        .expect("Failed to run migrations");
+
+    let manifest = Manifest::try_from(MANIFEST).unwrap();
+    let (namespace, identifier) = (&manifest.namespace, &manifest.identifier);
+
+    let indexer = queries::register_indexer(&mut conn, namespace, identifier, None)
+        .await
+        .expect("Failed to register indexer");
+    assert_eq!(
+        queries::get_indexer(&mut conn, namespace, identifier)
+            .await
+            .unwrap()
+            .map(|i| i.id),
+        Some(indexer.id)
+    );
+    queries::register_indexer_asset(
+        &mut conn,
+        namespace,
+        identifier,
+        Manifest::try_into(manifest.clone()).unwrap(),
+        IndexerAssetType::Manifest,
+        None,
+    )
+    .await
+    .expect("Failed to register manifest");
+
+    SchemaManager::new(pool.clone())
+        .new_schema(
+            namespace,
+            identifier,
+            GRAPHQL_SCHEMA,
+            manifest.execution_source(),
+            &mut conn,
+        )
+        .await
+        .expect("Failed to create schema");
+
+    let mut db = Database::new(pool.clone(), &manifest)
+        .await
+        .expect("Failed to create database object.");
+    let transfer_ty_id = type_id(
+        &fully_qualified_namespace(namespace, identifier),
+        "Transfer",
+    );
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    for (id, amount) in [(1, 9), (2, 10), (3, 100)] {
+        let columns = vec![FtColumn::ID(Some(id)), FtColumn::UInt8(Some(amount))];
+        db.put_object(transfer_ty_id, columns, vec![id as u8]).await;
+    }
+    assert!(db.take_write_error().is_none());
+    db.commit_transaction()
+        .await
+        .expect("commit transaction failed");
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    assert_eq!(db.get_object(transfer_ty_id, 2).await, Some(vec![2]));
+    assert_eq!(db.get_object(transfer_ty_id, 4).await, None);
+    db.revert_transaction()
+        .await
+        .expect("revert transaction failed");
+
+    // Amounts are stored as text, but are compared and sorted as numbers.
+    let schema = IndexerSchema::load(&pool, namespace, identifier)
+        .await
+        .expect("Failed to load schema");
+    let query = GraphqlQueryBuilder::new(
+        &schema,
+        "query { transfer(filter: { amount: { gt: 9 } }, order: { amount: desc }) { id amount } }",
+    )
+    .unwrap()
+    .build()
+    .unwrap()
+    .as_sql(&schema, pool.database_type())
+    .unwrap()
+    .join(";\n");
+
+    let result = queries::run_query(&mut conn, query)
+        .await
+        .expect("Failed to run query");
+    assert_eq!(
+        result,
+        json!([{ "id": "3", "amount": "100" }, { "id": "2", "amount": "10" }])
+    );
+}
+
+#[tokio::test]
+async fn test_sqlite_snapshot_restores_entities_kv_store_and_checkpoint() {
+    let pool = IndexerConnectionPool::connect("sqlite::memory:")
+        .await
+        .expect("Connection pool error");
+    let mut conn = pool
+        .acquire()
+        .await
+        .expect("Failed to acquire indexer connection");
+    queries::run_migration(&mut conn)
+        .await
+        .expect("Failed to run migrations");
+
+    let manifest = Manifest::try_from(MANIFEST).unwrap();
+    let (namespace, identifier) = (&manifest.namespace, &manifest.identifier);
+    register_indexer(&pool, &mut conn, &manifest).await;
+
+    let mut db = Database::new(pool.clone(), &manifest)
+        .await
+        .expect("Failed to create database object.");
+    let transfer_ty_id = type_id(
+        &fully_qualified_namespace(namespace, identifier),
+        "Transfer",
+    );
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    for (id, amount) in [(1, 9), (2, 10), (3, 100)] {
+        let columns = vec![FtColumn::ID(Some(id)), FtColumn::UInt8(Some(amount))];
+        db.put_object(transfer_ty_id, columns, vec![id as u8]).await;
+    }
+    assert!(db.take_write_error().is_none());
+    db.commit_transaction()
+        .await
+        .expect("commit transaction failed");
+
+    queries::put_kv(&mut conn, namespace, identifier, b"key", b"value")
+        .await
+        .expect("Failed to put key/value entry");
+    queries::put_checkpoint(
+        &mut conn,
+        namespace,
+        identifier,
+        &IndexerCheckpoint {
+            block_height: 42,
+            block_id: "ab".repeat(32),
+            cursor: Some("42".to_string()),
+        },
+    )
+    .await
+    .expect("Failed to put checkpoint");
+
+    let snapshot = IndexerSnapshot::take(&pool, namespace, identifier)
+        .await
+        .expect("Failed to take snapshot");
+    assert_eq!(
+        snapshot.tables.iter().map(|t| t.rows.len()).sum::<usize>(),
+        3
+    );
+
+    // Snapshots are transferred as JSON.
+    let snapshot: IndexerSnapshot =
+        serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+    let target = IndexerConnectionPool::connect("sqlite::memory:")
+        .await
+        .expect("Connection pool error");
+    let mut target_conn = target
+        .acquire()
+        .await
+        .expect("Failed to acquire indexer connection");
+    queries::run_migration(&mut target_conn)
+        .await
+        .expect("Failed to run migrations");
+
+    snapshot
+        .restore(&target)
+        .await
+        .expect("Failed to restore snapshot");
+    assert!(
+        snapshot.restore(&target).await.is_err(),
+        "Restoring over an existing indexer should fail"
+    );
+
+    assert_eq!(
+        queries::get_kv(&mut target_conn, namespace, identifier, b"key")
+            .await
+            .unwrap(),
+        Some(b"value".to_vec())
+    );
+    let checkpoint =
+        queries::last_checkpoint_for_indexer(&mut target_conn, namespace, identifier)
+            .await
+            .unwrap()
+            .expect("Checkpoint should be restored");
+    assert_eq!(checkpoint.block_height, 42);
+    assert_eq!(checkpoint.cursor.as_deref(), Some("42"));
+
+    let mut db = Database::new(target.clone(), &manifest)
+        .await
+        .expect("Failed to create database object.");
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    for id in 1..=3 {
+        assert_eq!(
+            db.get_object(transfer_ty_id, id).await,
+            Some(vec![id as u8])
+        );
+    }
+    db.revert_transaction()
+        .await
+        .expect("revert transaction failed");
+
+    let restored = IndexerSnapshot::take(&target, namespace, identifier)
+        .await
+        .expect("Failed to take snapshot");
+    assert_eq!(
+        serde_json::to_value(&restored.tables).unwrap(),
+        serde_json::to_value(&snapshot.tables).unwrap()
+    );
+    assert_eq!(restored.kv, snapshot.kv);
+}
+
+#[tokio::test]
+async fn test_sqlite_key_value_store_gets_puts_and_deletes_values() {
+    let pool = IndexerConnectionPool::connect("sqlite::memory:")
+        .await
+        .expect("Connection pool error");
+    let mut conn = pool
+        .acquire()
+        .await
+        .expect("Failed to acquire indexer connection");
+    queries::run_migration(&mut conn)
+        .await
+        .expect("Failed to run migrations");
+
+    let manifest = Manifest::try_from(MANIFEST).unwrap();
+    register_indexer(&pool, &mut conn, &manifest).await;
+
+    let mut db = Database::new(pool.clone(), &manifest)
+        .await
+        .expect("Failed to create database object.");
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    assert_eq!(db.get_kv(b"key").await, None);
+    db.put_kv(b"key", b"value").await;
+    db.put_kv(b"other", b"value").await;
+    assert_eq!(db.get_kv(b"key").await, Some(b"value".to_vec()));
+    db.put_kv(b"key", b"replaced").await;
+    assert_eq!(db.get_kv(b"key").await, Some(b"replaced".to_vec()));
+    assert!(db.take_write_error().is_none());
+    db.commit_transaction()
+        .await
+        .expect("commit transaction failed");
+
+    // Values are reverted along with the rest of the transaction.
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    db.delete_kv(b"key").await;
+    assert_eq!(db.get_kv(b"key").await, None);
+    db.revert_transaction()
+        .await
+        .expect("revert transaction failed");
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    assert_eq!(db.get_kv(b"key").await, Some(b"replaced".to_vec()));
+    db.delete_kv(b"key").await;
+    assert!(db.take_write_error().is_none());
+    db.commit_transaction()
+        .await
+        .expect("commit transaction failed");
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    assert_eq!(db.get_kv(b"key").await, None);
+    assert_eq!(db.get_kv(b"other").await, Some(b"value".to_vec()));
+    db.revert_transaction()
+        .await
+        .expect("revert transaction failed");
+
+    // A write that can't be persisted fails the batch, rather than only being logged.
+    let kv_store = DbType::Sqlite.table_name(
+        &fully_qualified_namespace(&manifest.namespace, &manifest.identifier),
+        "__kv_store",
+    );
+    queries::execute_query(&mut conn, format!("DROP TABLE {kv_store}"))
+        .await
+        .expect("Failed to drop key/value store");
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    db.put_kv(b"key", b"value").await;
+    assert!(db.take_write_error().is_some());
+    db.revert_transaction()
+        .await
+        .expect("revert transaction failed");
+}
+
+#[tokio::test]
+async fn test_sqlite_logs_are_flushed_per_batch_and_trimmed_to_capacity() {
+    let pool = IndexerConnectionPool::connect("sqlite::memory:")
+        .await
+        .expect("Connection pool error");
+    let mut conn = pool
+        .acquire()
+        .await
+        .expect("Failed to acquire indexer connection");
+    queries::run_migration(&mut conn)
+        .await
+        .expect("Failed to run migrations");
+
+    let manifest = Manifest::try_from(MANIFEST).unwrap();
+    let (namespace, identifier) = (&manifest.namespace, &manifest.identifier);
+    register_indexer(&pool, &mut conn, &manifest).await;
+
+    let mut db = Database::new(pool.clone(), &manifest)
+        .await
+        .expect("Failed to create database object.");
+
+    let levels = ["error", "warn", "info", "debug", "trace"]
+        .iter()
+        .map(|level| level.to_string())
+        .collect::<Vec<String>>();
+
+    // Logged lines are available once flushed, before the transaction commits.
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    db.set_block_height(7);
+    db.record_log("info", "first".to_string());
+    db.record_log("warn", "second".to_string());
+    db.flush_logs().await;
+
+    let logs = queries::indexer_logs(&mut conn, namespace, identifier, &levels, None, 10)
+        .await
+        .expect("Failed to get logs");
+    assert_eq!(
+        logs.iter()
+            .map(|log| (log.level.as_str(), log.message.as_str(), log.block_height))
+            .collect::<Vec<_>>(),
+        vec![("info", "first", Some(7)), ("warn", "second", Some(7))]
+    );
+
+    // Lines are kept even if the transaction is reverted.
+    db.revert_transaction()
+        .await
+        .expect("revert transaction failed");
+    let logs = queries::indexer_logs(&mut conn, namespace, identifier, &levels, None, 10)
+        .await
+        .expect("Failed to get logs");
+    assert_eq!(logs.len(), 2);
+
+    // Only the latest lines are kept.
+    let lines = (0..5)
+        .map(|i| IndexerLog {
+            id: 0,
+            level: if i % 2 == 0 { "error" } else { "debug" }.to_string(),
+            block_height: Some(i),
+            message: format!("line {i}"),
+            created_at: chrono::Utc::now(),
+        })
+        .collect::<Vec<IndexerLog>>();
+    queries::put_logs(&mut conn, namespace, identifier, &lines, 3)
+        .await
+        .expect("Failed to put logs");
+
+    let logs = queries::indexer_logs(&mut conn, namespace, identifier, &levels, None, 10)
+        .await
+        .expect("Failed to get logs");
+    assert_eq!(
+        logs.iter()
+            .map(|log| log.message.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["line 2", "line 3", "line 4"]
+    );
+
+    let errors =
+        queries::indexer_logs(&mut conn, namespace, identifier, &levels[..1], None, 10)
+            .await
+            .expect("Failed to get logs");
+    assert_eq!(
+        errors
+            .iter()
+            .map(|log| log.message.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["line 2", "line 4"]
+    );
+
+    let after = queries::indexer_logs(
+        &mut conn,
+        namespace,
+        identifier,
+        &levels,
+        Some(logs[0].id),
+        1,
+    )
+    .await
+    .expect("Failed to get logs");
+    assert_eq!(after.len(), 1);
+    assert_eq!(after[0].message, "line 3");
+}
+
+#[tokio::test]
+async fn test_sqlite_metric_updates_are_applied_on_commit_and_discarded_on_revert() {
+    let pool = IndexerConnectionPool::connect("sqlite::memory:")
+        .await
+        .expect("Connection pool error");
+    let mut conn = pool
+        .acquire()
+        .await
+        .expect("Failed to acquire indexer connection");
+    queries::run_migration(&mut conn)
+        .await
+        .expect("Failed to run migrations");
+
+    let manifest = Manifest::try_from(MANIFEST).unwrap();
+    register_indexer(&pool, &mut conn, &manifest).await;
+
+    let mut db = Database::new(pool.clone(), &manifest)
+        .await
+        .expect("Failed to create database object.");
+
+    let updates = |count: u64, level: f64| {
+        vec![
+            MetricUpdate::Counter {
+                name: "sqlite_transfers".to_string(),
+                value: count,
+            },
+            MetricUpdate::Gauge {
+                name: "sqlite_level".to_string(),
+                value: level,
+            },
+        ]
+    };
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    updates(5, 9.0)
+        .into_iter()
+        .for_each(|update| db.record_metric(update));
+    db.revert_transaction()
+        .await
+        .expect("revert transaction failed");
+
+    assert_eq!(
+        indexer_metric("indexer_counter", "sqlite_transfers").await,
+        None
+    );
+    assert_eq!(indexer_metric("indexer_gauge", "sqlite_level").await, None);
+
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    updates(2, 1.5)
+        .into_iter()
+        .for_each(|update| db.record_metric(update));
+    db.commit_transaction()
+        .await
+        .expect("commit transaction failed");
+
+    assert_eq!(
+        indexer_metric("indexer_counter", "sqlite_transfers").await,
+        Some("2".to_string())
+    );
+    assert_eq!(
+        indexer_metric("indexer_gauge", "sqlite_level").await,
+        Some("1.5".to_string())
+    );
+
+    // Updates buffered before a revert aren't applied by the next commit either.
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    updates(5, 9.0)
+        .into_iter()
+        .for_each(|update| db.record_metric(update));
+    db.revert_transaction()
+        .await
+        .expect("revert transaction failed");
+    db.start_transaction()
+        .await
+        .expect("Start transaction failed");
+    db.commit_transaction()
+        .await
+        .expect("commit transaction failed");
+
+    assert_eq!(
+        indexer_metric("indexer_counter", "sqlite_transfers").await,
+        Some("2".to_string())
+    );
+    assert_eq!(
+        indexer_metric("indexer_gauge", "sqlite_level").await,
+        Some("1.5".to_string())
+    );
+}
+
+/// A WASM module exporting the given FFI version, and importing the given functions.
+fn wasm_module(ffi_version: &str, imports: &[&str]) -> String {
+    let imports = imports
+        .iter()
+        .map(|name| format!(r#"(import "env" "{name}" (func (param i32 i32)))"#))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!(
+        r#"(module
+            {imports}
+            (memory (export "memory") 1)
+            (data (i32.const 0) "{ffi_version}")
+            (func (export "get_ffi_version_ptr") (result i32) i32.const 0)
+            (func (export "get_ffi_version_len") (result i32) i32.const {})
+            (func (export "alloc_fn") (param i32) (result i32) i32.const 64)
+            (func (export "dealloc_fn") (param i32 i32))
+            (func (export "handle_events") (param i32 i32)))"#,
+        ffi_version.len()
+    )
+}
+
+#[tokio::test]
+async fn test_sqlite_wasm_modules_of_unsupported_ffi_versions_are_rejected() {
+    let pool = IndexerConnectionPool::connect("sqlite::memory:")
+        .await
+        .expect("Connection pool error");
+    let mut conn = pool
+        .acquire()
+        .await
+        .expect("Failed to acquire indexer connection");
+    queries::run_migration(&mut conn)
+        .await
+        .expect("Failed to run migrations");
+
+    let manifest = Manifest::try_from(MANIFEST).unwrap();
+    register_indexer(&pool, &mut conn, &manifest).await;
+
+    let config = IndexerConfig::default();
+    let executor =
+        |module: String| WasmIndexExecutor::new(&config, &manifest, module, pool.clone());
+
+    let current = FFI_VERSION.to_string();
+    assert!(executor(wasm_module(&current, &["ff_put_panic"]))
+        .await
+        .is_ok());
+
+    let newer = (FFI_VERSION + 1).to_string();
+    assert!(matches!(
+        executor(wasm_module(&newer, &[])).await,
+        Err(IndexerError::UnsupportedFfiVersion(version)) if version == FFI_VERSION + 1
+    ));
+
+    assert!(matches!(
+        executor(wasm_module("0", &[])).await,
+        Err(IndexerError::UnsupportedFfiVersion(0))
+    ));
+
+    // Functions that the service doesn't export are reported before instantiating the module.
+    assert!(matches!(
+        executor(wasm_module(&current, &["ff_put_panic", "ff_from_the_future"])).await,
+        Err(IndexerError::MissingImport(name)) if name == "ff_from_the_future"
+    ));
+}
+
+#[tokio::test]
+async fn test_sqlite_wasm_panics_are_reported_as_indexer_failures() {
+    let pool = IndexerConnectionPool::connect("sqlite::memory:")
+        .await
+        .expect("Connection pool error");
+    let mut conn = pool
+        .acquire()
+        .await
+        .expect("Failed to acquire indexer connection");
+    queries::run_migration(&mut conn)
+        .await
+        .expect("Failed to run migrations");
+
+    let manifest = Manifest::try_from(MANIFEST).unwrap();
+    register_indexer(&pool, &mut conn, &manifest).await;
+
+    // A module that reports a panic to the host, then traps, like the panic hook of
+    // modules built with fuel-indexer-utils.
+    let panic = serialize(&WasmPanic {
+        message: "Greeting has no sender".to_string(),
+        file: Some("src/lib.rs".to_string()),
+        line: Some(12),
+        column: Some(5),
+    });
+    let module = format!(
+        r#"(module
+            (import "env" "ff_put_panic" (func $put_panic (param i32 i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "{}")
+            (func (export "get_ffi_version_ptr") (result i32) i32.const 512)
+            (func (export "get_ffi_version_len") (result i32) i32.const {})
+            (data (i32.const 512) "{FFI_VERSION}")
+            (func (export "alloc_fn") (param i32) (result i32) i32.const 1024)
+            (func (export "dealloc_fn") (param i32 i32))
+            (func (export "handle_events") (param i32 i32)
+                (call $put_panic (i32.const 0) (i32.const {}))
+                unreachable))"#,
+        panic
+            .iter()
+            .map(|byte| format!("\\{byte:02x}"))
+            .collect::<String>(),
+        FFI_VERSION.to_string().len(),
+        panic.len(),
+    );
+
+    let config = IndexerConfig {
+        indexer_handler_timeout: 5,
+        ..IndexerConfig::default()
+    };
+    let mut executor = WasmIndexExecutor::new(&config, &manifest, module, pool.clone())
+        .await
+        .expect("Failed to create executor");
+
+    let block = |height: u64| BlockData {
+        height,
+        id: [height as u8; 32].into(),
+        header: Header {
+            id: [height as u8; 32].into(),
+            da_height: 0,
+            transactions_count: 0,
+            output_messages_count: 0,
+            transactions_root: [0u8; 32].into(),
+            output_messages_root: [0u8; 32].into(),
+            height,
+            prev_root: [0u8; 32].into(),
+            time: 0,
+            application_hash: [0u8; 32].into(),
+        },
+        producer: None,
+        time: 0,
+        consensus: Consensus::default(),
+        transactions: Vec::new(),
+    };
+
+    let err = executor
+        .handle_events(vec![block(7), block(8)])
+        .await
+        .expect_err("Panicking handler succeeded");
+    let message = err.to_string();
+    assert_eq!(
+        message,
+        "Indexer panicked 'Greeting has no sender' at src/lib.rs:12:5 while handling block 7"
+    );
+    let IndexerError::Panic {
+        panic,
+        block_height,
+    } = err
+    else {
+        panic!("Expected a panic, got: {message}");
+    };
+    assert_eq!(panic.message, "Greeting has no sender");
+    assert_eq!(block_height, Some(7));
+
+    // The failure outlives the reverted transaction, and is reported by `/status`.
+    let index_id =
+        queries::get_indexer_id(&mut conn, &manifest.namespace, &manifest.identifier)
+            .await
+            .expect("Failed to get indexer ID");
+    let failure = queries::last_indexer_failure(&mut conn, &index_id)
+        .await
+        .expect("Failed to get last failure")
+        .expect("Failure was not recorded");
+    assert_eq!(failure.message, message);
+    assert_eq!(failure.block_height, Some(7));
+
+    let levels = vec!["error".to_string()];
+    let logs = queries::indexer_logs(
+        &mut conn,
+        &manifest.namespace,
+        &manifest.identifier,
+        &levels,
+        None,
+        10,
+    )
+    .await
+    .expect("Failed to get logs");
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, message);
+    assert_eq!(logs[0].block_height, Some(7));
+}
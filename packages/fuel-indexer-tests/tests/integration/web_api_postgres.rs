@@ -1,3 +1,4 @@
+use fuel_indexer_database::types::{IndexerFailure, IndexerLog};
 use fuel_indexer_lib::config::GraphQLConfig;
 use fuel_indexer_postgres as postgres;
 use fuel_indexer_tests::assets::{
@@ -198,3 +199,144 @@ async fn test_signature_route_validates_signature_expires_nonce_and_creates_jwt(
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn test_logs_route_returns_filtered_pages_of_logged_lines_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (app, _rx) = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    postgres::register_indexer(
+        &mut conn,
+        "test_namespace",
+        "logged_indexer",
+        None,
+        chrono::Utc::now(),
+    )
+    .await
+    .unwrap();
+
+    let lines = ["info", "error", "debug", "warn"]
+        .iter()
+        .enumerate()
+        .map(|(i, level)| IndexerLog {
+            id: 0,
+            level: level.to_string(),
+            block_height: Some(i as u64),
+            message: format!("line {i}"),
+            created_at: chrono::Utc::now(),
+        })
+        .collect::<Vec<IndexerLog>>();
+    postgres::put_logs(&mut conn, "test_namespace", "logged_indexer", &lines, 100)
+        .await
+        .unwrap();
+
+    let server = axum::Server::bind(&GraphQLConfig::default().into())
+        .serve(app.into_make_service());
+
+    let srv = tokio::spawn(server);
+
+    let logs = |query: &'static str| async move {
+        http_client()
+            .get(format!(
+                "http://localhost:29987/api/index/test_namespace/logged_indexer/logs{query}"
+            ))
+            .send()
+            .await
+            .unwrap()
+    };
+    let messages = |logs: &[IndexerLog]| {
+        logs.iter()
+            .map(|log| log.message.clone())
+            .collect::<Vec<String>>()
+    };
+
+    let all: Vec<IndexerLog> = logs("").await.json().await.unwrap();
+    assert_eq!(messages(&all), vec!["line 0", "line 1", "line 2", "line 3"]);
+
+    let warnings: Vec<IndexerLog> = logs("?level=warn").await.json().await.unwrap();
+    assert_eq!(messages(&warnings), vec!["line 1", "line 3"]);
+
+    let latest: Vec<IndexerLog> = logs("?limit=1").await.json().await.unwrap();
+    assert_eq!(messages(&latest), vec!["line 3"]);
+
+    let resp = http_client()
+        .get(format!(
+            "http://localhost:29987/api/index/test_namespace/logged_indexer/logs?after={}&limit=2",
+            all[0].id
+        ))
+        .send()
+        .await
+        .unwrap();
+    let after: Vec<IndexerLog> = resp.json().await.unwrap();
+    assert_eq!(messages(&after), vec!["line 1", "line 2"]);
+
+    let resp = logs("?level=loud").await;
+    assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    srv.abort();
+}
+
+#[tokio::test]
+async fn test_status_route_reports_the_last_failure_of_each_indexer_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (app, _rx) = api_server_app_postgres(Some(&test_db.url)).await;
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    for identifier in ["failed_indexer", "healthy_indexer"] {
+        postgres::register_indexer(
+            &mut conn,
+            "test_namespace",
+            identifier,
+            None,
+            chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+    }
+
+    for (message, block_height) in [("first failure", 3), ("second failure", 5)] {
+        let failure = IndexerFailure {
+            message: message.to_string(),
+            block_height: Some(block_height),
+            created_at: chrono::Utc::now(),
+        };
+        postgres::put_indexer_failure(
+            &mut conn,
+            "test_namespace",
+            "failed_indexer",
+            &failure,
+        )
+        .await
+        .unwrap();
+    }
+
+    let server = axum::Server::bind(&GraphQLConfig::default().into())
+        .serve(app.into_make_service());
+
+    let srv = tokio::spawn(server);
+
+    let statuses: Vec<serde_json::Value> = http_client()
+        .get("http://localhost:29987/api/status")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    srv.abort();
+
+    let status = |identifier: &str| {
+        statuses
+            .iter()
+            .find(|status| status["identifier"] == identifier)
+            .unwrap()
+            .clone()
+    };
+
+    let failed = status("failed_indexer");
+    assert_eq!(failed["last_failure"]["message"], "second failure");
+    assert_eq!(failed["last_failure"]["block_height"], 5);
+    assert!(status("healthy_indexer")["last_failure"].is_null());
+}
@@ -1,5 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the contract between the indexer service and indexer modules: the
+/// functions each side exports to the other, and the encoding of the data passed
+/// between them (e.g., `FtColumn`s).
+///
+/// Any change to that contract must bump this version, including functions that the
+/// indexer service starts exporting to modules: a module importing such a function
+/// can't run on an older indexer service.
+///
+/// - 1: Modules built before the contract was versioned.
+/// - 2: Modules export their version.
+/// - 3: Modules report panics through `ff_put_panic` before trapping.
+pub const FFI_VERSION: u32 = 3;
+
+/// Oldest version of the contract that the indexer service still supports.
+///
+/// Modules built before the contract was versioned don't export their version, and
+/// are considered to be built against version 1.
+pub const MIN_SUPPORTED_FFI_VERSION: u32 = 1;
+
 pub const LOG_LEVEL_ERROR: u32 = 0;
 pub const LOG_LEVEL_WARN: u32 = 1;
 pub const LOG_LEVEL_INFO: u32 = 2;
 pub const LOG_LEVEL_DEBUG: u32 = 3;
 pub const LOG_LEVEL_TRACE: u32 = 4;
+
+/// A panic raised by indexer code, passed from indexer modules to the host before trapping.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WasmPanic {
+    /// The panic message.
+    pub message: String,
+
+    /// Source file in which the panic was raised, if known.
+    pub file: Option<String>,
+
+    /// Line at which the panic was raised, if known.
+    pub line: Option<u32>,
+
+    /// Column at which the panic was raised, if known.
+    pub column: Option<u32>,
+}
+
+impl std::fmt::Display for WasmPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.file, self.line, self.column) {
+            (Some(file), Some(line), Some(column)) => {
+                write!(f, "'{}' at {file}:{line}:{column}", self.message)
+            }
+            (Some(file), Some(line), None) => {
+                write!(f, "'{}' at {file}:{line}", self.message)
+            }
+            _ => write!(f, "'{}'", self.message),
+        }
+    }
+}
+
+/// An update to a metric defined by an indexer, passed from indexer code to the host.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MetricUpdate {
+    /// Increment the counter with the given name.
+    Counter { name: String, value: u64 },
+
+    /// Set the gauge with the given name.
+    Gauge { name: String, value: f64 },
+
+    /// Record an observation in the histogram with the given name.
+    Histogram { name: String, value: f64 },
+}
@@ -158,6 +158,12 @@ pub struct TransactionData {
     pub id: TxId,
 }
 
+impl TypeId for TransactionData {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "TransactionData") as usize
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Header {
     pub id: Bytes32,
@@ -205,6 +211,12 @@ pub enum Input {
     Message(InputMessage),
 }
 
+impl TypeId for Input {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "Input") as usize
+    }
+}
+
 impl From<ClientInput> for Input {
     fn from(input: ClientInput) -> Self {
         match input {
@@ -390,6 +402,12 @@ pub struct InputCoin {
     pub predicate_data: HexString,
 }
 
+impl TypeId for InputCoin {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "InputCoin") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct InputContract {
     pub utxo_id: UtxoId,
@@ -399,6 +417,12 @@ pub struct InputContract {
     pub contract_id: ContractId,
 }
 
+impl TypeId for InputContract {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "InputContract") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct InputMessage {
     pub sender: Address,
@@ -411,6 +435,12 @@ pub struct InputMessage {
     pub predicate_data: HexString,
 }
 
+impl TypeId for InputMessage {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "InputMessage") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TransactionStatus {
     Failure {
@@ -457,6 +487,12 @@ pub enum Output {
     Unknown,
 }
 
+impl TypeId for Output {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "Output") as usize
+    }
+}
+
 impl From<ClientOutput> for Output {
     fn from(output: ClientOutput) -> Self {
         match output {
@@ -550,6 +586,12 @@ pub struct CoinOutput {
     pub asset_id: AssetId,
 }
 
+impl TypeId for CoinOutput {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "CoinOutput") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ContractOutput {
     pub input_index: i32,
@@ -557,6 +599,12 @@ pub struct ContractOutput {
     pub state_root: Bytes32,
 }
 
+impl TypeId for ContractOutput {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "ContractOutput") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChangeOutput {
     pub to: Address,
@@ -564,6 +612,12 @@ pub struct ChangeOutput {
     pub asset_id: AssetId,
 }
 
+impl TypeId for ChangeOutput {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "ChangeOutput") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VariableOutput {
     pub to: Address,
@@ -571,18 +625,36 @@ pub struct VariableOutput {
     pub asset_id: AssetId,
 }
 
+impl TypeId for VariableOutput {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "VariableOutput") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MessageOutput {
     pub amount: u64,
     pub recipient: Address,
 }
 
+impl TypeId for MessageOutput {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "MessageOutput") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ContractCreated {
     pub contract_id: ContractId,
     pub state_root: Bytes32,
 }
 
+impl TypeId for ContractCreated {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "ContractCreated") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Genesis {
     pub chain_config_hash: Bytes32,
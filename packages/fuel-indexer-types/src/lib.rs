@@ -110,6 +110,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_returndata_receipt_roundtrips_with_distinct_type_id() {
+        let returndata = ReturnData {
+            contract_id: ContractId::default(),
+            ptr: 2342143,
+            len: 3,
+            digest: Bytes32::default(),
+            data: vec![1, 2, 3],
+            pc: 0,
+            is: 0,
+        };
+
+        let returndata: ReturnData =
+            bincode::deserialize(&bincode::serialize(&returndata).unwrap()).unwrap();
+
+        assert_eq!(returndata.data, vec![1, 2, 3]);
+        assert_ne!(ReturnData::type_id(), Return::type_id());
+    }
+
     #[test]
     fn test_into_json_blob_timestamp() {
         let timestamp: Timestamp = 1234567890;
@@ -49,6 +49,23 @@ impl TypeId for LogData {
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReturnData {
+    pub contract_id: ContractId,
+    pub ptr: u64,
+    pub len: u64,
+    pub digest: Bytes32,
+    pub data: Vec<u8>,
+    pub pc: u64,
+    pub is: u64,
+}
+
+impl TypeId for ReturnData {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "ReturnData") as usize
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ScriptResult {
     pub result: u64,
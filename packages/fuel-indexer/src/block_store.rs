@@ -0,0 +1,430 @@
+use crate::{IndexerError, IndexerResult};
+use async_std::sync::Arc;
+use fuel_indexer_lib::{
+    config::BlockStoreConfig,
+    utils::{deserialize, serialize},
+};
+use fuel_indexer_types::fuel::BlockData;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+/// Extension of the files in which blocks are stored.
+const BLOCK_FILE_EXTENSION: &str = "blocks";
+
+/// Extension of files that are still being written.
+const TMP_FILE_EXTENSION: &str = "tmp";
+
+/// A file holding a contiguous range of blocks.
+#[derive(Debug, Clone)]
+struct BlockFile {
+    /// Height of the last block in the file.
+    last: u64,
+
+    /// Location of the file.
+    path: PathBuf,
+}
+
+/// On-disk store of the blocks fetched from a single Fuel node.
+///
+/// Each page fetched from the node is written to its own file, named after the range
+/// of heights it holds. Once the last block of a segment is stored, the files within
+/// that segment are compacted into as few files as possible, so that re-indexing reads
+/// a handful of large files rather than a great many small ones.
+///
+/// The store doesn't know the height of the chain, so pages served from it are always
+/// reported as having a next page. If the store is ahead of the node, the next request
+/// falls through to the node, which reports the actual tip.
+#[derive(Debug)]
+pub struct BlockStore {
+    /// Directory in which blocks are stored.
+    path: PathBuf,
+
+    /// Configuration of the block store.
+    config: BlockStoreConfig,
+
+    /// Stored files, by the height of their first block.
+    files: BTreeMap<u64, BlockFile>,
+
+    /// Contents of the most recently read file, by the height of its first block.
+    cache: Option<(u64, Arc<Vec<BlockData>>)>,
+}
+
+impl BlockStore {
+    /// Open the block store for the Fuel node at `fuel_node_addr`, creating it if necessary.
+    #[allow(clippy::result_large_err)]
+    pub fn open(config: &BlockStoreConfig, fuel_node_addr: &str) -> IndexerResult<Self> {
+        // Blocks of different nodes, which might be on different chains, are never mixed.
+        let node_dir = fuel_node_addr
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        let path = Path::new(&config.path).join(node_dir);
+        std::fs::create_dir_all(&path)?;
+
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&path)? {
+            let file_path = entry?.path();
+            match file_path.extension().and_then(|ext| ext.to_str()) {
+                Some(BLOCK_FILE_EXTENSION) => match parse_file_name(&file_path) {
+                    Some((first, last)) => files.push((first, last, file_path)),
+                    None => {
+                        warn!("Ignoring unrecognized block store file {file_path:?}.")
+                    }
+                },
+                // Left behind by an interrupted write.
+                Some(TMP_FILE_EXTENSION) => std::fs::remove_file(&file_path)?,
+                _ => {}
+            }
+        }
+
+        // A compaction that was interrupted after writing its output leaves behind
+        // the files it merged, which are covered by the output.
+        files.sort_by(|(a_first, a_last, _), (b_first, b_last, _)| {
+            a_first.cmp(b_first).then(b_last.cmp(a_last))
+        });
+        let mut store = Self {
+            path,
+            config: config.clone(),
+            files: BTreeMap::new(),
+            cache: None,
+        };
+        let mut covered_until = None;
+        for (first, last, path) in files {
+            if covered_until.map_or(false, |until| last <= until) {
+                std::fs::remove_file(&path)?;
+                continue;
+            }
+            covered_until = Some(last);
+            store.files.insert(first, BlockFile { last, path });
+        }
+
+        info!(
+            "Opened block store at {:?} with {} files.",
+            store.path,
+            store.files.len()
+        );
+
+        Ok(store)
+    }
+
+    /// Return up to `limit` stored blocks starting at `height`, if the block at
+    /// `height` is stored.
+    pub async fn blocks_from(
+        &mut self,
+        height: u64,
+        limit: usize,
+    ) -> IndexerResult<Option<Vec<BlockData>>> {
+        let mut blocks = Vec::new();
+        let mut next = height;
+
+        while blocks.len() < limit {
+            let (first, last) = match self.file_containing(next) {
+                Some((first, file)) => (first, file.last),
+                None => break,
+            };
+
+            let contents = self.read(first).await?;
+            blocks.extend(
+                contents
+                    .iter()
+                    .skip((next - first) as usize)
+                    .take(limit - blocks.len())
+                    .cloned(),
+            );
+            next = last + 1;
+        }
+
+        if blocks.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(blocks))
+    }
+
+    /// Persist the given blocks, skipping blocks that are already stored or that are
+    /// outside of the configured height range.
+    pub async fn put(&mut self, blocks: &[BlockData]) -> IndexerResult<()> {
+        let segment_size = self.config.segment_size.max(1);
+
+        // Files hold contiguous blocks, and never span more than one segment.
+        let mut runs: Vec<Vec<&BlockData>> = Vec::new();
+        for block in blocks {
+            if !self.config.contains(block.height)
+                || self.file_containing(block.height).is_some()
+            {
+                continue;
+            }
+
+            match runs.last_mut() {
+                Some(run)
+                    if run.last().map_or(false, |prev| {
+                        prev.height + 1 == block.height
+                            && prev.height / segment_size == block.height / segment_size
+                    }) =>
+                {
+                    run.push(block)
+                }
+                _ => runs.push(vec![block]),
+            }
+        }
+
+        for run in runs {
+            let first = run.first().map(|block| block.height).unwrap_or_default();
+            let last = run.last().map(|block| block.height).unwrap_or_default();
+            let path = self.write(first, last, &run).await?;
+            self.files.insert(first, BlockFile { last, path });
+
+            // Once the last block of a segment is stored, it is not expected to change.
+            if (last + 1) % segment_size == 0 {
+                self.compact(last / segment_size).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merge each contiguous run of files within the given segment into a single file.
+    async fn compact(&mut self, segment: u64) -> IndexerResult<()> {
+        let segment_size = self.config.segment_size.max(1);
+        let start = segment * segment_size;
+        let end = start + segment_size - 1;
+
+        let mut runs: Vec<Vec<(u64, BlockFile)>> = Vec::new();
+        for (first, file) in self.files.range(start..=end) {
+            match runs.last_mut() {
+                Some(run)
+                    if run
+                        .last()
+                        .map_or(false, |(_, prev)| prev.last + 1 == *first) =>
+                {
+                    run.push((*first, file.clone()))
+                }
+                _ => runs.push(vec![(*first, file.clone())]),
+            }
+        }
+
+        for run in runs.into_iter().filter(|run| run.len() > 1) {
+            let mut blocks = Vec::new();
+            for (first, _) in &run {
+                blocks.extend(self.read(*first).await?.iter().cloned());
+            }
+
+            let first = run.first().map(|(first, _)| *first).unwrap_or_default();
+            let last = run.last().map(|(_, file)| file.last).unwrap_or_default();
+            let path = self
+                .write(first, last, &blocks.iter().collect::<Vec<_>>())
+                .await?;
+
+            for (first, file) in run {
+                self.files.remove(&first);
+                async_std::fs::remove_file(&file.path).await?;
+            }
+            self.files.insert(first, BlockFile { last, path });
+            self.cache = None;
+        }
+
+        Ok(())
+    }
+
+    /// Return the file holding the block at `height`, if any.
+    fn file_containing(&self, height: u64) -> Option<(u64, &BlockFile)> {
+        self.files
+            .range(..=height)
+            .next_back()
+            .filter(|(_, file)| file.last >= height)
+            .map(|(first, file)| (*first, file))
+    }
+
+    /// Read the file whose first block is at height `first`.
+    async fn read(&mut self, first: u64) -> IndexerResult<Arc<Vec<BlockData>>> {
+        if let Some((cached, blocks)) = &self.cache {
+            if *cached == first {
+                return Ok(blocks.clone());
+            }
+        }
+
+        let file = self.files.get(&first).ok_or_else(|| {
+            IndexerError::Unknown(format!("No stored blocks from height {first}"))
+        })?;
+        let bytes = async_std::fs::read(&file.path).await?;
+        let blocks = Arc::new(deserialize::<Vec<BlockData>>(&bytes).map_err(|e| {
+            IndexerError::Unknown(format!(
+                "Failed to read stored blocks from {:?}: {e}",
+                file.path
+            ))
+        })?);
+
+        self.cache = Some((first, blocks.clone()));
+
+        Ok(blocks)
+    }
+
+    /// Write the given blocks to a new file, returning its location.
+    async fn write(
+        &self,
+        first: u64,
+        last: u64,
+        blocks: &[&BlockData],
+    ) -> IndexerResult<PathBuf> {
+        let path = self
+            .path
+            .join(format!("{first:020}-{last:020}.{BLOCK_FILE_EXTENSION}"));
+        let tmp_path = path.with_extension(TMP_FILE_EXTENSION);
+
+        // Files are renamed into place once fully written, so that an interrupted
+        // write never leaves behind a partial file.
+        async_std::fs::write(&tmp_path, serialize(&blocks)).await?;
+        async_std::fs::rename(&tmp_path, &path).await?;
+
+        Ok(path)
+    }
+}
+
+/// Parse the range of heights held by a file from its name.
+fn parse_file_name(path: &Path) -> Option<(u64, u64)> {
+    let stem = path.file_stem()?.to_str()?;
+    let (first, last) = stem.split_once('-')?;
+    let first = first.parse::<u64>().ok()?;
+    let last = last.parse::<u64>().ok()?;
+
+    (first <= last).then_some((first, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetcher::test_block;
+
+    const FUEL_NODE_ADDR: &str = "127.0.0.1:4000";
+
+    fn config(dir: &tempfile::TempDir, segment_size: u64) -> BlockStoreConfig {
+        BlockStoreConfig {
+            enabled: true,
+            path: dir.path().to_str().unwrap().to_string(),
+            segment_size,
+            ..BlockStoreConfig::default()
+        }
+    }
+
+    fn blocks(heights: std::ops::RangeInclusive<u64>) -> Vec<BlockData> {
+        heights.map(test_block).collect()
+    }
+
+    fn heights(blocks: &[BlockData]) -> Vec<u64> {
+        blocks.iter().map(|block| block.height).collect()
+    }
+
+    fn file_names(store: &BlockStore) -> Vec<String> {
+        let mut names = std::fs::read_dir(&store.path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<String>>();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn test_block_store_reads_blocks_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = BlockStore::open(&config(&dir, 100), FUEL_NODE_ADDR).unwrap();
+
+        store.put(&blocks(1..=3)).await.unwrap();
+        store.put(&blocks(4..=6)).await.unwrap();
+        // Blocks that are already stored are skipped.
+        store.put(&blocks(5..=8)).await.unwrap();
+        assert_eq!(store.files.len(), 3);
+
+        let read = store.blocks_from(2, 100).await.unwrap().unwrap();
+        assert_eq!(heights(&read), (2..=8).collect::<Vec<u64>>());
+
+        let read = store.blocks_from(3, 3).await.unwrap().unwrap();
+        assert_eq!(heights(&read), vec![3, 4, 5]);
+
+        assert!(store.blocks_from(0, 10).await.unwrap().is_none());
+        assert!(store.blocks_from(9, 10).await.unwrap().is_none());
+
+        // Reads stop at gaps.
+        store.put(&blocks(10..=11)).await.unwrap();
+        let read = store.blocks_from(7, 100).await.unwrap().unwrap();
+        assert_eq!(heights(&read), vec![7, 8]);
+    }
+
+    #[tokio::test]
+    async fn test_block_store_skips_blocks_outside_of_its_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BlockStoreConfig {
+            start_block: Some(3),
+            end_block: Some(5),
+            ..config(&dir, 100)
+        };
+        let mut store = BlockStore::open(&config, FUEL_NODE_ADDR).unwrap();
+
+        store.put(&blocks(1..=8)).await.unwrap();
+        assert!(store.blocks_from(2, 10).await.unwrap().is_none());
+        let read = store.blocks_from(3, 10).await.unwrap().unwrap();
+        assert_eq!(heights(&read), vec![3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_block_store_compacts_complete_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = BlockStore::open(&config(&dir, 10), FUEL_NODE_ADDR).unwrap();
+
+        store.put(&blocks(0..=3)).await.unwrap();
+        store.put(&blocks(4..=7)).await.unwrap();
+        assert_eq!(store.files.len(), 2);
+
+        // Pages never span segments, and storing the last block of a segment compacts it.
+        store.put(&blocks(8..=12)).await.unwrap();
+        assert_eq!(
+            file_names(&store),
+            vec![
+                format!("{:020}-{:020}.blocks", 0, 9),
+                format!("{:020}-{:020}.blocks", 10, 12),
+            ]
+        );
+
+        let read = store.blocks_from(0, 100).await.unwrap().unwrap();
+        assert_eq!(heights(&read), (0..=12).collect::<Vec<u64>>());
+
+        // Compacted files are found again once the store is reopened.
+        let mut store = BlockStore::open(&config(&dir, 10), FUEL_NODE_ADDR).unwrap();
+        assert_eq!(store.files.len(), 2);
+        let read = store.blocks_from(5, 100).await.unwrap().unwrap();
+        assert_eq!(heights(&read), (5..=12).collect::<Vec<u64>>());
+    }
+
+    #[tokio::test]
+    async fn test_block_store_open_removes_partial_and_covered_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = BlockStore::open(&config(&dir, 10), FUEL_NODE_ADDR).unwrap();
+        store.put(&blocks(0..=9)).await.unwrap();
+        store.put(&blocks(10..=12)).await.unwrap();
+
+        // An interrupted write, and a compaction interrupted after writing its output.
+        let write = |name: String, blocks: Vec<BlockData>| {
+            std::fs::write(
+                store.path.join(name),
+                serialize(&blocks.iter().collect::<Vec<&BlockData>>()),
+            )
+            .unwrap()
+        };
+        write(format!("{:020}-{:020}.tmp", 13, 14), blocks(13..=14));
+        write(format!("{:020}-{:020}.blocks", 0, 4), blocks(0..=4));
+        write(format!("{:020}-{:020}.blocks", 5, 9), blocks(5..=9));
+
+        let mut store = BlockStore::open(&config(&dir, 10), FUEL_NODE_ADDR).unwrap();
+        assert_eq!(
+            file_names(&store),
+            vec![
+                format!("{:020}-{:020}.blocks", 0, 9),
+                format!("{:020}-{:020}.blocks", 10, 12),
+            ]
+        );
+        let read = store.blocks_from(0, 100).await.unwrap().unwrap();
+        assert_eq!(heights(&read), (0..=12).collect::<Vec<u64>>());
+    }
+}
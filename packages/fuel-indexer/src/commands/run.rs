@@ -8,7 +8,7 @@ use fuel_indexer_lib::{
 };
 use tokio::signal::unix::{signal, Signal, SignalKind};
 use tokio::sync::mpsc::channel;
-use tracing::info;
+use tracing::{error, info};
 
 #[cfg(feature = "api-server")]
 use fuel_indexer_api_server::api::GraphQlApi;
@@ -84,7 +84,11 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
             info!(
                     "✨ ✨ GraphQL Playground at: http://localhost:29987/api/playground/{}/{}", m.namespace, m.identifier
                 );
-            service.register_indexer_from_manifest(m).await?;
+            // The service keeps running without the indexer, so that it can still be
+            // registered through the API.
+            if let Err(e) = service.register_indexer_from_manifest(m).await {
+                error!("Failed to register the indexer from its manifest: {e}");
+            }
         }
         None => {
             service.register_indexers_from_registry().await?;
@@ -1,20 +1,51 @@
-use crate::{IndexerResult, Manifest};
-use fuel_indexer_database::{queries, IndexerConnection, IndexerConnectionPool};
-use fuel_indexer_lib::{fully_qualified_namespace, graphql::types::IdCol};
-use fuel_indexer_schema::FtColumn;
-use std::collections::HashMap;
+use crate::{IndexerError, IndexerResult, Manifest};
+use fuel_indexer_database::{
+    queries,
+    types::{
+        history_table_name, IndexerBackfillPartition, IndexerCheckpoint, IndexerFailure,
+        IndexerLog,
+    },
+    DbType, IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_lib::{
+    defaults::INDEXER_LOG_CAPACITY,
+    fully_qualified_namespace,
+    graphql::{
+        types::{BucketCol, FromBlockCol, IdCol, IntervalCol, ObjectCol, ToBlockCol},
+        GraphQLSchema, ParsedGraphQLSchema, RollupFunction, Timeseries,
+    },
+    ExecutionSource,
+};
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
+use fuel_indexer_schema::{db::IndexerSchemaDbError, FtColumn};
+use fuel_indexer_types::{ffi::MetricUpdate, scalar::Bytes32};
+use sqlx::types::chrono::Utc;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, error, info};
 
 /// Database for an executor instance, with schema info.
 #[derive(Debug)]
 pub struct Database {
     pool: IndexerConnectionPool,
+    db_type: DbType,
     stashed: Option<IndexerConnection>,
+    in_transaction: bool,
+    partition: Option<(u64, u64)>,
+    metrics: Vec<MetricUpdate>,
+    block_height: Option<u64>,
+    logs: Vec<IndexerLog>,
+    /// First write that failed while handling the blocks of the open transaction.
+    write_error: Option<IndexerError>,
     pub namespace: String,
     pub identifier: String,
     pub version: String,
     pub schema: HashMap<String, Vec<String>>,
     pub tables: HashMap<i64, String>,
+    /// Tables whose every version of each object is kept in a history table.
+    pub historical: HashSet<String>,
+    /// Time-series rollups of each source table, along with the tables holding them.
+    pub rollups: HashMap<String, Vec<(String, Timeseries)>>,
 }
 
 // TODO: Use mutex
@@ -32,15 +63,24 @@ impl Database {
         manifest: &Manifest,
     ) -> IndexerResult<Database> {
         let mut db = Database {
+            db_type: pool.database_type(),
             pool,
             stashed: None,
+            in_transaction: false,
+            partition: None,
+            metrics: Vec::new(),
+            block_height: None,
+            logs: Vec::new(),
+            write_error: None,
             namespace: manifest.namespace.clone(),
             identifier: manifest.identifier.clone(),
             version: Default::default(),
             schema: Default::default(),
             tables: Default::default(),
+            historical: Default::default(),
+            rollups: Default::default(),
         };
-        db.load_schema().await?;
+        db.load_schema(manifest.execution_source()).await?;
         Ok(db)
     }
 
@@ -53,27 +93,185 @@ impl Database {
             "No stashed connection for start transaction. Was a transaction started?",
         );
         let result = queries::start_transaction(conn).await?;
+        self.in_transaction = true;
+        self.metrics.clear();
+        self.write_error = None;
         Ok(result)
     }
 
+    /// Restrict this database to backfilling the blocks from `start_block` to `end_block`,
+    /// so that checkpoints are recorded for that range rather than for the indexer.
+    pub fn set_backfill_partition(&mut self, start_block: u64, end_block: u64) {
+        self.partition = Some((start_block, end_block));
+    }
+
+    /// Whether a database transaction is currently open.
+    pub fn in_transaction(&self) -> bool {
+        self.in_transaction
+    }
+
     /// Commit transaction to database.
+    ///
+    /// The stashed connection is released whether or not the commit succeeds, so a
+    /// failed commit is rolled back and leaves no transaction open.
     pub async fn commit_transaction(&mut self) -> IndexerResult<usize> {
-        let conn = self
+        let mut conn = self
             .stashed
-            .as_mut()
+            .take()
             .expect("No stashed connection for commit. Was a transaction started?");
-        let res = queries::commit_transaction(conn).await?;
-        Ok(res)
+        self.in_transaction = false;
+
+        match queries::commit_transaction(&mut conn).await {
+            Ok(res) => {
+                self.apply_metrics();
+                self.flush_logs().await;
+                Ok(res)
+            }
+            Err(e) => {
+                if let Err(e) = queries::revert_transaction(&mut conn).await {
+                    error!("Failed to revert transaction after failed commit: {e:?}");
+                }
+                self.metrics.clear();
+                self.flush_logs().await;
+                Err(e.into())
+            }
+        }
     }
 
     /// Revert open transaction.
     pub async fn revert_transaction(&mut self) -> IndexerResult<usize> {
-        let conn = self
+        let mut conn = self
             .stashed
-            .as_mut()
+            .take()
             .expect("No stashed connection for revert. Was a transaction started?");
-        let res = queries::revert_transaction(conn).await?;
-        Ok(res)
+        self.in_transaction = false;
+        self.metrics.clear();
+        self.write_error = None;
+        let res = queries::revert_transaction(&mut conn).await;
+        self.flush_logs().await;
+        Ok(res?)
+    }
+
+    /// Take the error of the first write that failed while handling the blocks of the
+    /// open transaction, if any. Such blocks must not be committed.
+    pub fn take_write_error(&mut self) -> Option<IndexerError> {
+        self.write_error.take()
+    }
+
+    /// Set the height of the block being handled, with which logged lines are tagged.
+    pub fn set_block_height(&mut self, height: u64) {
+        self.block_height = Some(height);
+    }
+
+    /// Height of the block being handled, if known.
+    pub fn block_height(&self) -> Option<u64> {
+        self.block_height
+    }
+
+    /// Record the failure of this indexer to handle the block being handled.
+    ///
+    /// The failure is recorded outside of the open transaction, which is about to be
+    /// reverted.
+    pub async fn put_failure(&mut self, message: String) {
+        let failure = IndexerFailure {
+            message,
+            block_height: self.block_height,
+            created_at: Utc::now(),
+        };
+
+        let result = match self.pool.acquire().await {
+            Ok(mut conn) => {
+                queries::put_indexer_failure(
+                    &mut conn,
+                    &self.namespace,
+                    &self.identifier,
+                    &failure,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            error!(
+                "Failed to record failure of Indexer({}.{}): {e:?}",
+                self.namespace, self.identifier
+            );
+        }
+    }
+
+    /// Buffer a line logged by this indexer.
+    pub fn record_log(&mut self, level: &str, message: String) {
+        self.logs.push(IndexerLog {
+            id: 0,
+            level: level.to_string(),
+            block_height: self.block_height,
+            message,
+            created_at: Utc::now(),
+        });
+    }
+
+    /// Persist the buffered log lines.
+    ///
+    /// Unlike entities, logged lines are kept whether the transaction in which they
+    /// were logged is committed or reverted, as they're most useful when handling
+    /// a block fails. Executors flush them after every batch of blocks, rather than
+    /// waiting for a commit, so that they're available while commits are batched.
+    pub async fn flush_logs(&mut self) {
+        if self.logs.is_empty() {
+            return;
+        }
+
+        let logs = std::mem::take(&mut self.logs);
+        let result = match self.pool.acquire().await {
+            Ok(mut conn) => {
+                queries::put_logs(
+                    &mut conn,
+                    &self.namespace,
+                    &self.identifier,
+                    &logs,
+                    INDEXER_LOG_CAPACITY,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            error!(
+                "Failed to persist logs of Indexer({}.{}): {e:?}",
+                self.namespace, self.identifier
+            );
+        }
+    }
+
+    /// Buffer an update to one of this indexer's metrics.
+    ///
+    /// Updates are only applied once the open transaction commits, so that blocks
+    /// that are retried after a revert aren't counted twice.
+    pub fn record_metric(&mut self, update: MetricUpdate) {
+        self.metrics.push(update);
+    }
+
+    /// Apply the buffered metric updates.
+    fn apply_metrics(&mut self) {
+        #[cfg(feature = "metrics")]
+        for update in self.metrics.iter() {
+            let (namespace, identifier) = (&self.namespace, &self.identifier);
+            match update {
+                MetricUpdate::Counter { name, value } => METRICS
+                    .indexer
+                    .increment_counter(namespace, identifier, name, *value),
+                MetricUpdate::Gauge { name, value } => METRICS
+                    .indexer
+                    .set_gauge(namespace, identifier, name, *value),
+                MetricUpdate::Histogram { name, value } => METRICS
+                    .indexer
+                    .observe_histogram(namespace, identifier, name, *value),
+            }
+        }
+
+        self.metrics.clear();
     }
 
     /// Build an upsert query using a set of columns, insert values, update values, and a table name.
@@ -84,37 +282,224 @@ impl Database {
         inserts: Vec<String>,
         updates: Vec<String>,
     ) -> String {
-        if is_id_only_upsert(columns) {
+        let object = match self.db_type {
+            DbType::Postgres => "$1::bytea",
+            DbType::Sqlite => "?1",
+        };
+
+        if is_id_only_upsert(columns) || updates.is_empty() {
             format!(
                 "INSERT INTO {}
                     ({})
                  VALUES
-                    ({}, $1::bytea)
+                    ({}, {})
                  ON CONFLICT(id)
                  DO NOTHING",
                 table,
                 columns.join(", "),
                 inserts.join(", "),
+                object,
             )
         } else {
             format!(
                 "INSERT INTO {}
                     ({})
                  VALUES
-                    ({}, $1::bytea)
+                    ({}, {})
                  ON CONFLICT(id)
                  DO UPDATE SET {}",
                 table,
                 columns.join(", "),
                 inserts.join(", "),
+                object,
                 updates.join(", "),
             )
         }
     }
 
+    /// Build the queries that record a new version of an object in the history table of a
+    /// historical table: one that closes the current version of the object, and an upsert
+    /// of the version that starts at the block being handled.
+    ///
+    /// Without a block height the version can't be recorded, so the batch fails and is
+    /// handled again.
+    fn history_queries(
+        &mut self,
+        table: &str,
+        columns: &[String],
+        inserts: &[String],
+        updates: &[String],
+    ) -> Option<(String, String)> {
+        let Some(height) = self.block_height else {
+            let msg = format!(
+                "No block height at which to record a version of an object in {table}."
+            );
+            error!("{msg}");
+            self.write_error.get_or_insert(IndexerError::Unknown(msg));
+            return None;
+        };
+
+        let history = history_table_name(table);
+        let from_block = FromBlockCol::to_lowercase_str();
+        let to_block = ToBlockCol::to_lowercase_str();
+        let object = match self.db_type {
+            DbType::Postgres => "$1::bytea",
+            DbType::Sqlite => "?1",
+        };
+
+        // A version written earlier while handling the same block is replaced by the
+        // upsert rather than closed.
+        let close = format!(
+            "UPDATE {history}
+             SET {to_block} = {height}
+             WHERE id = {} AND {to_block} IS NULL AND {from_block} < {height}",
+            inserts[0],
+        );
+
+        let upsert = format!(
+            "INSERT INTO {history}
+                ({}, {from_block})
+             VALUES
+                ({}, {object}, {height})
+             ON CONFLICT(id, {from_block})
+             DO UPDATE SET {}, object = EXCLUDED.object",
+            columns.join(", "),
+            inserts.join(", "),
+            updates.join(", "),
+        );
+
+        Some((close, upsert))
+    }
+
+    /// Build the upserts that roll up an object of a source table into the buckets of
+    /// its time-series tables, one for each interval of each time series.
+    fn rollup_queries(&self, table: &str, values: &[FtColumn]) -> Vec<String> {
+        let Some(rollups) = self.rollups.get(table) else {
+            return Vec::new();
+        };
+
+        let value = |field: &str| {
+            self.schema[table]
+                .iter()
+                .position(|column| column == field)
+                .and_then(|i| values.get(i))
+        };
+        let fragment = |field: &str| {
+            value(field)
+                .map(|v| self.query_fragment(v))
+                .unwrap_or_else(|| "NULL".to_string())
+        };
+
+        let mut queries = Vec::new();
+        for (rollup_table, timeseries) in rollups {
+            let Some(time) = value(&timeseries.time_field).and_then(|v| v.as_i64())
+            else {
+                error!("No time at which to roll up an object of {table} into {rollup_table}.");
+                continue;
+            };
+
+            let dimensions: Vec<_> = timeseries
+                .dimensions
+                .iter()
+                .map(|field| fragment(field))
+                .collect();
+
+            for interval in timeseries.intervals.iter() {
+                let Some(bucket) = Timeseries::bucket(interval, time) else {
+                    continue;
+                };
+                let id = timeseries.bucket_id(interval, bucket, &dimensions);
+
+                let mut columns = vec![
+                    IdCol::to_lowercase_string(),
+                    IntervalCol::to_lowercase_string(),
+                    BucketCol::to_lowercase_string(),
+                ];
+                let mut inserts = vec![
+                    self.query_fragment(&FtColumn::ID(Some(id))),
+                    self.query_fragment(&FtColumn::Charfield(Some(interval.clone()))),
+                    bucket.to_string(),
+                ];
+                let mut updates = Vec::new();
+
+                columns.extend(timeseries.dimensions.iter().cloned());
+                inserts.extend(dimensions.iter().cloned());
+
+                for rollup in timeseries.rollups.iter() {
+                    let field = &rollup.field;
+                    let insert = match &rollup.source_field {
+                        Some(source_field) => fragment(source_field),
+                        None => "1".to_string(),
+                    };
+
+                    // Values of large integers are stored as text in SQLite, so they're
+                    // compared as numbers explicitly.
+                    let (current, new) = match self.db_type {
+                        DbType::Postgres => (field.clone(), format!("EXCLUDED.{field}")),
+                        DbType::Sqlite => (
+                            format!("CAST({field} AS NUMERIC)"),
+                            format!("CAST(EXCLUDED.{field} AS NUMERIC)"),
+                        ),
+                    };
+                    let update = match (rollup.function, &self.db_type) {
+                        (RollupFunction::Sum | RollupFunction::Count, _) => {
+                            Some(format!(
+                                "COALESCE({field}, 0) + COALESCE(EXCLUDED.{field}, 0)"
+                            ))
+                        }
+                        (RollupFunction::First, _) => None,
+                        (RollupFunction::Last, _) => Some(format!("EXCLUDED.{field}")),
+                        (RollupFunction::Min, DbType::Postgres) => {
+                            Some(format!("LEAST({current}, {new})"))
+                        }
+                        (RollupFunction::Max, DbType::Postgres) => {
+                            Some(format!("GREATEST({current}, {new})"))
+                        }
+                        // SQLite's scalar `min` and `max` return NULL if any value is NULL.
+                        (RollupFunction::Min, DbType::Sqlite) => Some(format!(
+                            "COALESCE(min({current}, {new}), {current}, {new})"
+                        )),
+                        (RollupFunction::Max, DbType::Sqlite) => Some(format!(
+                            "COALESCE(max({current}, {new}), {current}, {new})"
+                        )),
+                    };
+
+                    columns.push(field.clone());
+                    inserts.push(insert);
+                    if let Some(update) = update {
+                        updates.push(format!("{field} = {update}"));
+                    }
+                }
+
+                // Time-series objects are never loaded by handlers, so they're stored empty.
+                columns.push(ObjectCol::to_lowercase_string());
+
+                queries.push(self.upsert_query(rollup_table, &columns, inserts, updates));
+            }
+        }
+
+        queries
+    }
+
     /// Return a query to get an object from the database.
     fn get_query(&self, table: &str, object_id: u64) -> String {
-        format!("SELECT object from {table} where id = {object_id}")
+        match self.db_type {
+            DbType::Postgres => {
+                format!("SELECT object from {table} where id = {object_id}")
+            }
+            // IDs are stored as text in SQLite.
+            DbType::Sqlite => {
+                format!("SELECT object from {table} where id = '{object_id}'")
+            }
+        }
+    }
+
+    /// Return the query fragment used to persist the given column.
+    fn query_fragment(&self, column: &FtColumn) -> String {
+        match self.db_type {
+            DbType::Postgres => column.query_fragment(),
+            DbType::Sqlite => column.sqlite_query_fragment(),
+        }
     }
 
     /// Put an object into the database.
@@ -125,7 +510,7 @@ impl Database {
         bytes: Vec<u8>,
     ) {
         let table = match self.tables.get(&type_id) {
-            Some(t) => t,
+            Some(t) => t.clone(),
             None => {
                 error!(
                     r#"TypeId({}) not found in tables: {:?}. 
@@ -140,16 +525,41 @@ Do your WASM modules need to be rebuilt?
                 return;
             }
         };
+        let table = table.as_str();
+
+        // Time-series tables are only written by rolling up their source tables.
+        if self
+            .rollups
+            .values()
+            .flatten()
+            .any(|(rollup_table, _)| rollup_table == table)
+        {
+            error!("Objects of time series {table} can't be put by handlers.");
+            return;
+        }
 
-        let inserts: Vec<_> = columns.iter().map(|col| col.query_fragment()).collect();
+        let inserts: Vec<_> =
+            columns.iter().map(|col| self.query_fragment(col)).collect();
         let updates: Vec<_> = self.schema[table]
             .iter()
             .zip(columns.iter())
-            .map(|(colname, value)| format!("{} = {}", colname, value.query_fragment()))
+            .map(|(colname, value)| {
+                format!("{} = {}", colname, self.query_fragment(value))
+            })
             .collect();
 
+        let rollup_queries = self.rollup_queries(table, &columns);
+        let exists_query = (!rollup_queries.is_empty())
+            .then(|| format!("SELECT object FROM {table} WHERE id = {}", inserts[0]));
+
         let columns = self.schema[table].clone();
 
+        let history_queries = if self.historical.contains(table) {
+            self.history_queries(table, &columns, &inserts, &updates)
+        } else {
+            None
+        };
+
         let query_text = self.upsert_query(table, &columns, inserts, updates);
 
         let conn = self
@@ -157,8 +567,58 @@ Do your WASM modules need to be rebuilt?
             .as_mut()
             .expect("No stashed connection for put. Was a transaction started?");
 
-        if let Err(e) = queries::put_object(conn, query_text, bytes).await {
+        // Objects are only rolled up the first time they're saved, so that saving an
+        // object again doesn't count it twice. Rollups can't take back what an object
+        // contributed, so sources of time series can't be changed once saved.
+        let is_new = match exists_query {
+            Some(query) => match queries::get_object(conn, query).await {
+                Ok(object) if object == bytes => false,
+                Ok(_) => {
+                    let msg = format!(
+                        "Object of {table} has already been rolled up into its time series, and can't be changed."
+                    );
+                    error!("{msg}");
+                    self.write_error.get_or_insert(IndexerError::Unknown(msg));
+                    return;
+                }
+                Err(sqlx::Error::RowNotFound) => true,
+                Err(e) => {
+                    error!("Failed to look up object to roll up: {:?}", e);
+                    self.write_error.get_or_insert(e.into());
+                    return;
+                }
+            },
+            None => false,
+        };
+
+        if let Err(e) = queries::put_object(conn, query_text, bytes.clone()).await {
             error!("Failed to put object: {:?}", e);
+            self.write_error.get_or_insert(e.into());
+            return;
+        }
+
+        if let Some((close, upsert)) = history_queries {
+            if let Err(e) = queries::execute_query(conn, close).await {
+                error!("Failed to close object version: {:?}", e);
+                self.write_error.get_or_insert(e.into());
+                return;
+            }
+
+            if let Err(e) = queries::put_object(conn, upsert, bytes).await {
+                error!("Failed to put object version: {:?}", e);
+                self.write_error.get_or_insert(e.into());
+                return;
+            }
+        }
+
+        if is_new {
+            for query in rollup_queries {
+                if let Err(e) = queries::put_object(conn, query, Vec::new()).await {
+                    error!("Failed to roll up object: {:?}", e);
+                    self.write_error.get_or_insert(e.into());
+                    return;
+                }
+            }
         }
     }
 
@@ -184,9 +644,113 @@ Do your WASM modules need to be rebuilt?
         }
     }
 
+    /// Get the value stored under `key` in this indexer's key/value store, if any.
+    ///
+    /// A failed read can't be told apart from a missing value by the handler, so it
+    /// fails the batch like a failed write.
+    pub async fn get_kv(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        let conn = self
+            .stashed
+            .as_mut()
+            .expect("No stashed connection for get. Was a transaction started?");
+
+        match queries::get_kv(conn, &self.namespace, &self.identifier, key).await {
+            Ok(value) => value,
+            Err(e) => {
+                error!("Failed to get value from key/value store: {:?}", e);
+                self.write_error.get_or_insert(e.into());
+                None
+            }
+        }
+    }
+
+    /// Store `value` under `key` in this indexer's key/value store, replacing any
+    /// existing value.
+    ///
+    /// Like entities, the value is written in the open transaction, and a failed write
+    /// fails the batch.
+    pub async fn put_kv(&mut self, key: &[u8], value: &[u8]) {
+        let conn = self
+            .stashed
+            .as_mut()
+            .expect("No stashed connection for put. Was a transaction started?");
+
+        if let Err(e) =
+            queries::put_kv(conn, &self.namespace, &self.identifier, key, value).await
+        {
+            error!("Failed to put value into key/value store: {:?}", e);
+            self.write_error.get_or_insert(e.into());
+        }
+    }
+
+    /// Remove the value stored under `key` in this indexer's key/value store, if any.
+    pub async fn delete_kv(&mut self, key: &[u8]) {
+        let conn = self
+            .stashed
+            .as_mut()
+            .expect("No stashed connection for delete. Was a transaction started?");
+
+        if let Err(e) =
+            queries::delete_kv(conn, &self.namespace, &self.identifier, key).await
+        {
+            error!("Failed to delete value from key/value store: {:?}", e);
+            self.write_error.get_or_insert(e.into());
+        }
+    }
+
+    /// Record the block with the given height and ID as the last block handled by this indexer.
+    ///
+    /// The checkpoint is written in the open transaction, so it is committed (or
+    /// reverted) together with the entities written while handling the block. While
+    /// backfilling a partition, the checkpoint is recorded for that partition instead.
+    pub async fn put_checkpoint(
+        &mut self,
+        block_height: u64,
+        block_id: &Bytes32,
+    ) -> IndexerResult<()> {
+        let conn = self
+            .stashed
+            .as_mut()
+            .expect("No stashed connection for checkpoint. Was a transaction started?");
+
+        match self.partition {
+            Some((start_block, end_block)) => {
+                let partition = IndexerBackfillPartition {
+                    start_block,
+                    end_block,
+                    block_height: Some(block_height),
+                    block_id: Some(format!("{block_id:x}")),
+                };
+                queries::put_backfill_partition(
+                    conn,
+                    &self.namespace,
+                    &self.identifier,
+                    &partition,
+                )
+                .await?;
+            }
+            None => {
+                let checkpoint = IndexerCheckpoint {
+                    block_height,
+                    block_id: format!("{block_id:x}"),
+                    cursor: Some(block_height.to_string()),
+                };
+                queries::put_checkpoint(
+                    conn,
+                    &self.namespace,
+                    &self.identifier,
+                    &checkpoint,
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Load the schema for this indexer from the database, and build a mapping of `TypeId`s to
     /// tables.
-    async fn load_schema(&mut self) -> IndexerResult<()> {
+    async fn load_schema(&mut self, exec_source: ExecutionSource) -> IndexerResult<()> {
         let mut conn = self.pool.acquire().await?;
         self.version =
             queries::type_id_latest(&mut conn, &self.namespace, &self.identifier).await?;
@@ -196,7 +760,6 @@ Do your WASM modules need to be rebuilt?
             self.namespace, self.identifier, self.version
         );
 
-        let mut conn = self.pool.acquire().await?;
         let columns = queries::columns_get_schema(
             &mut conn,
             &self.namespace,
@@ -206,10 +769,9 @@ Do your WASM modules need to be rebuilt?
         .await?;
 
         for column in columns {
-            let table = &format!(
-                "{}.{}",
-                fully_qualified_namespace(&self.namespace, &self.identifier),
-                &column.table_name
+            let table = &self.db_type.table_name(
+                &fully_qualified_namespace(&self.namespace, &self.identifier),
+                &column.table_name,
             );
 
             self.tables
@@ -224,6 +786,42 @@ Do your WASM modules need to be rebuilt?
             columns.push(column.column_name);
         }
 
+        // Which types are historical is only known from the GraphQL schema itself.
+        let root =
+            queries::graph_root_latest(&mut conn, &self.namespace, &self.identifier)
+                .await?;
+        let parsed = ParsedGraphQLSchema::new(
+            &self.namespace,
+            &self.identifier,
+            exec_source,
+            Some(&GraphQLSchema::new(root.schema)),
+        )
+        .map_err(IndexerSchemaDbError::from)?;
+
+        self.historical = parsed
+            .non_enum_typdefs()
+            .iter()
+            .filter(|(name, _)| parsed.is_historical_typedef(name))
+            .map(|(name, _)| {
+                self.db_type.table_name(
+                    &fully_qualified_namespace(&self.namespace, &self.identifier),
+                    &name.to_lowercase(),
+                )
+            })
+            .collect();
+
+        let namespace = fully_qualified_namespace(&self.namespace, &self.identifier);
+        let mut rollups: HashMap<String, Vec<(String, Timeseries)>> = HashMap::new();
+        for timeseries in parsed.timeseries().values() {
+            let table =
+                |name: &str| self.db_type.table_name(&namespace, &name.to_lowercase());
+            rollups
+                .entry(table(&timeseries.source))
+                .or_default()
+                .push((table(&timeseries.name), timeseries.clone()));
+        }
+        self.rollups = rollups;
+
         Ok(())
     }
 }
@@ -1,6 +1,7 @@
 use crate::{
-    database::Database, ffi, queries::ClientExt, IndexerConfig, IndexerError,
-    IndexerResult,
+    database::Database,
+    fetcher::{BlockFetcher, BlockPage},
+    ffi, IndexerConfig, IndexerError, IndexerResult, SubscriptionFilter,
 };
 use async_std::{
     fs::File,
@@ -8,32 +9,26 @@ use async_std::{
     sync::{Arc, Mutex},
 };
 use async_trait::async_trait;
-use fuel_core_client::client::{
-    schema::block::{Consensus as ClientConsensus, Genesis as ClientGenesis},
-    types::TransactionStatus as ClientTransactionStatus,
-    FuelClient, PageDirection, PaginatedResult, PaginationRequest,
+use fuel_indexer_database::{
+    queries,
+    types::{IndexerBackfillPartition, IndexerCheckpoint},
+    IndexerConnectionPool,
 };
-use fuel_indexer_database::IndexerConnectionPool;
 use fuel_indexer_lib::{defaults::*, manifest::Manifest, utils::serialize};
 use fuel_indexer_types::{
-    fuel::{field::*, *},
-    scalar::{Bytes32, HexString},
+    ffi::{WasmPanic, FFI_VERSION, MIN_SUPPORTED_FFI_VERSION},
+    fuel::BlockData,
 };
-use fuel_tx::UniqueIdentifier;
-use fuel_vm::prelude::Deserializable;
-use fuel_vm::state::ProgramState as ClientProgramState;
 use futures::Future;
-use itertools::Itertools;
 use std::{
     marker::{Send, Sync},
     path::Path,
-    str::FromStr,
     sync::atomic::{AtomicBool, Ordering},
 };
 use thiserror::Error;
 use tokio::{
     task::{spawn_blocking, JoinHandle},
-    time::{sleep, timeout, Duration},
+    time::{sleep, timeout, Duration, Instant},
 };
 use tracing::{debug, error, info, warn};
 use wasmer::{
@@ -65,15 +60,28 @@ impl ExecutorSource {
     }
 }
 
+/// Return the address of the Fuel node from which the given indexer fetches blocks.
+pub(crate) fn fuel_node_addr(config: &IndexerConfig, manifest: &Manifest) -> String {
+    if config.indexer_net_config {
+        manifest
+            .fuel_client
+            .clone()
+            .unwrap_or(config.fuel_node.to_string())
+    } else {
+        config.fuel_node.to_string()
+    }
+}
+
 // Run the executor task until the kill switch is flipped, or until some other
 // stop criteria is met.
 //
-// In general the logic in this function isn't very idiomatic, but that's because
-// types in `fuel_core_client` don't compile to WASM.
+// Blocks are fetched through the given fetcher, which is shared with every other
+// indexer that fetches blocks from the same Fuel node.
 pub fn run_executor<T: 'static + Executor + Send + Sync>(
     config: &IndexerConfig,
     manifest: &Manifest,
     mut executor: T,
+    fetcher: Arc<BlockFetcher>,
     kill_switch: Arc<AtomicBool>,
 ) -> impl Future<Output = ()> {
     // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
@@ -84,15 +92,7 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
         warn!("No end_block specified in manifest. Indexer will run forever.");
     }
     let stop_idle_indexers = config.stop_idle_indexers;
-
-    let fuel_node_addr = if config.indexer_net_config {
-        manifest
-            .fuel_client
-            .clone()
-            .unwrap_or(config.fuel_node.to_string())
-    } else {
-        config.fuel_node.to_string()
-    };
+    let commit_policy = manifest.commit_policy.unwrap_or(config.commit_policy);
 
     let mut next_cursor = if start_block > 1 {
         let decremented = start_block - 1;
@@ -103,15 +103,24 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
 
     let indexer_uid = manifest.uid();
 
-    info!("Indexer({indexer_uid}) subscribing to Fuel node at {fuel_node_addr}");
-
-    let client = FuelClient::from_str(&fuel_node_addr).unwrap_or_else(|e| {
-        panic!("Indexer({indexer_uid}) client node connection failed: {e}.")
-    });
+    info!(
+        "Indexer({indexer_uid}) subscribing to Fuel node at {}",
+        fetcher.fuel_node_addr()
+    );
 
     async move {
         let mut retry_count = 0;
 
+        // Blocks handled in the open database transaction are only durable once it is
+        // committed, so a failure rewinds the cursor to the last committed block.
+        let mut committed_cursor = next_cursor.clone();
+        let mut uncommitted_blocks = 0;
+        let mut last_commit = Instant::now();
+
+        // After a constraint violation, uncommitted blocks are replayed with a commit
+        // after every batch, so that only the offending batch is skipped.
+        let mut replay_until = None;
+
         // If we're testing or running on CI, we don't want indexers to run forever. But in production
         // let the index operators decide if they want to stop idle indexers. Maybe we can eventually
         // make this MAX_EMPTY_BLOCK_REQUESTS value configurable
@@ -127,243 +136,68 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 "Indexer({indexer_uid}) fetching paginated results from {next_cursor:?}"
             );
 
-            let PaginatedResult {
-                cursor, results, ..
-            } = client
-                .full_blocks(PaginationRequest {
-                    cursor: next_cursor.clone(),
-                    results: NODE_GRAPHQL_PAGE_SIZE,
-                    direction: PageDirection::Forward,
-                })
+            let BlockPage {
+                mut blocks,
+                cursor,
+                has_next_page,
+            } = fetcher
+                .blocks_after(next_cursor.clone(), NODE_GRAPHQL_PAGE_SIZE)
                 .await
                 .unwrap_or_else(|e| {
-                    error!("Indexer({indexer_uid}) ailed to retrieve blocks: {e}");
-                    PaginatedResult {
-                        cursor: None,
-                        results: vec![],
-                        has_next_page: false,
-                        has_previous_page: false,
-                    }
+                    error!("Indexer({indexer_uid}) failed to retrieve blocks: {e}");
+                    BlockPage::default()
                 });
 
-            let mut block_info = Vec::new();
-            for block in results.into_iter() {
-                if let Some(end_block) = end_block {
-                    if block.header.height.0 > end_block {
-                        info!("Stopping Indexer({indexer_uid}) at the specified end_block: {end_block}");
-                        break;
+            let reached_end_block = truncate_at_end_block(&mut blocks, end_block);
+
+            // Once the indexer has caught up with the chain, there is no next page.
+            let at_tip = !has_next_page;
+
+            let mut failure = None;
+            let mut blocks = blocks.into_iter().peekable();
+            while blocks.peek().is_some() {
+                let batch = blocks
+                    .by_ref()
+                    .take(commit_policy.remaining_blocks(uncommitted_blocks, at_tip))
+                    .collect::<Vec<BlockData>>();
+                let batch_end =
+                    batch.last().map(|block| block.height).unwrap_or_default();
+                let uncommitted_before = uncommitted_blocks;
+                uncommitted_blocks += batch.len() as u64;
+
+                let mut result = executor.handle_events(batch).await;
+
+                let is_replaying = replay_until.map_or(false, |end| batch_end <= end);
+                if result.is_ok()
+                    && (is_replaying
+                        || commit_policy.should_commit(
+                            uncommitted_blocks,
+                            last_commit.elapsed(),
+                            at_tip,
+                        ))
+                {
+                    result = executor.commit().await;
+                    if result.is_ok() {
+                        committed_cursor = Some(batch_end.to_string());
+                        uncommitted_blocks = 0;
+                        last_commit = Instant::now();
                     }
                 }
 
-                let producer = block
-                    .block_producer()
-                    .map(|pk| Bytes32::from(<[u8; 32]>::try_from(pk.hash()).unwrap()));
-
-                let mut transactions = Vec::new();
-
-                for trans in block.transactions {
-                    let receipts = trans
-                        .receipts
-                        .unwrap_or_default()
-                        .into_iter()
-                        .map(TryInto::try_into)
-                        .try_collect()
-                        .expect("Bad receipts.");
-
-                    let status = trans.status.expect("Bad transaction status.");
-                    // NOTE: https://github.com/FuelLabs/fuel-indexer/issues/286
-                    let status = match status.try_into().unwrap() {
-                        ClientTransactionStatus::Success {
-                            block_id,
-                            time,
-                            program_state,
-                        } => {
-                            let program_state = program_state.map(|p| match p {
-                                ClientProgramState::Return(w) => ProgramState {
-                                    return_type: ReturnType::Return,
-                                    data: HexString::from(w.to_le_bytes().to_vec()),
-                                },
-                                ClientProgramState::ReturnData(d) => ProgramState {
-                                    return_type: ReturnType::ReturnData,
-                                    data: HexString::from(d.to_vec()),
-                                },
-                                ClientProgramState::Revert(w) => ProgramState {
-                                    return_type: ReturnType::Revert,
-                                    data: HexString::from(w.to_le_bytes().to_vec()),
-                                },
-                                // Either `cargo watch` complains that this is unreachable, or `clippy` complains
-                                // that all patterns are not matched. These other program states are only used in
-                                // debug modes.
-                                #[allow(unreachable_patterns)]
-                                _ => unreachable!("Bad program state."),
-                            });
-                            TransactionStatus::Success {
-                                block: block_id.parse().expect("Bad block height."),
-                                time: time.to_unix() as u64,
-                                program_state,
-                            }
-                        }
-                        ClientTransactionStatus::Failure {
-                            block_id,
-                            time,
-                            reason,
-                            program_state,
-                        } => {
-                            let program_state = program_state.map(|p| match p {
-                                ClientProgramState::Return(w) => ProgramState {
-                                    return_type: ReturnType::Return,
-                                    data: HexString::from(w.to_le_bytes().to_vec()),
-                                },
-                                ClientProgramState::ReturnData(d) => ProgramState {
-                                    return_type: ReturnType::ReturnData,
-                                    data: HexString::from(d.to_vec()),
-                                },
-                                ClientProgramState::Revert(w) => ProgramState {
-                                    return_type: ReturnType::Revert,
-                                    data: HexString::from(w.to_le_bytes().to_vec()),
-                                },
-                                // Either `cargo watch` complains that this is unreachable, or `clippy` complains
-                                // that all patterns are not matched. These other program states are only used in
-                                // debug modes.
-                                #[allow(unreachable_patterns)]
-                                _ => unreachable!("Bad program state."),
-                            });
-                            TransactionStatus::Failure {
-                                block: block_id.parse().expect("Bad block ID."),
-                                time: time.to_unix() as u64,
-                                program_state,
-                                reason,
-                            }
-                        }
-                        ClientTransactionStatus::Submitted { submitted_at } => {
-                            TransactionStatus::Submitted {
-                                submitted_at: submitted_at.to_unix() as u64,
-                            }
-                        }
-                        ClientTransactionStatus::SqueezedOut { reason } => {
-                            TransactionStatus::SqueezedOut { reason }
-                        }
-                    };
-
-                    let transaction = fuel_tx::Transaction::from_bytes(
-                        trans.raw_payload.0 .0.as_slice(),
-                    )
-                    .expect("Bad transaction.");
-
-                    let id = transaction.id();
-
-                    let transaction = match transaction {
-                        ClientTransaction::Create(tx) => Transaction::Create(Create {
-                            gas_price: *tx.gas_price(),
-                            gas_limit: *tx.gas_limit(),
-                            maturity: *tx.maturity(),
-                            bytecode_length: *tx.bytecode_length(),
-                            bytecode_witness_index: *tx.bytecode_witness_index(),
-                            storage_slots: tx
-                                .storage_slots()
-                                .iter()
-                                .map(|x| StorageSlot {
-                                    key: <[u8; 32]>::from(*x.key()).into(),
-                                    value: <[u8; 32]>::from(*x.value()).into(),
-                                })
-                                .collect(),
-                            inputs: tx
-                                .inputs()
-                                .iter()
-                                .map(|i| i.to_owned().into())
-                                .collect(),
-                            outputs: tx
-                                .outputs()
-                                .iter()
-                                .map(|o| o.to_owned().into())
-                                .collect(),
-                            witnesses: tx.witnesses().to_vec(),
-                            salt: <[u8; 32]>::from(*tx.salt()).into(),
-                            metadata: None,
-                        }),
-                        _ => Transaction::default(),
-                    };
-
-                    let tx_data = TransactionData {
-                        receipts,
-                        status,
-                        transaction,
-                        id,
-                    };
-
-                    transactions.push(tx_data);
+                if let Err(e) = result {
+                    failure = Some((e, uncommitted_before, batch_end));
+                    break;
                 }
-
-                // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
-                let consensus = match &block.consensus {
-                    ClientConsensus::Unknown => Consensus::Unknown,
-                    ClientConsensus::Genesis(g) => {
-                        let ClientGenesis {
-                            chain_config_hash,
-                            coins_root,
-                            contracts_root,
-                            messages_root,
-                        } = g.to_owned();
-
-                        Consensus::Genesis(Genesis {
-                            chain_config_hash: <[u8; 32]>::from(
-                                chain_config_hash.to_owned().0 .0,
-                            )
-                            .into(),
-                            coins_root: <[u8; 32]>::from(coins_root.0 .0.to_owned())
-                                .into(),
-                            contracts_root: <[u8; 32]>::from(
-                                contracts_root.0 .0.to_owned(),
-                            )
-                            .into(),
-                            messages_root: <[u8; 32]>::from(
-                                messages_root.0 .0.to_owned(),
-                            )
-                            .into(),
-                        })
-                    }
-                    ClientConsensus::PoAConsensus(poa) => Consensus::PoA(PoA {
-                        signature: <[u8; 64]>::from(poa.signature.0 .0.to_owned()).into(),
-                    }),
-                };
-
-                // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
-                let block = BlockData {
-                    height: block.header.height.clone().into(),
-                    id: Bytes32::from(<[u8; 32]>::from(block.id.0 .0)),
-                    producer,
-                    time: block.header.time.0.to_unix(),
-                    consensus,
-                    header: Header {
-                        id: Bytes32::from(<[u8; 32]>::from(block.header.id.0 .0)),
-                        da_height: block.header.da_height.0,
-                        transactions_count: block.header.transactions_count.0,
-                        output_messages_count: block.header.output_messages_count.0,
-                        transactions_root: Bytes32::from(<[u8; 32]>::from(
-                            block.header.transactions_root.0 .0,
-                        )),
-                        output_messages_root: Bytes32::from(<[u8; 32]>::from(
-                            block.header.output_messages_root.0 .0,
-                        )),
-                        height: block.header.height.0,
-                        prev_root: Bytes32::from(<[u8; 32]>::from(
-                            block.header.prev_root.0 .0,
-                        )),
-                        time: block.header.time.0.to_unix(),
-                        application_hash: Bytes32::from(<[u8; 32]>::from(
-                            block.header.application_hash.0 .0,
-                        )),
-                    },
-                    transactions,
-                };
-
-                block_info.push(block);
             }
 
-            let result = executor.handle_events(block_info).await;
-
-            if let Err(e) = result {
+            if let Some((e, uncommitted_before, batch_end)) = failure {
                 error!("Indexer executor failed {e:?}, retrying.");
+
+                // Everything handled since the last commit was reverted.
+                uncommitted_blocks = 0;
+                last_commit = Instant::now();
+                next_cursor = committed_cursor.clone();
+
                 match e {
                     IndexerError::SqlxError(sqlx::Error::Database(inner)) => {
                         // sqlx v0.7 let's you determine if this was specifically a unique constraint violation
@@ -371,9 +205,15 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                         //
                         // TODO: https://github.com/FuelLabs/fuel-indexer/issues/1093
                         if inner.constraint().is_some() {
-                            // Just bump the cursor and keep going
-                            warn!("Constraint violation. Continuing...");
-                            next_cursor = cursor;
+                            if uncommitted_before == 0 {
+                                // Just bump the cursor past the offending batch and keep going
+                                warn!("Constraint violation. Continuing...");
+                                next_cursor = Some(batch_end.to_string());
+                                committed_cursor = next_cursor.clone();
+                            } else {
+                                warn!("Constraint violation. Replaying uncommitted blocks...");
+                                replay_until = Some(batch_end);
+                            }
                             continue;
                         } else {
                             error!("Database error: {inner}.");
@@ -397,7 +237,29 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 }
             }
 
+            if let Some(end_block) = reached_end_block {
+                info!("Stopping Indexer({indexer_uid}) at the specified end_block: {end_block}");
+                break;
+            }
+
             if cursor.is_none() {
+                // Nothing new to handle, so blocks handled so far should not wait any longer.
+                if commit_policy.should_commit(
+                    uncommitted_blocks,
+                    last_commit.elapsed(),
+                    true,
+                ) {
+                    match executor.commit().await {
+                        Ok(()) => committed_cursor = next_cursor.clone(),
+                        Err(e) => {
+                            error!("Indexer({indexer_uid}) failed to commit: {e:?}.");
+                            next_cursor = committed_cursor.clone();
+                        }
+                    }
+                    uncommitted_blocks = 0;
+                    last_commit = Instant::now();
+                }
+
                 info!("No new blocks to process, sleeping.");
                 sleep(Duration::from_secs(DELAY_FOR_EMPTY_PAGE)).await;
 
@@ -419,7 +281,243 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
 
             retry_count = 0;
         }
+
+        // Persist whatever was handled before the indexer stopped.
+        if let Err(e) = executor.commit().await {
+            error!("Indexer({indexer_uid}) failed to commit before stopping: {e:?}.");
+        }
+    }
+}
+
+/// Drop the blocks past `end_block`, returning `end_block` if the blocks reach it.
+///
+/// `end_block` is inclusive: the block at `end_block` is handled, after which the
+/// indexer stops. This applies to every indexer with an `end_block`, not just to
+/// backfill partitions. Indexers used to skip the blocks past `end_block` but keep
+/// polling the node; they now stop as soon as a page reaches `end_block`.
+fn truncate_at_end_block(
+    blocks: &mut Vec<BlockData>,
+    end_block: Option<u64>,
+) -> Option<u64> {
+    let end_block = end_block?;
+    if !blocks.iter().any(|block| block.height >= end_block) {
+        return None;
+    }
+
+    blocks.retain(|block| block.height <= end_block);
+    Some(end_block)
+}
+
+/// Split the blocks from `start_block` to `end_block` into at most `partitions` ranges
+/// of at least `MIN_BACKFILL_PARTITION_SIZE` blocks each.
+fn plan_backfill_partitions(
+    start_block: u64,
+    end_block: u64,
+    partitions: usize,
+) -> Vec<IndexerBackfillPartition> {
+    let num_blocks = (end_block + 1).saturating_sub(start_block);
+    let partitions =
+        std::cmp::min(partitions as u64, num_blocks / MIN_BACKFILL_PARTITION_SIZE);
+    if partitions < 2 {
+        return vec![];
+    }
+
+    let size = num_blocks / partitions;
+    (0..partitions)
+        .map(|i| {
+            let start = start_block + i * size;
+            let end = if i == partitions - 1 {
+                end_block
+            } else {
+                start + size - 1
+            };
+            IndexerBackfillPartition {
+                start_block: start,
+                end_block: end,
+                block_height: None,
+                block_id: None,
+            }
+        })
+        .collect()
+}
+
+// Run a partitionable indexer until the kill switch is flipped, or until some other
+// stop criteria is met.
+//
+// Blocks up to the tip of the chain are first backfilled by several executors running
+// concurrently, each over its own range of blocks and in its own database transaction.
+// Once every range is backfilled, the ranges' checkpoints are merged into the indexer's
+// checkpoint, and the given executor follows the chain from there.
+async fn run_partitioned_executor(
+    config: IndexerConfig,
+    manifest: Manifest,
+    executor: WasmIndexExecutor,
+    wasm_bytes: Vec<u8>,
+    pool: IndexerConnectionPool,
+    fetcher: Arc<BlockFetcher>,
+    kill_switch: Arc<AtomicBool>,
+) {
+    let indexer_uid = manifest.uid();
+
+    let mut manifest = manifest;
+    match backfill_partitions(
+        &config,
+        &manifest,
+        &wasm_bytes,
+        pool,
+        fetcher.clone(),
+        kill_switch.clone(),
+    )
+    .await
+    {
+        Ok(Backfill::Completed(follow_from)) => {
+            info!("Indexer({indexer_uid}) backfilled, following the chain from block {follow_from}.");
+            manifest.start_block = Some(follow_from);
+        }
+        Ok(Backfill::Skipped) => {}
+        Ok(Backfill::Stopped) => {
+            info!("Backfill of Indexer({indexer_uid}) stopped before completing.");
+            return;
+        }
+        Err(e) => {
+            error!("Indexer({indexer_uid}) failed to backfill: {e:?}. <('.')>");
+            return;
+        }
+    }
+
+    run_executor(&config, &manifest, executor, fetcher, kill_switch).await
+}
+
+/// Outcome of backfilling a partitionable indexer.
+enum Backfill {
+    /// Every partition was backfilled, and the chain is followed from the given block.
+    Completed(u64),
+
+    /// There were too few blocks to split between several executors.
+    Skipped,
+
+    /// The kill switch was flipped before every partition was backfilled.
+    Stopped,
+}
+
+/// Backfill a partitionable indexer with several executors running concurrently.
+async fn backfill_partitions(
+    config: &IndexerConfig,
+    manifest: &Manifest,
+    wasm_bytes: &[u8],
+    pool: IndexerConnectionPool,
+    fetcher: Arc<BlockFetcher>,
+    kill_switch: Arc<AtomicBool>,
+) -> IndexerResult<Backfill> {
+    let Manifest {
+        namespace,
+        identifier,
+        ..
+    } = manifest;
+    let indexer_uid = manifest.uid();
+    let mut conn = pool.acquire().await?;
+
+    // Partitions are recorded before backfilling, so that an interrupted backfill
+    // resumes with the same partitions.
+    let mut partitions =
+        queries::backfill_partitions_for_indexer(&mut conn, namespace, identifier)
+            .await?;
+    if partitions.is_empty() {
+        let start_block = manifest.start_block.unwrap_or(1);
+        let mut end_block = fetcher.latest_block_height().await?;
+        if let Some(manifest_end_block) = manifest.end_block {
+            end_block = std::cmp::min(end_block, manifest_end_block);
+        }
+
+        partitions =
+            plan_backfill_partitions(start_block, end_block, config.backfill_partitions);
+        for partition in partitions.iter() {
+            queries::put_backfill_partition(&mut conn, namespace, identifier, partition)
+                .await?;
+        }
+    }
+
+    if partitions.is_empty() {
+        return Ok(Backfill::Skipped);
+    }
+
+    info!(
+        "Indexer({indexer_uid}) backfilling blocks {} to {} with {} executors.",
+        partitions[0].start_block,
+        partitions[partitions.len() - 1].end_block,
+        partitions.iter().filter(|p| !p.is_complete()).count()
+    );
+
+    let mut handles = Vec::new();
+    for partition in partitions.iter().filter(|p| !p.is_complete()) {
+        let mut partition_manifest = manifest.clone();
+        partition_manifest.start_block = Some(partition.next_block());
+        partition_manifest.end_block = Some(partition.end_block);
+
+        let executor =
+            WasmIndexExecutor::new(config, &partition_manifest, wasm_bytes, pool.clone())
+                .await?;
+        executor
+            .db
+            .lock()
+            .await
+            .set_backfill_partition(partition.start_block, partition.end_block);
+
+        handles.push(tokio::spawn(run_executor(
+            config,
+            &partition_manifest,
+            executor,
+            fetcher.clone(),
+            kill_switch.clone(),
+        )));
+    }
+
+    for handle in handles {
+        handle.await?;
+    }
+
+    if kill_switch.load(Ordering::SeqCst) {
+        return Ok(Backfill::Stopped);
+    }
+
+    let partitions =
+        queries::backfill_partitions_for_indexer(&mut conn, namespace, identifier)
+            .await?;
+    if let Some(partition) = partitions.iter().find(|p| !p.is_complete()) {
+        return Err(IndexerError::Unknown(format!(
+            "Partition of blocks {} to {} was not backfilled",
+            partition.start_block, partition.end_block
+        )));
+    }
+
+    // The last partition's checkpoint becomes the indexer's, in the same transaction
+    // that removes the partitions.
+    let last = partitions
+        .last()
+        .expect("Backfill partitions were removed while backfilling.");
+    let checkpoint = IndexerCheckpoint {
+        block_height: last.end_block,
+        block_id: last.block_id.clone().unwrap_or_default(),
+        cursor: Some(last.end_block.to_string()),
+    };
+
+    queries::start_transaction(&mut conn).await?;
+    let merged = async {
+        queries::put_checkpoint(&mut conn, namespace, identifier, &checkpoint).await?;
+        queries::remove_backfill_partitions(&mut conn, namespace, identifier).await
+    }
+    .await;
+    match merged {
+        Ok(()) => {
+            queries::commit_transaction(&mut conn).await?;
+        }
+        Err(e) => {
+            queries::revert_transaction(&mut conn).await?;
+            return Err(e.into());
+        }
     }
+
+    Ok(Backfill::Completed(last.end_block + 1))
 }
 
 #[async_trait]
@@ -427,7 +525,12 @@ pub trait Executor
 where
     Self: Sized,
 {
+    /// Handle blocks in the executor's open database transaction, opening one if
+    /// necessary. If handling fails, the open transaction is reverted.
     async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()>;
+
+    /// Commit the executor's open database transaction, if any.
+    async fn commit(&mut self) -> IndexerResult<()>;
 }
 
 #[derive(Error, Debug)]
@@ -442,6 +545,20 @@ pub struct IndexEnv {
     pub alloc: Option<TypedFunction<u32, u32>>,
     pub dealloc: Option<TypedFunction<(u32, u32), ()>>,
     pub db: Arc<Mutex<Database>>,
+
+    /// Panic reported by the module while handling the current batch, if any.
+    pub panic: Option<WasmPanic>,
+}
+
+// `TypedFunction` doesn't implement `Debug`, so the module exports are left out.
+impl std::fmt::Debug for IndexEnv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexEnv")
+            .field("memory", &self.memory)
+            .field("db", &self.db)
+            .field("panic", &self.panic)
+            .finish_non_exhaustive()
+    }
 }
 
 impl IndexEnv {
@@ -455,6 +572,7 @@ impl IndexEnv {
             alloc: None,
             dealloc: None,
             db: Arc::new(Mutex::new(db)),
+            panic: None,
         })
     }
 }
@@ -500,6 +618,7 @@ where
         config: &IndexerConfig,
         manifest: &Manifest,
         pool: IndexerConnectionPool,
+        fetcher: Arc<BlockFetcher>,
         handle_events: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> T,
     ) -> IndexerResult<(JoinHandle<()>, ExecutorSource, Arc<AtomicBool>)> {
         let executor = NativeIndexExecutor::new(manifest, pool, handle_events).await?;
@@ -508,6 +627,7 @@ where
             config,
             manifest,
             executor,
+            fetcher,
             kill_switch.clone(),
         ));
         Ok((handle, ExecutorSource::Manifest, kill_switch))
@@ -520,14 +640,50 @@ where
     F: Future<Output = IndexerResult<()>> + Send,
 {
     async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
-        self.db.lock().await.start_transaction().await?;
+        {
+            let mut db = self.db.lock().await;
+            if !db.in_transaction() {
+                db.start_transaction().await?;
+            }
+
+            // Until the indexer reports the block it's handling, logged lines are
+            // tagged with the first block of the batch.
+            if let Some(block) = blocks.first() {
+                db.set_block_height(block.height);
+            }
+        }
+        let last_block = blocks.last().map(|block| (block.height, block.id));
         let res = (self.handle_events_fn)(blocks, self.db.clone()).await;
         if let Err(e) = res {
             error!("NativeIndexExecutor handle_events failed: {e}.");
             self.db.lock().await.revert_transaction().await?;
             return Err(IndexerError::NativeExecutionRuntimeError);
-        } else {
-            self.db.lock().await.commit_transaction().await?;
+        }
+
+        let mut db = self.db.lock().await;
+        if let Some(e) = db.take_write_error() {
+            error!("NativeIndexExecutor failed to write to the database: {e:?}.");
+            db.revert_transaction().await?;
+            return Err(e);
+        }
+
+        if let Some((height, id)) = last_block {
+            if let Err(e) = db.put_checkpoint(height, &id).await {
+                error!("NativeIndexExecutor failed to record checkpoint: {e:?}.");
+                db.revert_transaction().await?;
+                return Err(e);
+            }
+        }
+
+        db.flush_logs().await;
+
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> IndexerResult<()> {
+        let mut db = self.db.lock().await;
+        if db.in_transaction() {
+            db.commit_transaction().await?;
         }
         Ok(())
     }
@@ -539,9 +695,11 @@ pub struct WasmIndexExecutor {
     instance: Instance,
     _module: Module,
     store: Arc<Mutex<Store>>,
+    env: FunctionEnv<IndexEnv>,
     db: Arc<Mutex<Database>>,
     #[allow(unused)]
     timeout: u64,
+    filter: SubscriptionFilter,
 }
 
 impl WasmIndexExecutor {
@@ -563,8 +721,29 @@ impl WasmIndexExecutor {
             imports.define("env", &export_name, export.clone());
         }
 
+        // A module built for a newer FFI version might import functions that this
+        // service doesn't provide, in which case it can't even be instantiated.
+        if let Some(import) = module.imports().find(|import| {
+            import.module() == "env" && imports.get_export("env", import.name()).is_none()
+        }) {
+            return Err(IndexerError::MissingImport(import.name().to_string()));
+        }
+
         let instance = Instance::new(&mut store, &module, &imports)?;
 
+        // Modules built before the FFI contract was versioned don't export their version.
+        let ffi_version =
+            match ffi::get_export_string(&mut store, &instance, "ffi_version") {
+                Ok(version) => version.parse::<u32>().map_err(|_| {
+                    IndexerError::Unknown(format!("Invalid FFI version: {version:?}"))
+                })?,
+                Err(_) => MIN_SUPPORTED_FFI_VERSION,
+            };
+
+        if !(MIN_SUPPORTED_FFI_VERSION..=FFI_VERSION).contains(&ffi_version) {
+            return Err(IndexerError::UnsupportedFfiVersion(ffi_version));
+        }
+
         if !instance
             .exports
             .contains(ffi::MODULE_ENTRYPOINT.to_string())
@@ -591,12 +770,29 @@ impl WasmIndexExecutor {
             );
         };
 
+        // Modules built before handled types were exported can't be filtered.
+        let filter = match ffi::get_export_string(&mut store, &instance, "handled_types")
+        {
+            Ok(handled_types) => {
+                SubscriptionFilter::new(manifest, handled_types.split(','))
+            }
+            Err(_) => {
+                debug!(
+                    "Indexer({}) does not export its handled types, events will not be filtered.",
+                    manifest.uid()
+                );
+                SubscriptionFilter::default()
+            }
+        };
+
         Ok(WasmIndexExecutor {
             instance,
             _module: module,
             store: Arc::new(Mutex::new(store)),
+            env,
             db,
             timeout: config.indexer_handler_timeout,
+            filter,
         })
     }
 
@@ -617,45 +813,51 @@ impl WasmIndexExecutor {
         manifest: &Manifest,
         exec_source: ExecutorSource,
         pool: IndexerConnectionPool,
+        fetcher: Arc<BlockFetcher>,
     ) -> IndexerResult<(JoinHandle<()>, ExecutorSource, Arc<AtomicBool>)> {
         let killer = Arc::new(AtomicBool::new(false));
 
-        match &exec_source {
+        let bytes = match exec_source {
             ExecutorSource::Manifest => match &manifest.module {
                 crate::Module::Wasm(ref module) => {
                     let mut bytes = Vec::<u8>::new();
                     let mut file = File::open(module).await?;
                     file.read_to_end(&mut bytes).await?;
-
-                    let executor =
-                        WasmIndexExecutor::new(config, manifest, bytes.clone(), pool)
-                            .await?;
-                    let handle = tokio::spawn(run_executor(
-                        config,
-                        manifest,
-                        executor,
-                        killer.clone(),
-                    ));
-
-                    Ok((handle, ExecutorSource::Registry(bytes), killer))
+                    bytes
                 }
                 crate::Module::Native => {
-                    Err(IndexerError::NativeExecutionInstantiationError)
+                    return Err(IndexerError::NativeExecutionInstantiationError)
                 }
             },
-            ExecutorSource::Registry(bytes) => {
-                let executor =
-                    WasmIndexExecutor::new(config, manifest, bytes, pool).await?;
-                let handle = tokio::spawn(run_executor(
-                    config,
-                    manifest,
-                    executor,
-                    killer.clone(),
-                ));
-
-                Ok((handle, exec_source, killer))
-            }
-        }
+            ExecutorSource::Registry(bytes) => bytes,
+        };
+
+        let executor =
+            WasmIndexExecutor::new(config, manifest, bytes.clone(), pool.clone()).await?;
+
+        let handle = if manifest.partitionable.unwrap_or(false)
+            && config.backfill_partitions > 1
+        {
+            tokio::spawn(run_partitioned_executor(
+                config.clone(),
+                manifest.clone(),
+                executor,
+                bytes.clone(),
+                pool,
+                fetcher,
+                killer.clone(),
+            ))
+        } else {
+            tokio::spawn(run_executor(
+                config,
+                manifest,
+                executor,
+                fetcher,
+                killer.clone(),
+            ))
+        };
+
+        Ok((handle, ExecutorSource::Registry(bytes), killer))
     }
 }
 
@@ -663,6 +865,7 @@ impl WasmIndexExecutor {
 impl Executor for WasmIndexExecutor {
     /// Trigger a WASM event handler, passing in a serialized event struct.
     async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
+        let blocks = self.filter.filter_blocks(blocks);
         let bytes = serialize(&blocks);
 
         let mut arg = {
@@ -678,7 +881,18 @@ impl Executor for WasmIndexExecutor {
             )?
         };
 
-        let _ = self.db.lock().await.start_transaction().await?;
+        {
+            let mut db = self.db.lock().await;
+            if !db.in_transaction() {
+                db.start_transaction().await?;
+            }
+
+            // Until the indexer reports the block it's handling, logged lines are
+            // tagged with the first block of the batch.
+            if let Some(block) = blocks.first() {
+                db.set_block_height(block.height);
+            }
+        }
 
         let ptr = arg.get_ptr();
         let len = arg.get_len();
@@ -708,12 +922,48 @@ impl Executor for WasmIndexExecutor {
                 return Err(IndexerError::from(e));
             }
             Ok(Ok(Err(e))) => {
-                error!("WasmIndexExecutor WASM module failed: {e:?}.");
-                self.db.lock().await.revert_transaction().await?;
-                return Err(IndexerError::from(e));
+                let panic = {
+                    let mut store_guard = self.store.lock().await;
+                    self.env.as_mut(&mut store_guard).panic.take()
+                };
+
+                let mut db = self.db.lock().await;
+                let err = match panic {
+                    Some(panic) => {
+                        let err = IndexerError::Panic {
+                            panic,
+                            block_height: db.block_height(),
+                        };
+                        error!("WasmIndexExecutor WASM module failed: {err}.");
+                        db.record_log("error", err.to_string());
+                        db.put_failure(err.to_string()).await;
+                        err
+                    }
+                    None => {
+                        error!("WasmIndexExecutor WASM module failed: {e:?}.");
+                        IndexerError::from(e)
+                    }
+                };
+                db.revert_transaction().await?;
+                return Err(err);
             }
             Ok(Ok(Ok(()))) => {
-                let _ = self.db.lock().await.commit_transaction().await?;
+                let mut db = self.db.lock().await;
+                if let Some(e) = db.take_write_error() {
+                    error!("WasmIndexExecutor failed to write to the database: {e:?}.");
+                    db.revert_transaction().await?;
+                    return Err(e);
+                }
+
+                if let Some(block) = blocks.last() {
+                    if let Err(e) = db.put_checkpoint(block.height, &block.id).await {
+                        error!("WasmIndexExecutor failed to record checkpoint: {e:?}.");
+                        db.revert_transaction().await?;
+                        return Err(e);
+                    }
+                }
+
+                db.flush_logs().await;
             }
         }
 
@@ -722,4 +972,83 @@ impl Executor for WasmIndexExecutor {
 
         Ok(())
     }
+
+    async fn commit(&mut self) -> IndexerResult<()> {
+        let mut db = self.db.lock().await;
+        if db.in_transaction() {
+            db.commit_transaction().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetcher::test_block;
+
+    fn heights(blocks: &[BlockData]) -> Vec<u64> {
+        blocks.iter().map(|block| block.height).collect()
+    }
+
+    #[test]
+    fn test_blocks_are_truncated_at_the_end_block() {
+        let blocks = || (1..=5).map(test_block).collect::<Vec<BlockData>>();
+
+        let mut unbounded = blocks();
+        assert_eq!(truncate_at_end_block(&mut unbounded, None), None);
+        assert_eq!(heights(&unbounded), vec![1, 2, 3, 4, 5]);
+
+        let mut before = blocks();
+        assert_eq!(truncate_at_end_block(&mut before, Some(6)), None);
+        assert_eq!(heights(&before), vec![1, 2, 3, 4, 5]);
+
+        // The end block itself is handled, and the indexer stops after it.
+        let mut at = blocks();
+        assert_eq!(truncate_at_end_block(&mut at, Some(5)), Some(5));
+        assert_eq!(heights(&at), vec![1, 2, 3, 4, 5]);
+
+        let mut past = blocks();
+        assert_eq!(truncate_at_end_block(&mut past, Some(3)), Some(3));
+        assert_eq!(heights(&past), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_backfill_partitions_cover_the_range_without_overlapping() {
+        let partitions = plan_backfill_partitions(1, 10_000, 4);
+        assert_eq!(
+            partitions
+                .iter()
+                .map(|p| (p.start_block, p.end_block))
+                .collect::<Vec<(u64, u64)>>(),
+            vec![(1, 2500), (2501, 5000), (5001, 7500), (7501, 10_000)]
+        );
+        assert!(partitions
+            .iter()
+            .all(|p| p.block_height.is_none() && p.block_id.is_none()));
+
+        // The last partition holds the remainder.
+        let partitions = plan_backfill_partitions(0, 10_002, 3);
+        assert_eq!(
+            partitions
+                .iter()
+                .map(|p| (p.start_block, p.end_block))
+                .collect::<Vec<(u64, u64)>>(),
+            vec![(0, 3333), (3334, 6667), (6668, 10_002)]
+        );
+    }
+
+    #[test]
+    fn test_backfill_partitions_are_at_least_the_minimum_size() {
+        let partitions = plan_backfill_partitions(1, 3 * MIN_BACKFILL_PARTITION_SIZE, 8);
+        assert_eq!(partitions.len(), 3);
+        assert!(partitions
+            .iter()
+            .all(|p| p.end_block + 1 - p.start_block >= MIN_BACKFILL_PARTITION_SIZE));
+
+        // Ranges too small for two partitions aren't partitioned.
+        assert!(plan_backfill_partitions(1, MIN_BACKFILL_PARTITION_SIZE, 4).is_empty());
+        assert!(plan_backfill_partitions(1, 10_000, 1).is_empty());
+        assert!(plan_backfill_partitions(10_000, 1, 4).is_empty());
+    }
 }
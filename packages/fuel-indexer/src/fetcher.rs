@@ -0,0 +1,678 @@
+use crate::{block_store::BlockStore, queries::ClientExt, IndexerError, IndexerResult};
+use async_std::sync::{Arc, Mutex};
+use fuel_core_client::client::{
+    schema::block::{Consensus as ClientConsensus, Genesis as ClientGenesis},
+    types::TransactionStatus as ClientTransactionStatus,
+    FuelClient, PageDirection, PaginatedResult, PaginationRequest,
+};
+use fuel_indexer_lib::config::BlockStoreConfig;
+use fuel_indexer_types::{
+    fuel::{field::*, *},
+    scalar::{Bytes32, HexString},
+};
+use fuel_tx::UniqueIdentifier;
+use fuel_vm::prelude::Deserializable;
+use fuel_vm::state::ProgramState as ClientProgramState;
+use itertools::Itertools;
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+use tracing::{debug, info, warn};
+
+/// A page of blocks, converted from the Fuel node's types to those of the indexer.
+#[derive(Debug, Default)]
+pub struct BlockPage {
+    /// Blocks in this page, in ascending order of height.
+    pub blocks: Vec<BlockData>,
+
+    /// Cursor from which to fetch the next page, if this page is not empty.
+    pub cursor: Option<String>,
+
+    /// Whether the Fuel node has more blocks after this page.
+    pub has_next_page: bool,
+}
+
+/// Fetches blocks from a single Fuel node on behalf of all indexers that subscribe to it.
+///
+/// Each block is fetched and converted once, and kept in a bounded window of the most
+/// recently fetched blocks. Indexers are served from the window at their own positions,
+/// and the first indexer to ask for a block past the window fetches it for everyone else,
+/// while indexers asking for blocks from other heights fetch them concurrently.
+/// Indexers that have fallen behind the window fetch their blocks directly from the node,
+/// without evicting blocks that indexers at the front still need.
+///
+/// If a block store is enabled, every block fetched from the node is also persisted to
+/// it, and indexers are served from the store before falling back to the node.
+pub struct BlockFetcher {
+    /// Address of the Fuel node.
+    fuel_node_addr: String,
+
+    /// Client used to fetch blocks from the Fuel node.
+    client: FuelClient,
+
+    /// Most recently fetched blocks.
+    window: Mutex<BlockWindow>,
+
+    /// Fetches from the Fuel node in progress, by the height of their first block.
+    in_flight: Mutex<HashMap<u64, Arc<Mutex<()>>>>,
+
+    /// Maximum number of blocks kept in the window.
+    window_size: usize,
+
+    /// Local store of previously fetched blocks, if enabled.
+    store: Option<Mutex<BlockStore>>,
+}
+
+/// Blocks most recently fetched from the Fuel node.
+///
+/// Indexers at different heights fetch different ranges of blocks, so the window may
+/// have gaps. Pages are only ever served from a contiguous run of blocks.
+#[derive(Debug, Default)]
+struct BlockWindow {
+    /// Blocks in the window, by height.
+    blocks: BTreeMap<u64, BlockData>,
+
+    /// Whether the Fuel node had more blocks after the last block in the window.
+    has_next_page: bool,
+}
+
+impl BlockWindow {
+    /// Height of the first block in the window.
+    fn first_height(&self) -> Option<u64> {
+        self.blocks.keys().next().copied()
+    }
+
+    /// Height of the last block in the window.
+    fn last_height(&self) -> Option<u64> {
+        self.blocks.keys().next_back().copied()
+    }
+
+    /// Return up to `limit` consecutive blocks starting at `height`, if the window
+    /// contains `height`.
+    fn page_from(&self, height: u64, limit: usize) -> Option<BlockPage> {
+        if !self.blocks.contains_key(&height) {
+            return None;
+        }
+        let last = self.last_height()?;
+
+        let blocks = self
+            .blocks
+            .range(height..)
+            .enumerate()
+            .take_while(|(i, (h, _))| **h == height + *i as u64)
+            .take(limit)
+            .map(|(_, (_, block))| block.clone())
+            .collect::<Vec<BlockData>>();
+        let end = blocks.last().map(|block| block.height).unwrap_or(height);
+
+        Some(BlockPage {
+            blocks,
+            cursor: Some(end.to_string()),
+            has_next_page: end < last || self.has_next_page,
+        })
+    }
+
+    /// Add a page fetched from the Fuel node, evicting the oldest blocks beyond
+    /// `window_size`.
+    fn extend(&mut self, page: &BlockPage, window_size: usize) {
+        let Some(end) = page.blocks.last().map(|block| block.height) else {
+            return;
+        };
+
+        // Only the page reaching furthest knows whether the node has more blocks.
+        if self.last_height().map_or(true, |last| end >= last) {
+            self.has_next_page = page.has_next_page;
+        }
+        for block in page.blocks.iter() {
+            self.blocks.insert(block.height, block.clone());
+        }
+        while self.blocks.len() > window_size {
+            self.blocks.pop_first();
+        }
+    }
+}
+
+impl BlockFetcher {
+    /// Create a new `BlockFetcher` for the Fuel node at `fuel_node_addr`.
+    #[allow(clippy::result_large_err)]
+    pub fn new(
+        fuel_node_addr: &str,
+        window_size: usize,
+        store: Option<BlockStore>,
+    ) -> IndexerResult<Self> {
+        let client = FuelClient::from_str(fuel_node_addr).map_err(|e| {
+            IndexerError::Unknown(format!(
+                "Client node connection to {fuel_node_addr} failed: {e}"
+            ))
+        })?;
+
+        Ok(Self {
+            fuel_node_addr: fuel_node_addr.to_string(),
+            client,
+            window: Mutex::new(BlockWindow::default()),
+            in_flight: Mutex::new(HashMap::new()),
+            window_size,
+            store: store.map(Mutex::new),
+        })
+    }
+
+    /// Address of the Fuel node from which this fetcher fetches blocks.
+    pub fn fuel_node_addr(&self) -> &str {
+        &self.fuel_node_addr
+    }
+
+    /// Height of the latest block on the Fuel node.
+    pub async fn latest_block_height(&self) -> IndexerResult<u64> {
+        Ok(self.client.chain_info().await?.latest_block.header.height.0)
+    }
+
+    /// Return up to `limit` blocks after `cursor`.
+    pub async fn blocks_after(
+        &self,
+        cursor: Option<String>,
+        limit: usize,
+    ) -> IndexerResult<BlockPage> {
+        let height = match cursor.as_deref().map(str::parse::<u64>) {
+            Some(Ok(height)) => height + 1,
+            // Cursors that aren't block heights can't be matched against the window.
+            Some(Err(_)) => {
+                return retrieve_blocks_from_node(&self.client, cursor, limit).await
+            }
+            None => 0,
+        };
+
+        if let Some(page) = self.window.lock().await.page_from(height, limit) {
+            return Ok(page);
+        }
+
+        if let Some(blocks) = self.stored_blocks(height, limit).await {
+            let end = blocks.last().map(|block| block.height).unwrap_or(height);
+            // Stored blocks are behind the tip of the chain, or the next request will
+            // find out that they are not.
+            return Ok(BlockPage {
+                blocks,
+                cursor: Some(end.to_string()),
+                has_next_page: true,
+            });
+        }
+
+        // Indexers behind the window fetch directly, so that they don't evict blocks
+        // that indexers at the front are about to read.
+        let first = self.window.lock().await.first_height();
+        if matches!(first, Some(first) if height < first) {
+            debug!(
+                "Fetching blocks after {cursor:?} directly from {}, behind the shared window.",
+                self.fuel_node_addr
+            );
+            let page = retrieve_blocks_from_node(&self.client, cursor, limit).await?;
+            self.store_blocks(&page.blocks).await;
+            return Ok(page);
+        }
+
+        // Indexers asking for the same blocks wait for a single fetch of them, rather
+        // than fetching the blocks themselves. The window isn't locked while fetching,
+        // so that indexers at other heights aren't held up.
+        let flight = self
+            .in_flight
+            .lock()
+            .await
+            .entry(height)
+            .or_default()
+            .clone();
+        let _guard = flight.lock().await;
+
+        // Another indexer may have fetched these blocks in the meantime.
+        if let Some(page) = self.window.lock().await.page_from(height, limit) {
+            return Ok(page);
+        }
+
+        let result = retrieve_blocks_from_node(&self.client, cursor, limit).await;
+        if let Ok(page) = &result {
+            self.window.lock().await.extend(page, self.window_size);
+        }
+
+        {
+            let mut in_flight = self.in_flight.lock().await;
+            if in_flight
+                .get(&height)
+                .map_or(false, |f| Arc::ptr_eq(f, &flight))
+            {
+                in_flight.remove(&height);
+            }
+        }
+
+        let page = result?;
+        self.store_blocks(&page.blocks).await;
+
+        Ok(page)
+    }
+
+    /// Return up to `limit` blocks starting at `height` from the block store, if it
+    /// holds the block at `height`.
+    async fn stored_blocks(&self, height: u64, limit: usize) -> Option<Vec<BlockData>> {
+        let store = self.store.as_ref()?;
+        match store.lock().await.blocks_from(height, limit).await {
+            Ok(blocks) => blocks,
+            Err(e) => {
+                warn!("Failed to read stored blocks from height {height}, fetching them from the Fuel node: {e}");
+                None
+            }
+        }
+    }
+
+    /// Persist blocks fetched from the Fuel node to the block store, if enabled.
+    async fn store_blocks(&self, blocks: &[BlockData]) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.lock().await.put(blocks).await {
+                warn!("Failed to persist blocks to the block store: {e}");
+            }
+        }
+    }
+}
+
+/// Block fetchers shared by all indexers of an `IndexerService`, one per Fuel node.
+pub struct BlockFetchers {
+    /// Fetchers by the address of their Fuel node.
+    fetchers: HashMap<String, Arc<BlockFetcher>>,
+
+    /// Maximum number of blocks kept in each fetcher's window.
+    window_size: usize,
+
+    /// Configuration of each fetcher's block store.
+    block_store: BlockStoreConfig,
+}
+
+impl BlockFetchers {
+    /// Create a new, empty set of block fetchers.
+    pub fn new(window_size: usize, block_store: BlockStoreConfig) -> Self {
+        Self {
+            fetchers: HashMap::default(),
+            window_size,
+            block_store,
+        }
+    }
+
+    /// Return the fetcher for the Fuel node at `fuel_node_addr`, creating it if necessary.
+    #[allow(clippy::result_large_err)]
+    pub fn get(&mut self, fuel_node_addr: &str) -> IndexerResult<Arc<BlockFetcher>> {
+        if let Some(fetcher) = self.fetchers.get(fuel_node_addr) {
+            return Ok(fetcher.clone());
+        }
+
+        info!("Creating shared block fetcher for Fuel node at {fuel_node_addr}");
+        let store = if self.block_store.enabled {
+            Some(BlockStore::open(&self.block_store, fuel_node_addr)?)
+        } else {
+            None
+        };
+        let fetcher =
+            Arc::new(BlockFetcher::new(fuel_node_addr, self.window_size, store)?);
+        self.fetchers
+            .insert(fuel_node_addr.to_string(), fetcher.clone());
+
+        Ok(fetcher)
+    }
+}
+
+/// Fetch a page of blocks after `cursor` from the Fuel node, and convert them into
+/// the types passed to indexers.
+async fn retrieve_blocks_from_node(
+    client: &FuelClient,
+    cursor: Option<String>,
+    page_size: usize,
+) -> IndexerResult<BlockPage> {
+    let PaginatedResult {
+        cursor,
+        results,
+        has_next_page,
+        ..
+    } = client
+        .full_blocks(PaginationRequest {
+            cursor,
+            results: page_size,
+            direction: PageDirection::Forward,
+        })
+        .await?;
+
+    // In general the logic in this loop isn't very idiomatic, but that's because
+    // types in `fuel_core_client` don't compile to WASM.
+    let mut blocks = Vec::new();
+    for block in results.into_iter() {
+        let producer = block
+            .block_producer()
+            .map(|pk| Bytes32::from(<[u8; 32]>::try_from(pk.hash()).unwrap()));
+
+        let mut transactions = Vec::new();
+
+        for trans in block.transactions {
+            let receipts = trans
+                .receipts
+                .unwrap_or_default()
+                .into_iter()
+                .map(TryInto::try_into)
+                .try_collect()
+                .expect("Bad receipts.");
+
+            let status = trans.status.expect("Bad transaction status.");
+            // NOTE: https://github.com/FuelLabs/fuel-indexer/issues/286
+            let status = match status.try_into().unwrap() {
+                ClientTransactionStatus::Success {
+                    block_id,
+                    time,
+                    program_state,
+                } => {
+                    let program_state = program_state.map(|p| match p {
+                        ClientProgramState::Return(w) => ProgramState {
+                            return_type: ReturnType::Return,
+                            data: HexString::from(w.to_le_bytes().to_vec()),
+                        },
+                        ClientProgramState::ReturnData(d) => ProgramState {
+                            return_type: ReturnType::ReturnData,
+                            data: HexString::from(d.to_vec()),
+                        },
+                        ClientProgramState::Revert(w) => ProgramState {
+                            return_type: ReturnType::Revert,
+                            data: HexString::from(w.to_le_bytes().to_vec()),
+                        },
+                        // Either `cargo watch` complains that this is unreachable, or `clippy` complains
+                        // that all patterns are not matched. These other program states are only used in
+                        // debug modes.
+                        #[allow(unreachable_patterns)]
+                        _ => unreachable!("Bad program state."),
+                    });
+                    TransactionStatus::Success {
+                        block: block_id.parse().expect("Bad block height."),
+                        time: time.to_unix() as u64,
+                        program_state,
+                    }
+                }
+                ClientTransactionStatus::Failure {
+                    block_id,
+                    time,
+                    reason,
+                    program_state,
+                } => {
+                    let program_state = program_state.map(|p| match p {
+                        ClientProgramState::Return(w) => ProgramState {
+                            return_type: ReturnType::Return,
+                            data: HexString::from(w.to_le_bytes().to_vec()),
+                        },
+                        ClientProgramState::ReturnData(d) => ProgramState {
+                            return_type: ReturnType::ReturnData,
+                            data: HexString::from(d.to_vec()),
+                        },
+                        ClientProgramState::Revert(w) => ProgramState {
+                            return_type: ReturnType::Revert,
+                            data: HexString::from(w.to_le_bytes().to_vec()),
+                        },
+                        // Either `cargo watch` complains that this is unreachable, or `clippy` complains
+                        // that all patterns are not matched. These other program states are only used in
+                        // debug modes.
+                        #[allow(unreachable_patterns)]
+                        _ => unreachable!("Bad program state."),
+                    });
+                    TransactionStatus::Failure {
+                        block: block_id.parse().expect("Bad block ID."),
+                        time: time.to_unix() as u64,
+                        program_state,
+                        reason,
+                    }
+                }
+                ClientTransactionStatus::Submitted { submitted_at } => {
+                    TransactionStatus::Submitted {
+                        submitted_at: submitted_at.to_unix() as u64,
+                    }
+                }
+                ClientTransactionStatus::SqueezedOut { reason } => {
+                    TransactionStatus::SqueezedOut { reason }
+                }
+            };
+
+            let transaction =
+                fuel_tx::Transaction::from_bytes(trans.raw_payload.0 .0.as_slice())
+                    .expect("Bad transaction.");
+
+            let id = transaction.id();
+
+            let transaction = match transaction {
+                ClientTransaction::Create(tx) => Transaction::Create(Create {
+                    gas_price: *tx.gas_price(),
+                    gas_limit: *tx.gas_limit(),
+                    maturity: *tx.maturity(),
+                    bytecode_length: *tx.bytecode_length(),
+                    bytecode_witness_index: *tx.bytecode_witness_index(),
+                    storage_slots: tx
+                        .storage_slots()
+                        .iter()
+                        .map(|x| StorageSlot {
+                            key: <[u8; 32]>::from(*x.key()).into(),
+                            value: <[u8; 32]>::from(*x.value()).into(),
+                        })
+                        .collect(),
+                    inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
+                    outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
+                    witnesses: tx.witnesses().to_vec(),
+                    salt: <[u8; 32]>::from(*tx.salt()).into(),
+                    metadata: None,
+                }),
+                ClientTransaction::Script(tx) => Transaction::Script(Script {
+                    gas_price: *tx.gas_price(),
+                    gas_limit: *tx.gas_limit(),
+                    maturity: *tx.maturity(),
+                    script: tx.script().to_vec(),
+                    script_data: tx.script_data().to_vec(),
+                    inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
+                    outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
+                    witnesses: tx.witnesses().to_vec(),
+                    receipts_root: <[u8; 32]>::from(*tx.receipts_root()).into(),
+                    metadata: None,
+                }),
+                ClientTransaction::Mint(tx) => Transaction::Mint(Mint {
+                    tx_pointer: tx.tx_pointer().to_owned().into(),
+                    outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
+                    metadata: None,
+                }),
+            };
+
+            let tx_data = TransactionData {
+                receipts,
+                status,
+                transaction,
+                id,
+            };
+
+            transactions.push(tx_data);
+        }
+
+        // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
+        let consensus = match &block.consensus {
+            ClientConsensus::Unknown => Consensus::Unknown,
+            ClientConsensus::Genesis(g) => {
+                let ClientGenesis {
+                    chain_config_hash,
+                    coins_root,
+                    contracts_root,
+                    messages_root,
+                } = g.to_owned();
+
+                Consensus::Genesis(Genesis {
+                    chain_config_hash: <[u8; 32]>::from(
+                        chain_config_hash.to_owned().0 .0,
+                    )
+                    .into(),
+                    coins_root: <[u8; 32]>::from(coins_root.0 .0.to_owned()).into(),
+                    contracts_root: <[u8; 32]>::from(contracts_root.0 .0.to_owned())
+                        .into(),
+                    messages_root: <[u8; 32]>::from(messages_root.0 .0.to_owned()).into(),
+                })
+            }
+            ClientConsensus::PoAConsensus(poa) => Consensus::PoA(PoA {
+                signature: <[u8; 64]>::from(poa.signature.0 .0.to_owned()).into(),
+            }),
+        };
+
+        // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
+        let block = BlockData {
+            height: block.header.height.clone().into(),
+            id: Bytes32::from(<[u8; 32]>::from(block.id.0 .0)),
+            producer,
+            time: block.header.time.0.to_unix(),
+            consensus,
+            header: Header {
+                id: Bytes32::from(<[u8; 32]>::from(block.header.id.0 .0)),
+                da_height: block.header.da_height.0,
+                transactions_count: block.header.transactions_count.0,
+                output_messages_count: block.header.output_messages_count.0,
+                transactions_root: Bytes32::from(<[u8; 32]>::from(
+                    block.header.transactions_root.0 .0,
+                )),
+                output_messages_root: Bytes32::from(<[u8; 32]>::from(
+                    block.header.output_messages_root.0 .0,
+                )),
+                height: block.header.height.0,
+                prev_root: Bytes32::from(<[u8; 32]>::from(block.header.prev_root.0 .0)),
+                time: block.header.time.0.to_unix(),
+                application_hash: Bytes32::from(<[u8; 32]>::from(
+                    block.header.application_hash.0 .0,
+                )),
+            },
+            transactions,
+        };
+
+        blocks.push(block);
+    }
+
+    Ok(BlockPage {
+        blocks,
+        cursor,
+        has_next_page,
+    })
+}
+
+/// Return an empty block at the given height, for tests.
+#[cfg(test)]
+pub(crate) fn test_block(height: u64) -> BlockData {
+    BlockData {
+        height,
+        id: [height as u8; 32].into(),
+        header: Header {
+            id: [height as u8; 32].into(),
+            da_height: 0,
+            transactions_count: 0,
+            output_messages_count: 0,
+            transactions_root: [0u8; 32].into(),
+            output_messages_root: [0u8; 32].into(),
+            height,
+            prev_root: [0u8; 32].into(),
+            time: 0,
+            application_hash: [0u8; 32].into(),
+        },
+        producer: None,
+        time: 0,
+        consensus: Consensus::default(),
+        transactions: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(heights: std::ops::RangeInclusive<u64>, has_next_page: bool) -> BlockPage {
+        let blocks = heights.map(test_block).collect::<Vec<BlockData>>();
+        BlockPage {
+            cursor: blocks.last().map(|block| block.height.to_string()),
+            blocks,
+            has_next_page,
+        }
+    }
+
+    fn heights(page: &BlockPage) -> Vec<u64> {
+        page.blocks.iter().map(|block| block.height).collect()
+    }
+
+    #[test]
+    fn test_block_window_serves_pages_of_the_blocks_it_holds() {
+        let mut window = BlockWindow::default();
+        assert!(window.page_from(1, 10).is_none());
+
+        window.extend(&page(1..=5, true), 100);
+
+        let first = window.page_from(1, 3).unwrap();
+        assert_eq!(heights(&first), vec![1, 2, 3]);
+        assert_eq!(first.cursor.as_deref(), Some("3"));
+        assert!(first.has_next_page);
+
+        // Pages at the end of the window have a next page if the node had one.
+        let last = window.page_from(4, 10).unwrap();
+        assert_eq!(heights(&last), vec![4, 5]);
+        assert_eq!(last.cursor.as_deref(), Some("5"));
+        assert!(last.has_next_page);
+
+        window.extend(&page(6..=7, false), 100);
+        let tip = window.page_from(6, 10).unwrap();
+        assert_eq!(heights(&tip), vec![6, 7]);
+        assert!(!tip.has_next_page);
+
+        assert!(window.page_from(0, 10).is_none());
+        assert!(window.page_from(8, 10).is_none());
+    }
+
+    #[test]
+    fn test_block_window_evicts_its_oldest_blocks() {
+        let mut window = BlockWindow::default();
+        window.extend(&page(1..=5, true), 4);
+        window.extend(&page(6..=7, true), 4);
+
+        assert_eq!(window.first_height(), Some(4));
+        assert_eq!(window.last_height(), Some(7));
+        assert!(window.page_from(3, 10).is_none());
+        assert_eq!(heights(&window.page_from(4, 10).unwrap()), vec![4, 5, 6, 7]);
+    }
+
+    #[tokio::test]
+    async fn test_stored_pages_always_have_a_next_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BlockStoreConfig {
+            enabled: true,
+            path: dir.path().to_str().unwrap().to_string(),
+            ..BlockStoreConfig::default()
+        };
+        let mut store = BlockStore::open(&config, "127.0.0.1:4000").unwrap();
+        store.put(&page(1..=5, false).blocks).await.unwrap();
+
+        // Nothing is fetched from the node, since the blocks are stored.
+        let fetcher = BlockFetcher::new("127.0.0.1:4000", 10, Some(store)).unwrap();
+        let stored = fetcher
+            .blocks_after(Some("2".to_string()), 10)
+            .await
+            .unwrap();
+        assert_eq!(heights(&stored), vec![3, 4, 5]);
+        assert_eq!(stored.cursor.as_deref(), Some("5"));
+        assert!(stored.has_next_page);
+    }
+
+    #[test]
+    fn test_block_window_keeps_blocks_of_indexers_at_other_heights() {
+        let mut window = BlockWindow::default();
+        window.extend(&page(1..=3, true), 100);
+        window.extend(&page(10..=12, false), 100);
+
+        // Pages stop at gaps, and have a next page since the node has later blocks.
+        let behind = window.page_from(2, 10).unwrap();
+        assert_eq!(heights(&behind), vec![2, 3]);
+        assert!(behind.has_next_page);
+        assert!(window.page_from(4, 10).is_none());
+        assert_eq!(
+            heights(&window.page_from(10, 10).unwrap()),
+            vec![10, 11, 12]
+        );
+
+        // Filling the gap doesn't change whether the node has blocks after the tip.
+        window.extend(&page(4..=9, true), 100);
+        let all = window.page_from(1, 100).unwrap();
+        assert_eq!(heights(&all), (1..=12).collect::<Vec<u64>>());
+        assert!(!all.has_next_page);
+    }
+}
@@ -1,6 +1,7 @@
 use fuel_indexer_schema::FtColumn;
 use fuel_indexer_types::ffi::{
-    LOG_LEVEL_DEBUG, LOG_LEVEL_ERROR, LOG_LEVEL_INFO, LOG_LEVEL_TRACE, LOG_LEVEL_WARN,
+    MetricUpdate, WasmPanic, LOG_LEVEL_DEBUG, LOG_LEVEL_ERROR, LOG_LEVEL_INFO,
+    LOG_LEVEL_TRACE, LOG_LEVEL_WARN,
 };
 use thiserror::Error;
 use tracing::{debug, error, info, trace, warn};
@@ -38,6 +39,17 @@ fn get_object_id(mem: &MemoryView, ptr: u32) -> u64 {
         .expect("Could not read object ID")
 }
 
+fn get_bytes(mem: &MemoryView, ptr: u32, len: u32) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    bytes
+}
+
 fn log_data(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32, log_level: u32) {
     let (idx_env, store) = env.data_and_store_mut();
     let mem = idx_env
@@ -49,14 +61,40 @@ fn log_data(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32, log_level: u3
     let log_string =
         get_string(&mem, ptr, len).expect("Log string could not be fetched.");
 
-    match log_level {
-        LOG_LEVEL_ERROR => error!("{log_string}",),
-        LOG_LEVEL_WARN => warn!("{log_string}",),
-        LOG_LEVEL_INFO => info!("{log_string}",),
-        LOG_LEVEL_DEBUG => debug!("{log_string}",),
-        LOG_LEVEL_TRACE => trace!("{log_string}",),
+    let rt = tokio::runtime::Handle::current();
+    let mut db = rt.block_on(idx_env.db.lock());
+    let uid = format!("{}.{}", db.namespace, db.identifier);
+
+    let level = match log_level {
+        LOG_LEVEL_ERROR => {
+            error!("Indexer({uid}): {log_string}");
+            "error"
+        }
+        LOG_LEVEL_WARN => {
+            warn!("Indexer({uid}): {log_string}");
+            "warn"
+        }
+        LOG_LEVEL_INFO => {
+            info!("Indexer({uid}): {log_string}");
+            "info"
+        }
+        LOG_LEVEL_DEBUG => {
+            debug!("Indexer({uid}): {log_string}");
+            "debug"
+        }
+        LOG_LEVEL_TRACE => {
+            trace!("Indexer({uid}): {log_string}");
+            "trace"
+        }
         l => panic!("Invalid log level: {l}"),
-    }
+    };
+
+    db.record_log(level, log_string);
+}
+
+fn set_block_height(env: FunctionEnvMut<IndexEnv>, height: u64) {
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { env.data().db.lock().await.set_block_height(height) });
 }
 
 fn get_object(
@@ -144,6 +182,147 @@ fn put_object(mut env: FunctionEnvMut<IndexEnv>, type_id: i64, ptr: u32, len: u3
     });
 }
 
+fn kv_get(
+    mut env: FunctionEnvMut<IndexEnv>,
+    key_ptr: u32,
+    key_len: u32,
+    len_ptr: u32,
+) -> u32 {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    let key = {
+        let mem = idx_env
+            .memory
+            .as_mut()
+            .expect("Memory unitialized.")
+            .view(&store);
+        get_bytes(&mem, key_ptr, key_len)
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    let value = rt.block_on(async { idx_env.db.lock().await.get_kv(&key).await });
+
+    if let Some(bytes) = value {
+        let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+        let size = bytes.len() as u32;
+        let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+        let range = result as usize..result as usize + size as usize;
+
+        let mem = idx_env
+            .memory
+            .as_mut()
+            .expect("Memory unitialized.")
+            .view(&store);
+        WasmPtr::<u32>::new(len_ptr)
+            .deref(&mem)
+            .write(size)
+            .unwrap();
+
+        unsafe {
+            mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+        }
+
+        result
+    } else {
+        0
+    }
+}
+
+fn kv_put(
+    mut env: FunctionEnvMut<IndexEnv>,
+    key_ptr: u32,
+    key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) {
+    let (idx_env, store) = env.data_and_store_mut();
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized")
+        .view(&store);
+
+    let key = get_bytes(&mem, key_ptr, key_len);
+    let value = get_bytes(&mem, value_ptr, value_len);
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { idx_env.db.lock().await.put_kv(&key, &value).await });
+}
+
+fn kv_delete(mut env: FunctionEnvMut<IndexEnv>, key_ptr: u32, key_len: u32) {
+    let (idx_env, store) = env.data_and_store_mut();
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized")
+        .view(&store);
+
+    let key = get_bytes(&mem, key_ptr, key_len);
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { idx_env.db.lock().await.delete_kv(&key).await });
+}
+
+fn put_metric(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32) {
+    let (idx_env, store) = env.data_and_store_mut();
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized")
+        .view(&store);
+
+    let bytes = get_bytes(&mem, ptr, len);
+
+    let update: MetricUpdate = match bincode::deserialize(&bytes) {
+        Ok(update) => update,
+        Err(e) => {
+            error!("Failed to deserialize MetricUpdate for put_metric: {:?}", e);
+            return;
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { idx_env.db.lock().await.record_metric(update) });
+}
+
+fn put_panic(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32) {
+    let (idx_env, store) = env.data_and_store_mut();
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized")
+        .view(&store);
+
+    let bytes = get_bytes(&mem, ptr, len);
+
+    match bincode::deserialize::<WasmPanic>(&bytes) {
+        Ok(panic) => idx_env.panic = Some(panic),
+        Err(e) => error!("Failed to deserialize WasmPanic for put_panic: {:?}", e),
+    }
+}
+
+/// Read a string constant exported by an indexer module through its
+/// `get_{name}_ptr` and `get_{name}_len` functions.
+pub fn get_export_string(
+    store: &mut Store,
+    instance: &Instance,
+    name: &str,
+) -> Result<String, FFIError> {
+    let ptr_fn = instance
+        .exports
+        .get_typed_function::<(), u32>(store, &format!("get_{name}_ptr"))?;
+    let len_fn = instance
+        .exports
+        .get_typed_function::<(), u32>(store, &format!("get_{name}_len"))?;
+
+    let ptr = ptr_fn.call(store)?;
+    let len = len_fn.call(store)?;
+
+    let mem = instance.exports.get_memory("memory")?.view(store);
+    get_string(&mem, ptr, len)
+}
+
 pub fn get_exports(store: &mut Store, env: &wasmer::FunctionEnv<IndexEnv>) -> Exports {
     let mut exports = Exports::new();
 
@@ -154,6 +333,22 @@ pub fn get_exports(store: &mut Store, env: &wasmer::FunctionEnv<IndexEnv>) -> Ex
     exports.insert("ff_put_object".to_string(), f_put_obj);
     exports.insert("ff_log_data".to_string(), f_log_data);
 
+    let f_set_block_height = Function::new_typed_with_env(store, env, set_block_height);
+    exports.insert("ff_set_block_height".to_string(), f_set_block_height);
+
+    let f_kv_get = Function::new_typed_with_env(store, env, kv_get);
+    let f_kv_put = Function::new_typed_with_env(store, env, kv_put);
+    let f_kv_delete = Function::new_typed_with_env(store, env, kv_delete);
+    exports.insert("ff_kv_get".to_string(), f_kv_get);
+    exports.insert("ff_kv_put".to_string(), f_kv_put);
+    exports.insert("ff_kv_delete".to_string(), f_kv_delete);
+
+    let f_put_metric = Function::new_typed_with_env(store, env, put_metric);
+    exports.insert("ff_put_metric".to_string(), f_put_metric);
+
+    let f_put_panic = Function::new_typed_with_env(store, env, put_panic);
+    exports.insert("ff_put_panic".to_string(), f_put_panic);
+
     exports
 }
 
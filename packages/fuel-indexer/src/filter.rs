@@ -0,0 +1,620 @@
+use fuel_indexer_lib::manifest::{ContractIds, Manifest};
+use fuel_indexer_types::{
+    fuel::{BlockData, Input, Receipt, Script, Transaction, TransactionData},
+    scalar::Bech32ContractId,
+};
+use std::{collections::HashSet, str::FromStr};
+use tracing::warn;
+
+/// Fuel types whose handlers need to see whole blocks or transactions. If an
+/// indexer handles any of these, nothing is stripped before it reaches the module.
+const UNFILTERABLE_TYPES: [&str; 9] = [
+    "BlockData",
+    "ChangeOutput",
+    "CoinOutput",
+    "ContractCreated",
+    "ContractOutput",
+    "MessageOutput",
+    "Output",
+    "TransactionData",
+    "VariableOutput",
+];
+
+/// Input types that can be passed to handlers directly.
+const INPUT_TYPES: [&str; 4] = ["Input", "InputCoin", "InputContract", "InputMessage"];
+
+/// Receipt types that can be passed to handlers directly.
+const RECEIPT_TYPES: [&str; 11] = [
+    "Call",
+    "Log",
+    "LogData",
+    "MessageOut",
+    "Panic",
+    "Return",
+    "ReturnData",
+    "Revert",
+    "ScriptResult",
+    "Transfer",
+    "TransferOut",
+];
+
+/// Receipts from which the indexer module decodes ABI types.
+const ABI_RECEIPT_TYPES: [&str; 3] = ["LogData", "MessageOut", "ReturnData"];
+
+/// Strips transactions, receipts and inputs that no handler of an indexer would ever
+/// see, before blocks are serialized and copied into the indexer module.
+///
+/// The filter is derived from the manifest's `contract_id`, `addresses`, `asset_ids` and
+/// `predicate_roots`, and from the handler argument types that the module declares.
+/// Blocks are never dropped entirely, since the module still records block metadata
+/// for every block it is given.
+#[derive(Debug, Default)]
+pub struct SubscriptionFilter {
+    /// Whether filtering is enabled at all.
+    enabled: bool,
+
+    /// Receipt types that at least one handler depends on.
+    receipt_types: HashSet<&'static str>,
+
+    /// Subscribed contracts, if the manifest specifies any.
+    contract_ids: Option<HashSet<Bech32ContractId>>,
+
+    /// Subscribed addresses, if the manifest specifies any.
+    addresses: Option<HashSet<[u8; 32]>>,
+
+    /// Subscribed assets, if the manifest specifies any.
+    asset_ids: Option<HashSet<[u8; 32]>>,
+
+    /// Subscribed predicate roots, if the manifest specifies any.
+    predicate_roots: Option<HashSet<[u8; 32]>>,
+
+    /// Whether at least one handler depends on transaction inputs.
+    handles_inputs: bool,
+}
+
+impl SubscriptionFilter {
+    /// Create a new `SubscriptionFilter` from a manifest and the handler argument
+    /// types declared by the indexer module.
+    pub fn new<'a>(
+        manifest: &Manifest,
+        handled_types: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let handled_types = handled_types
+            .into_iter()
+            .filter(|ty| !ty.is_empty())
+            .collect::<HashSet<&str>>();
+
+        if handled_types
+            .iter()
+            .any(|ty| UNFILTERABLE_TYPES.contains(ty))
+        {
+            return Self::default();
+        }
+
+        let contract_ids = match &manifest.contract_id {
+            ContractIds::Single(None) => None,
+            ContractIds::Single(Some(id)) => match Bech32ContractId::from_str(id) {
+                Ok(id) => Some(HashSet::from([id])),
+                Err(e) => {
+                    warn!("Indexer({}) has an invalid contract_id, events will not be filtered: {e}.", manifest.uid());
+                    return Self::default();
+                }
+            },
+            ContractIds::Multiple(ids) => {
+                match ids
+                    .iter()
+                    .map(|id| Bech32ContractId::from_str(id))
+                    .collect::<Result<HashSet<_>, _>>()
+                {
+                    Ok(ids) => Some(ids),
+                    Err(e) => {
+                        warn!("Indexer({}) has an invalid contract_id, events will not be filtered: {e}.", manifest.uid());
+                        return Self::default();
+                    }
+                }
+            }
+        };
+
+        let (addresses, asset_ids, predicate_roots) = match (
+            manifest.subscribed_addresses(),
+            manifest.subscribed_asset_ids(),
+            manifest.subscribed_predicate_roots(),
+        ) {
+            (Ok(addresses), Ok(asset_ids), Ok(predicate_roots)) => (
+                addresses.map(HashSet::from_iter),
+                asset_ids.map(HashSet::from_iter),
+                predicate_roots.map(HashSet::from_iter),
+            ),
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+                warn!("Indexer({}) has an invalid subscription, events will not be filtered: {e}.", manifest.uid());
+                return Self::default();
+            }
+        };
+
+        let mut receipt_types = RECEIPT_TYPES
+            .into_iter()
+            .filter(|ty| handled_types.contains(ty))
+            .collect::<HashSet<&'static str>>();
+
+        let handles_inputs = handled_types.iter().any(|ty| INPUT_TYPES.contains(ty));
+
+        if handled_types
+            .iter()
+            .any(|ty| !RECEIPT_TYPES.contains(ty) && !INPUT_TYPES.contains(ty))
+        {
+            receipt_types.extend(ABI_RECEIPT_TYPES);
+        }
+
+        // Return values are matched to the preceding `Call` receipt of the same contract.
+        if receipt_types.contains("Return") || receipt_types.contains("ReturnData") {
+            receipt_types.insert("Call");
+        }
+
+        Self {
+            enabled: true,
+            receipt_types,
+            contract_ids,
+            addresses,
+            asset_ids,
+            predicate_roots,
+            handles_inputs,
+        }
+    }
+
+    /// Whether this filter strips anything at all.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Strip irrelevant transactions and receipts from the given blocks.
+    ///
+    /// Blocks with nothing relevant are kept, but without any transactions, so that
+    /// the block metadata (and therefore the indexer's checkpoint) is still recorded.
+    /// The bodies of the transactions that are kept are replaced with
+    /// `Transaction::default()`, since no handler of a filtered indexer reads them,
+    /// except for the relevant inputs if a handler takes inputs.
+    pub fn filter_blocks(&self, blocks: Vec<BlockData>) -> Vec<BlockData> {
+        if !self.enabled {
+            return blocks;
+        }
+
+        blocks
+            .into_iter()
+            .map(|mut block| {
+                block.transactions = block
+                    .transactions
+                    .into_iter()
+                    .filter_map(|tx| self.filter_transaction(tx))
+                    .collect();
+                block
+            })
+            .collect()
+    }
+
+    /// Strip irrelevant receipts and inputs from a transaction, returning `None` if
+    /// none remain.
+    fn filter_transaction(&self, mut tx: TransactionData) -> Option<TransactionData> {
+        tx.receipts.retain(|receipt| self.is_relevant(receipt));
+
+        let inputs = match (&mut tx.transaction, self.handles_inputs) {
+            (Transaction::Script(t), true) => std::mem::take(&mut t.inputs),
+            (Transaction::Create(t), true) => std::mem::take(&mut t.inputs),
+            _ => Vec::new(),
+        }
+        .into_iter()
+        .filter(|input| self.is_relevant_input(input))
+        .collect::<Vec<Input>>();
+
+        if tx.receipts.is_empty() && inputs.is_empty() {
+            return None;
+        }
+
+        // No handler sees the transaction body itself, only its receipts and inputs.
+        tx.transaction = Transaction::Script(Script {
+            inputs,
+            ..Script::default()
+        });
+
+        Some(tx)
+    }
+
+    /// Whether any handler could be dispatched from the given input.
+    fn is_relevant_input(&self, input: &Input) -> bool {
+        match input {
+            Input::Coin(coin) => {
+                self.is_subscribed_to_address(&coin.owner)
+                    && self.is_subscribed_to_asset(&coin.asset_id)
+                    && self.is_subscribed_to_predicate(&coin.owner, &coin.predicate)
+            }
+            Input::Contract(contract) => {
+                self.is_subscribed_to_contract(&contract.contract_id)
+            }
+            Input::Message(message) => {
+                (self.is_subscribed_to_address(&message.sender)
+                    || self.is_subscribed_to_address(&message.recipient))
+                    && self.is_subscribed_to_predicate(
+                        &message.recipient,
+                        &message.predicate,
+                    )
+            }
+        }
+    }
+
+    /// Whether any handler could be dispatched from the given receipt.
+    fn is_relevant(&self, receipt: &Receipt) -> bool {
+        let (ty, contract_id) = match receipt {
+            Receipt::Call { to, .. } => ("Call", Some(to)),
+            Receipt::Return { id, .. } => ("Return", Some(id)),
+            Receipt::ReturnData { id, .. } => ("ReturnData", Some(id)),
+            Receipt::Panic { id, .. } => ("Panic", Some(id)),
+            Receipt::Revert { id, .. } => ("Revert", Some(id)),
+            Receipt::Log { id, .. } => ("Log", Some(id)),
+            Receipt::LogData { id, .. } => ("LogData", Some(id)),
+            Receipt::Transfer { id, .. } => ("Transfer", Some(id)),
+            Receipt::TransferOut { id, .. } => ("TransferOut", Some(id)),
+            Receipt::ScriptResult { .. } => ("ScriptResult", None),
+            Receipt::MessageOut { .. } => ("MessageOut", None),
+            // Either `cargo watch` complains that this is unreachable, or `clippy` complains
+            // that all patterns are not matched. Receipts we don't know about are never stripped.
+            #[allow(unreachable_patterns)]
+            _ => return true,
+        };
+
+        if let Some(id) = contract_id {
+            if !self.is_subscribed_to_contract(id) {
+                return false;
+            }
+        }
+
+        let is_subscribed = match receipt {
+            Receipt::Transfer { asset_id, .. } => self.is_subscribed_to_asset(asset_id),
+            Receipt::TransferOut { to, asset_id, .. } => {
+                self.is_subscribed_to_address(to) && self.is_subscribed_to_asset(asset_id)
+            }
+            Receipt::MessageOut {
+                sender, recipient, ..
+            } => {
+                self.is_subscribed_to_address(sender)
+                    || self.is_subscribed_to_address(recipient)
+            }
+            _ => true,
+        };
+
+        is_subscribed && self.receipt_types.contains(ty)
+    }
+
+    /// Whether the manifest subscribes to the given contract.
+    fn is_subscribed_to_contract(&self, id: &[u8; 32]) -> bool {
+        match &self.contract_ids {
+            Some(contract_ids) => {
+                contract_ids.contains(&Bech32ContractId::new("fuel", *id))
+            }
+            None => true,
+        }
+    }
+
+    /// Whether the manifest subscribes to the given address.
+    fn is_subscribed_to_address(&self, address: &[u8; 32]) -> bool {
+        match &self.addresses {
+            Some(addresses) => addresses.contains(address),
+            None => true,
+        }
+    }
+
+    /// Whether the manifest subscribes to the predicate with the given root. Only
+    /// inputs spent by a predicate have a root: the coin owner or message recipient.
+    fn is_subscribed_to_predicate(&self, root: &[u8; 32], predicate: &[u8]) -> bool {
+        match &self.predicate_roots {
+            Some(predicate_roots) => {
+                !predicate.is_empty() && predicate_roots.contains(root)
+            }
+            None => true,
+        }
+    }
+
+    /// Whether the manifest subscribes to the given asset.
+    fn is_subscribed_to_asset(&self, asset_id: &[u8; 32]) -> bool {
+        match &self.asset_ids {
+            Some(asset_ids) => asset_ids.contains(asset_id),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetcher::test_block;
+    use fuel_indexer_types::fuel::{
+        InputCoin, InputMessage, ScriptExecutionResult, TransactionStatus, TxPointer,
+    };
+    use fuel_tx::ContractId;
+
+    const MANIFEST: &str = r#"
+namespace: test_namespace
+identifier: filtered
+graphql_schema: schema.graphql
+module:
+  wasm: filtered.wasm
+contract_id: ~
+"#;
+
+    fn manifest(contract_ids: &[[u8; 32]]) -> Manifest {
+        let mut manifest = Manifest::try_from(MANIFEST).unwrap();
+        manifest.contract_id = ContractIds::Multiple(
+            contract_ids
+                .iter()
+                .map(|id| Bech32ContractId::new("fuel", *id).to_string())
+                .collect(),
+        );
+        manifest
+    }
+
+    fn call(to: [u8; 32]) -> Receipt {
+        Receipt::Call {
+            id: ContractId::from([0u8; 32]),
+            to: ContractId::from(to),
+            amount: 0,
+            asset_id: [0u8; 32].into(),
+            gas: 0,
+            param1: 0,
+            param2: 0,
+            pc: 0,
+            is: 0,
+        }
+    }
+
+    fn ret(id: [u8; 32]) -> Receipt {
+        Receipt::Return {
+            id: ContractId::from(id),
+            val: 0,
+            pc: 0,
+            is: 0,
+        }
+    }
+
+    fn log(id: [u8; 32]) -> Receipt {
+        Receipt::Log {
+            id: ContractId::from(id),
+            ra: 0,
+            rb: 0,
+            rc: 0,
+            rd: 0,
+            pc: 0,
+            is: 0,
+        }
+    }
+
+    fn script_result() -> Receipt {
+        Receipt::ScriptResult {
+            result: ScriptExecutionResult::Success,
+            gas_used: 0,
+        }
+    }
+
+    fn tx(receipts: Vec<Receipt>) -> TransactionData {
+        TransactionData {
+            transaction: Transaction::Script(Script {
+                gas_limit: 42,
+                ..Script::default()
+            }),
+            status: TransactionStatus::default(),
+            receipts,
+            id: [0u8; 32].into(),
+        }
+    }
+
+    fn block(txs: Vec<TransactionData>) -> BlockData {
+        BlockData {
+            transactions: txs,
+            ..test_block(1)
+        }
+    }
+
+    fn coin(owner: [u8; 32], predicate: &[u8]) -> Input {
+        Input::Coin(InputCoin {
+            utxo_id: Default::default(),
+            owner: owner.into(),
+            amount: 0,
+            asset_id: [0u8; 32].into(),
+            tx_pointer: TxPointer {
+                block_height: 0u32.into(),
+                tx_index: 0,
+            },
+            witness_index: 0,
+            maturity: 0,
+            predicate: predicate.to_vec().into(),
+            predicate_data: Vec::new().into(),
+        })
+    }
+
+    fn message(recipient: [u8; 32], predicate: &[u8]) -> Input {
+        Input::Message(InputMessage {
+            sender: [0u8; 32].into(),
+            recipient: recipient.into(),
+            amount: 0,
+            nonce: Default::default(),
+            witness_index: 0,
+            data: Vec::new().into(),
+            predicate: predicate.to_vec().into(),
+            predicate_data: Vec::new().into(),
+        })
+    }
+
+    fn inputs(blocks: &[BlockData]) -> Vec<Vec<Input>> {
+        blocks[0]
+            .transactions
+            .iter()
+            .map(|tx| match &tx.transaction {
+                Transaction::Script(script) => script.inputs.clone(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    fn owners(inputs: &[Input]) -> Vec<[u8; 32]> {
+        inputs
+            .iter()
+            .map(|input| match input {
+                Input::Coin(coin) => *coin.owner,
+                Input::Message(message) => *message.recipient,
+                Input::Contract(contract) => *contract.contract_id,
+            })
+            .collect()
+    }
+
+    fn receipt_types(blocks: &[BlockData]) -> Vec<Vec<&'static str>> {
+        blocks[0]
+            .transactions
+            .iter()
+            .map(|tx| {
+                tx.receipts
+                    .iter()
+                    .map(|receipt| match receipt {
+                        Receipt::Call { .. } => "Call",
+                        Receipt::Return { .. } => "Return",
+                        Receipt::Log { .. } => "Log",
+                        Receipt::ScriptResult { .. } => "ScriptResult",
+                        _ => "Other",
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_strips_receipts_of_other_contracts() {
+        let filter = SubscriptionFilter::new(&manifest(&[[1u8; 32]]), ["Log"]);
+        assert!(filter.is_enabled());
+
+        let blocks = filter.filter_blocks(vec![block(vec![
+            tx(vec![log([1u8; 32]), log([2u8; 32]), script_result()]),
+            tx(vec![log([2u8; 32])]),
+        ])]);
+
+        // Transactions left without receipts are dropped.
+        assert_eq!(receipt_types(&blocks), vec![vec!["Log"]]);
+        let Receipt::Log { id, .. } = &blocks[0].transactions[0].receipts[0] else {
+            panic!("Expected a Log receipt.");
+        };
+        assert_eq!(*id, ContractId::from([1u8; 32]));
+    }
+
+    #[test]
+    fn test_filter_keeps_calls_for_return_handlers() {
+        let receipts = || vec![call([1u8; 32]), ret([1u8; 32]), log([1u8; 32])];
+
+        let filter = SubscriptionFilter::new(&manifest(&[[1u8; 32]]), ["Return"]);
+        let blocks = filter.filter_blocks(vec![block(vec![tx(receipts())])]);
+        assert_eq!(receipt_types(&blocks), vec![vec!["Call", "Return"]]);
+
+        // Handlers of ABI types need `ReturnData`, and so `Call`, receipts too.
+        let filter = SubscriptionFilter::new(&manifest(&[[1u8; 32]]), ["SomeEvent"]);
+        let blocks = filter.filter_blocks(vec![block(vec![tx(receipts())])]);
+        assert_eq!(receipt_types(&blocks), vec![vec!["Call"]]);
+
+        let filter = SubscriptionFilter::new(&manifest(&[[1u8; 32]]), ["Log"]);
+        let blocks = filter.filter_blocks(vec![block(vec![tx(receipts())])]);
+        assert_eq!(receipt_types(&blocks), vec![vec!["Log"]]);
+    }
+
+    #[test]
+    fn test_filter_passes_blocks_through_for_unfilterable_types() {
+        let filter =
+            SubscriptionFilter::new(&manifest(&[[1u8; 32]]), ["Log", "TransactionData"]);
+        assert!(!filter.is_enabled());
+
+        let blocks = filter.filter_blocks(vec![block(vec![tx(vec![log([2u8; 32])])])]);
+        assert_eq!(receipt_types(&blocks), vec![vec!["Log"]]);
+        let Transaction::Script(script) = &blocks[0].transactions[0].transaction else {
+            panic!("Expected a script transaction.");
+        };
+        assert_eq!(script.gas_limit, 42);
+    }
+
+    #[test]
+    fn test_filter_keeps_emptied_blocks() {
+        let filter = SubscriptionFilter::new(&manifest(&[[1u8; 32]]), ["Log"]);
+
+        let blocks = filter
+            .filter_blocks(vec![block(vec![tx(vec![log([2u8; 32])])]), test_block(2)]);
+        assert_eq!(
+            blocks.iter().map(|b| b.height).collect::<Vec<u64>>(),
+            vec![1, 2]
+        );
+        assert!(blocks.iter().all(|b| b.transactions.is_empty()));
+    }
+
+    #[test]
+    fn test_filter_replaces_transaction_bodies_with_defaults() {
+        let filter = SubscriptionFilter::new(&manifest(&[[1u8; 32]]), ["Log"]);
+
+        let blocks = filter.filter_blocks(vec![block(vec![tx(vec![log([1u8; 32])])])]);
+        let Transaction::Script(script) = &blocks[0].transactions[0].transaction else {
+            panic!("Expected a script transaction.");
+        };
+        assert_eq!(script.gas_limit, 0);
+        assert_eq!(blocks[0].transactions[0].receipts.len(), 1);
+    }
+
+    #[test]
+    fn test_filter_strips_inputs_of_other_predicates() {
+        let mut manifest = Manifest::try_from(MANIFEST).unwrap();
+        manifest.predicate_roots = Some(vec![format!("0x{}", "01".repeat(32))]);
+
+        let filter = SubscriptionFilter::new(&manifest, ["InputCoin", "InputMessage"]);
+        assert!(filter.is_enabled());
+
+        let with_inputs = |inputs| TransactionData {
+            transaction: Transaction::Script(Script {
+                gas_limit: 42,
+                inputs,
+                ..Script::default()
+            }),
+            ..tx(vec![log([1u8; 32])])
+        };
+
+        let blocks = filter.filter_blocks(vec![block(vec![
+            with_inputs(vec![
+                coin([1u8; 32], &[0x42]),
+                // Only inputs spent by a predicate have a predicate root.
+                coin([1u8; 32], &[]),
+                coin([2u8; 32], &[0x42]),
+                message([1u8; 32], &[0x42]),
+                message([2u8; 32], &[0x42]),
+            ]),
+            with_inputs(vec![coin([2u8; 32], &[0x42])]),
+        ])]);
+
+        // Transactions left without receipts or inputs are dropped.
+        let inputs = inputs(&blocks);
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(owners(&inputs[0]), vec![[1u8; 32], [1u8; 32]]);
+        assert!(matches!(inputs[0][0], Input::Coin(_)));
+        assert!(matches!(inputs[0][1], Input::Message(_)));
+        assert!(blocks[0].transactions[0].receipts.is_empty());
+
+        let Transaction::Script(script) = &blocks[0].transactions[0].transaction else {
+            panic!("Expected a script transaction.");
+        };
+        assert_eq!(script.gas_limit, 0);
+    }
+
+    #[test]
+    fn test_filter_strips_inputs_unless_handled() {
+        let mut manifest = manifest(&[[1u8; 32]]);
+        manifest.predicate_roots = Some(vec![format!("0x{}", "01".repeat(32))]);
+
+        let filter = SubscriptionFilter::new(&manifest, ["Log"]);
+        let blocks = filter.filter_blocks(vec![block(vec![TransactionData {
+            transaction: Transaction::Script(Script {
+                inputs: vec![coin([1u8; 32], &[0x42])],
+                ..Script::default()
+            }),
+            ..tx(vec![log([1u8; 32])])
+        }])]);
+
+        assert_eq!(receipt_types(&blocks), vec![vec!["Log"]]);
+        assert!(inputs(&blocks)[0].is_empty());
+    }
+}
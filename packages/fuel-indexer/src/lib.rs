@@ -1,20 +1,28 @@
 #![deny(unused_crate_dependencies)]
+mod block_store;
 pub mod cli;
 pub(crate) mod commands;
 mod database;
 pub mod executor;
+mod fetcher;
 pub mod ffi;
+mod filter;
+mod pruner;
 pub(crate) mod queries;
 mod service;
 
+pub use block_store::BlockStore;
 pub use database::Database;
 pub use executor::{Executor, IndexEnv, NativeIndexExecutor, WasmIndexExecutor};
+pub use fetcher::{BlockFetcher, BlockFetchers, BlockPage};
+pub use filter::SubscriptionFilter;
 pub use fuel_indexer_database::IndexerDatabaseError;
 pub use fuel_indexer_lib::{
     config::IndexerConfig,
     manifest::{Manifest, ManifestError, Module},
 };
 pub use fuel_indexer_schema::{db::IndexerSchemaDbError, FtColumn};
+use fuel_indexer_types::ffi::{WasmPanic, FFI_VERSION, MIN_SUPPORTED_FFI_VERSION};
 pub use service::IndexerService;
 use thiserror::Error;
 use wasmer::{ExportError, InstantiationError, RuntimeError};
@@ -53,6 +61,15 @@ pub enum IndexerError {
     FFIError(#[from] ffi::FFIError),
     #[error("Missing handler")]
     MissingHandler,
+    #[error("Indexer panicked {panic}{}", .block_height.map(|height| format!(" while handling block {height}")).unwrap_or_default())]
+    Panic {
+        panic: WasmPanic,
+        block_height: Option<u64>,
+    },
+    #[error("Indexer module was built for FFI version {0}, but only versions {} to {} are supported. Rebuild it with a compatible version of fuel-indexer-utils", MIN_SUPPORTED_FFI_VERSION, FFI_VERSION)]
+    UnsupportedFfiVersion(u32),
+    #[error("Indexer module imports {0:?}, which this indexer service does not provide. It was likely built for a newer FFI version")]
+    MissingImport(String),
     #[error("Indexer transaction error {0:?}")]
     TxError(#[from] crate::executor::TxError),
     #[error("Database error {0:?}")]
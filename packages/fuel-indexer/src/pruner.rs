@@ -0,0 +1,167 @@
+use crate::{IndexerConfig, IndexerResult};
+use fuel_indexer_database::{
+    queries,
+    types::{Constraint, ForeignKey, IndexerAsset, IndexerAssetType, SqlNamed},
+    IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_lib::{
+    defaults, graphql::GraphQLSchema, manifest::Manifest, ExecutionSource,
+};
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use sqlx::types::chrono::Utc;
+use tokio::time::{sleep, Duration};
+use tracing::{error, info, warn};
+
+/// Periodically delete the expired rows of every registered indexer whose schema
+/// declares retention policies.
+pub(crate) async fn create_pruning_task(
+    config: IndexerConfig,
+    pool: IndexerConnectionPool,
+) {
+    // Configuration files and arguments reject an interval of 0, but a config built
+    // in code might still have one, which would make this a busy loop.
+    if config.prune_interval_secs == 0 {
+        warn!("Pruning interval is 0 seconds, expired rows will not be pruned.");
+        return;
+    }
+
+    loop {
+        sleep(Duration::from_secs(config.prune_interval_secs)).await;
+
+        if let Err(e) = prune_indexers(&pool).await {
+            error!("Failed to prune indexers: {e:?}");
+        }
+    }
+}
+
+/// Delete the expired rows of every registered indexer.
+async fn prune_indexers(pool: &IndexerConnectionPool) -> IndexerResult<()> {
+    let mut conn = pool.acquire().await?;
+    let indexers = queries::all_registered_indexers(&mut conn).await?;
+
+    for indexer in indexers {
+        let (namespace, identifier) = (&indexer.namespace, &indexer.identifier);
+        if let Err(e) = prune_indexer(pool, namespace, identifier).await {
+            error!("Failed to prune Indexer({namespace}.{identifier}): {e:?}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Delete the expired rows of each table of an indexer that has a retention policy.
+///
+/// Rows are deleted in batches, each in a statement of its own, so that pruning
+/// never holds locks on a large number of rows at once.
+async fn prune_indexer(
+    pool: &IndexerConnectionPool,
+    namespace: &str,
+    identifier: &str,
+) -> IndexerResult<()> {
+    // Native indexers don't register a manifest, so the schema is built from the
+    // latest graph root rather than loaded using `IndexerSchema::load`.
+    let mut conn = pool.acquire().await?;
+    let index_id = queries::get_indexer_id(&mut conn, namespace, identifier).await?;
+    let execution_source = match queries::latest_asset_for_indexer(
+        &mut conn,
+        &index_id,
+        IndexerAssetType::Manifest,
+    )
+    .await
+    {
+        Ok(IndexerAsset { bytes, .. }) => Manifest::try_from(&bytes)?.execution_source(),
+        Err(sqlx::Error::RowNotFound) => ExecutionSource::Native,
+        Err(e) => return Err(e.into()),
+    };
+
+    let root = queries::graph_root_latest(&mut conn, namespace, identifier).await?;
+    let schema = IndexerSchema::new(
+        namespace,
+        identifier,
+        &GraphQLSchema::new(root.schema),
+        pool.database_type(),
+        execution_source,
+    )?;
+    let retention = schema.parsed().retention();
+    if retention.is_empty() {
+        return Ok(());
+    }
+
+    let block_height =
+        queries::last_block_height_for_indexer(&mut conn, namespace, identifier).await?;
+    let now = Utc::now().timestamp();
+
+    for table in schema.tables() {
+        let Some((_, policy)) = retention
+            .iter()
+            .find(|(name, _)| name.to_lowercase() == table.sql_name())
+        else {
+            continue;
+        };
+        let Some(cutoff) = policy.cutoff(block_height, now) else {
+            continue;
+        };
+
+        // Rows that are still referenced are kept until the rows referencing them are
+        // deleted themselves.
+        let references = schema
+            .tables()
+            .iter()
+            .flat_map(|t| t.constraints())
+            .filter_map(|c| match c {
+                Constraint::Fk(fk) if fk.ref_tablename == table.sql_name() => Some(fk),
+                _ => None,
+            })
+            .collect::<Vec<&ForeignKey>>();
+
+        let stmnt = table.prune(
+            &policy.field,
+            cutoff,
+            &references,
+            defaults::PRUNE_BATCH_SIZE,
+        );
+        let mut rows = delete_in_batches(&mut conn, stmnt).await?;
+
+        if table.is_historical() {
+            let stmnt =
+                table.prune_history(&policy.field, cutoff, defaults::PRUNE_BATCH_SIZE);
+            rows += delete_in_batches(&mut conn, stmnt).await?;
+        }
+
+        if rows > 0 {
+            info!(
+                "Pruned {rows} expired rows of {} for Indexer({namespace}.{identifier}).",
+                table.qualified_name()
+            );
+        }
+
+        #[cfg(feature = "metrics")]
+        METRICS
+            .pruning
+            .record(namespace, identifier, &table.sql_name(), rows as u64);
+    }
+
+    Ok(())
+}
+
+/// Run a statement that deletes a batch of expired rows until a batch deletes fewer
+/// than `PRUNE_BATCH_SIZE` rows, returning the number of rows deleted.
+///
+/// A batch of versions of a history table holds every expired version of the rows it
+/// selects, so it's never smaller than the number of rows selected either: a batch
+/// that deletes fewer rows than the limit selected every expired row that was left.
+async fn delete_in_batches(
+    conn: &mut IndexerConnection,
+    stmnt: String,
+) -> IndexerResult<usize> {
+    let mut rows = 0;
+    loop {
+        let deleted = queries::execute_query(conn, stmnt.clone()).await?;
+        rows += deleted;
+        if deleted < defaults::PRUNE_BATCH_SIZE {
+            return Ok(rows);
+        }
+    }
+}
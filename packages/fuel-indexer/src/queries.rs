@@ -6,8 +6,12 @@ use fuel_core_client::client::{
         tx::OpaqueTransaction,
         BlockId, ConnectionArgs, PageInfo,
     },
-    FuelClient, PaginatedResult, PaginationRequest,
+    FuelClient, PageDirection, PaginatedResult, PaginationRequest,
 };
+use fuel_indexer_lib::defaults::NODE_GRAPHQL_PAGE_SIZE;
+use fuel_indexer_types::fuel::field::Outputs;
+use fuel_tx::{ContractId, Output, Transaction};
+use fuel_vm::prelude::Deserializable;
 
 #[derive(cynic::QueryFragment, Debug)]
 #[cynic(
@@ -87,6 +91,20 @@ pub trait ClientExt {
         &self,
         request: PaginationRequest<String>,
     ) -> std::io::Result<PaginatedResult<FullBlock, String>>;
+
+    /// Return the height of the block with the given ID, if it exists.
+    async fn block_height_by_id(&self, id: &str) -> std::io::Result<Option<u64>>;
+
+    /// Return the height of the first block produced at or after the given UNIX
+    /// timestamp, if such a block exists yet.
+    async fn block_height_at_time(&self, time: i64) -> std::io::Result<Option<u64>>;
+
+    /// Return the height of the first block in which any of the given contracts
+    /// was deployed, if any of them was deployed.
+    async fn contract_deploy_height(
+        &self,
+        contract_ids: &[ContractId],
+    ) -> std::io::Result<Option<u64>>;
 }
 
 #[async_trait::async_trait]
@@ -99,4 +117,83 @@ impl ClientExt for FuelClient {
         let blocks = self.query(query).await?.blocks.into();
         Ok(blocks)
     }
+
+    async fn block_height_by_id(&self, id: &str) -> std::io::Result<Option<u64>> {
+        let block = self.block(id).await?;
+        Ok(block.map(|block| block.header.height.0))
+    }
+
+    async fn block_height_at_time(&self, time: i64) -> std::io::Result<Option<u64>> {
+        let latest = self.chain_info().await?.latest_block;
+        if latest.header.time.0.to_unix() < time {
+            return Ok(None);
+        }
+
+        // Block times are monotonic, so the first block at or after `time` is found
+        // with a binary search over heights.
+        let (mut low, mut high) = (0, latest.header.height.0);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let block_time = match self.block_by_height(mid).await? {
+                Some(block) => block.header.time.0.to_unix(),
+                None => i64::MIN,
+            };
+
+            if block_time < time {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(Some(low))
+    }
+
+    async fn contract_deploy_height(
+        &self,
+        contract_ids: &[ContractId],
+    ) -> std::io::Result<Option<u64>> {
+        let mut cursor = None;
+
+        loop {
+            let PaginatedResult {
+                cursor: next_cursor,
+                results,
+                has_next_page,
+                ..
+            } = self
+                .full_blocks(PaginationRequest {
+                    cursor,
+                    results: NODE_GRAPHQL_PAGE_SIZE,
+                    direction: PageDirection::Forward,
+                })
+                .await?;
+
+            for block in results {
+                let is_deployed = block.transactions.iter().any(|tx| {
+                    match Transaction::from_bytes(tx.raw_payload.0 .0.as_slice()) {
+                        Ok(Transaction::Create(create)) => {
+                            create.outputs().iter().any(|output| match output {
+                                Output::ContractCreated { contract_id, .. } => {
+                                    contract_ids.contains(contract_id)
+                                }
+                                _ => false,
+                            })
+                        }
+                        _ => false,
+                    }
+                });
+
+                if is_deployed {
+                    return Ok(Some(block.header.height.0));
+                }
+            }
+
+            if !has_next_page || next_cursor.is_none() {
+                return Ok(None);
+            }
+
+            cursor = next_cursor;
+        }
+    }
 }
@@ -1,20 +1,25 @@
 use crate::{
-    executor::{ExecutorSource, NativeIndexExecutor, WasmIndexExecutor},
+    executor::{fuel_node_addr, ExecutorSource, NativeIndexExecutor, WasmIndexExecutor},
+    fetcher::BlockFetchers,
+    pruner::create_pruning_task,
+    queries::ClientExt,
     Database, IndexerConfig, IndexerError, IndexerResult, Manifest,
 };
 use async_std::sync::{Arc, Mutex};
+use fuel_core_client::client::FuelClient;
 use fuel_indexer_database::{
     queries, types::IndexerAssetType, IndexerConnection, IndexerConnectionPool,
 };
-use fuel_indexer_lib::{defaults, utils::ServiceRequest};
-use fuel_indexer_schema::db::manager::SchemaManager;
-use fuel_indexer_types::fuel::BlockData;
+use fuel_indexer_lib::{defaults, manifest::StartPosition, utils::ServiceRequest};
+use fuel_indexer_schema::db::{manager::SchemaManager, snapshot::IndexerSnapshot};
+use fuel_indexer_types::{fuel::BlockData, scalar::Bech32ContractId};
 use futures::{
     stream::{FuturesUnordered, StreamExt},
     Future,
 };
 use std::collections::HashMap;
 use std::marker::Send;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::{
     sync::mpsc::Receiver,
@@ -30,6 +35,7 @@ pub struct IndexerService {
     handles: HashMap<String, JoinHandle<()>>,
     rx: Receiver<ServiceRequest>,
     killers: HashMap<String, Arc<AtomicBool>>,
+    fetchers: BlockFetchers,
 }
 
 impl IndexerService {
@@ -39,6 +45,10 @@ impl IndexerService {
         rx: Receiver<ServiceRequest>,
     ) -> IndexerResult<IndexerService> {
         let manager = SchemaManager::new(pool.clone());
+        let fetchers = BlockFetchers::new(
+            defaults::SHARED_BLOCK_WINDOW_SIZE,
+            config.block_store.clone(),
+        );
 
         Ok(IndexerService {
             config,
@@ -46,6 +56,7 @@ impl IndexerService {
             manager,
             handles: HashMap::default(),
             killers: HashMap::default(),
+            fetchers,
             rx,
         })
     }
@@ -106,13 +117,25 @@ impl IndexerService {
             )
             .await?;
 
-        let start_block = get_start_block(&mut conn, &manifest).await?;
+        let start_block = get_start_block(&mut conn, &self.config, &manifest)
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to resolve the start block of Indexer({}): {e}",
+                    manifest.uid()
+                );
+                e
+            })?;
         manifest.start_block = Some(start_block);
+        let fetcher = self
+            .fetchers
+            .get(&fuel_node_addr(&self.config, &manifest))?;
         let (handle, exec_source, killer) = WasmIndexExecutor::create(
             &self.config,
             &manifest,
             ExecutorSource::Manifest,
             self.pool.clone(),
+            fetcher,
         )
         .await?;
 
@@ -158,13 +181,36 @@ impl IndexerService {
             let assets = queries::latest_assets_for_indexer(&mut conn, &index.id).await?;
             let mut manifest = Manifest::try_from(&assets.manifest.bytes)?;
 
-            let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
+            // Indexers registered before key/value stores were introduced don't have
+            // one yet.
+            queries::create_kv_store(
+                &mut conn,
+                &manifest.namespace,
+                &manifest.identifier,
+            )
+            .await?;
+
+            // An indexer whose start block can't be resolved is skipped, so that it
+            // doesn't keep the others from starting.
+            let start_block =
+                match get_start_block(&mut conn, &self.config, &manifest).await {
+                    Ok(start_block) => start_block,
+                    Err(e) => {
+                        let uid = manifest.uid();
+                        error!("Skipping Indexer({uid}), its start block failed: {e}");
+                        continue;
+                    }
+                };
             manifest.start_block = Some(start_block);
+            let fetcher = self
+                .fetchers
+                .get(&fuel_node_addr(&self.config, &manifest))?;
             let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
                 &self.config,
                 &manifest,
                 ExecutorSource::Registry(assets.wasm.bytes),
                 self.pool.clone(),
+                fetcher,
             )
             .await?;
 
@@ -176,6 +222,56 @@ impl IndexerService {
         Ok(())
     }
 
+    pub async fn snapshot_indexer(
+        &self,
+        namespace: &str,
+        identifier: &str,
+    ) -> IndexerResult<IndexerSnapshot> {
+        Ok(IndexerSnapshot::take(&self.pool, namespace, identifier).await?)
+    }
+
+    pub async fn restore_indexer_from_snapshot(
+        &mut self,
+        snapshot: IndexerSnapshot,
+    ) -> IndexerResult<()> {
+        snapshot.restore(&self.pool).await?;
+
+        let mut conn = self.pool.acquire().await?;
+        let index_id =
+            queries::get_indexer_id(&mut conn, &snapshot.namespace, &snapshot.identifier)
+                .await?;
+        let assets = queries::latest_assets_for_indexer(&mut conn, &index_id).await?;
+        let mut manifest = Manifest::try_from(&assets.manifest.bytes)?;
+
+        let start_block = get_start_block(&mut conn, &self.config, &manifest)
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to resolve the start block of Indexer({}): {e}",
+                    manifest.uid()
+                );
+                e
+            })?;
+        manifest.start_block = Some(start_block);
+        let fetcher = self
+            .fetchers
+            .get(&fuel_node_addr(&self.config, &manifest))?;
+        let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
+            &self.config,
+            &manifest,
+            ExecutorSource::Registry(assets.wasm.bytes),
+            self.pool.clone(),
+            fetcher,
+        )
+        .await?;
+
+        info!("Restored Indexer({}) from snapshot", manifest.uid());
+        self.handles.insert(manifest.uid(), handle);
+        self.killers.insert(manifest.uid(), killer);
+
+        Ok(())
+    }
+
     pub async fn register_native_indexer<
         T: Future<Output = IndexerResult<()>> + Send + 'static,
     >(
@@ -203,14 +299,28 @@ impl IndexerService {
                 &mut conn,
             )
             .await?;
+        queries::create_kv_store(&mut conn, &manifest.namespace, &manifest.identifier)
+            .await?;
 
-        let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
+        let start_block = get_start_block(&mut conn, &self.config, &manifest)
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to resolve the start block of Indexer({}): {e}",
+                    manifest.uid()
+                );
+                e
+            })?;
         manifest.start_block = Some(start_block);
         let uid = manifest.uid();
+        let fetcher = self
+            .fetchers
+            .get(&fuel_node_addr(&self.config, &manifest))?;
         let (handle, _module_bytes, killer) = NativeIndexExecutor::<T>::create(
             &self.config,
             &manifest,
             self.pool.clone(),
+            fetcher,
             handle_events,
         )
         .await?;
@@ -229,6 +339,7 @@ impl IndexerService {
             pool,
             config,
             killers,
+            fetchers,
             ..
         } = self;
 
@@ -236,12 +347,15 @@ impl IndexerService {
             handles.into_values(),
         )));
 
+        tokio::spawn(create_pruning_task(config.clone(), pool.clone()));
+
         let _ = tokio::spawn(create_service_task(
             rx,
             config.clone(),
             pool.clone(),
             futs.clone(),
             killers,
+            fetchers,
         ))
         .await
         .unwrap();
@@ -258,6 +372,7 @@ async fn create_service_task(
     pool: IndexerConnectionPool,
     futs: Arc<Mutex<FuturesUnordered<JoinHandle<()>>>>,
     mut killers: HashMap<String, Arc<AtomicBool>>,
+    mut fetchers: BlockFetchers,
 ) -> IndexerResult<()> {
     loop {
         let futs = futs.lock().await;
@@ -280,15 +395,28 @@ async fn create_service_task(
                             let mut manifest =
                                 Manifest::try_from(&assets.manifest.bytes)?;
 
-                            let start_block =
-                                get_start_block(&mut conn, &manifest).await?;
+                            let start_block = match get_start_block(
+                                &mut conn, &config, &manifest,
+                            )
+                            .await
+                            {
+                                Ok(start_block) => start_block,
+                                Err(e) => {
+                                    let uid = manifest.uid();
+                                    error!("Not reloading Indexer({uid}), its start block failed: {e}");
+                                    continue;
+                                }
+                            };
                             manifest.start_block = Some(start_block);
+                            let fetcher =
+                                fetchers.get(&fuel_node_addr(&config, &manifest))?;
                             let (handle, _module_bytes, killer) =
                                 WasmIndexExecutor::create(
                                     &config,
                                     &manifest,
                                     ExecutorSource::Registry(assets.wasm.bytes),
                                     pool.clone(),
+                                    fetcher,
                                 )
                                 .await?;
 
@@ -332,6 +460,7 @@ async fn create_service_task(
 
 async fn get_start_block(
     conn: &mut IndexerConnection,
+    config: &IndexerConfig,
     manifest: &Manifest,
 ) -> Result<u64, IndexerError> {
     let Manifest {
@@ -341,11 +470,31 @@ async fn get_start_block(
         resumable,
         ..
     } = manifest;
+
+    let start_block = match manifest.start_position()? {
+        Some(position) => {
+            Some(resolve_start_position(conn, config, manifest, position).await?)
+        }
+        None => *start_block,
+    };
+
     match &resumable {
         Some(resumable) => {
+            // The executor records a checkpoint in the same transaction as the entities
+            // written for a block, so resuming right after it handles each block exactly
+            // once. Indexers without a checkpoint yet resume from their own metadata.
             let last =
-                queries::last_block_height_for_indexer(conn, namespace, identifier)
-                    .await?;
+                match queries::last_checkpoint_for_indexer(conn, namespace, identifier)
+                    .await?
+                {
+                    Some(checkpoint) => checkpoint.block_height + 1,
+                    None => {
+                        queries::last_block_height_for_indexer(
+                            conn, namespace, identifier,
+                        )
+                        .await?
+                    }
+                };
             let start = start_block.unwrap_or(last);
             let resume = if *resumable {
                 std::cmp::max(start, last)
@@ -358,3 +507,73 @@ async fn get_start_block(
         None => Ok(start_block.unwrap_or(1)),
     }
 }
+
+/// Resolve a start position given by the manifest to a block height, using the
+/// indexer's block source.
+///
+/// The resolved height is stored in the indexer's status, so that restarting the
+/// indexer with the same manifest starts from the same height.
+async fn resolve_start_position(
+    conn: &mut IndexerConnection,
+    config: &IndexerConfig,
+    manifest: &Manifest,
+    position: StartPosition,
+) -> Result<u64, IndexerError> {
+    let index_id =
+        queries::get_indexer_id(conn, &manifest.namespace, &manifest.identifier).await?;
+    let source = position.to_string();
+
+    if let Some(status) = queries::indexer_status(conn, &index_id).await? {
+        if status.start_block_source == source {
+            return Ok(status.start_block);
+        }
+    }
+
+    let client =
+        FuelClient::from_str(&fuel_node_addr(config, manifest)).map_err(|e| {
+            IndexerError::Unknown(format!(
+                "Indexer({}) client node connection failed: {e}",
+                manifest.uid()
+            ))
+        })?;
+
+    let height = match &position {
+        StartPosition::BlockId(id) => {
+            client
+                .block_height_by_id(&format!("{:#x}", fuel_tx::Bytes32::from(*id)))
+                .await?
+        }
+        StartPosition::Time(time) => client.block_height_at_time(*time).await?,
+        StartPosition::ContractDeploy(contract_ids) => {
+            let contract_ids = contract_ids
+                .iter()
+                .map(|id| {
+                    Bech32ContractId::from_str(id)
+                        .map(|id| fuel_tx::ContractId::from(*id.hash()))
+                        .map_err(|e| {
+                            IndexerError::Unknown(format!(
+                                "Invalid contract_id '{id}': {e}"
+                            ))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            client.contract_deploy_height(&contract_ids).await?
+        }
+    };
+
+    let height = height.ok_or_else(|| {
+        IndexerError::Unknown(format!(
+            "Indexer({}) could not resolve '{source}' to a block height",
+            manifest.uid()
+        ))
+    })?;
+
+    queries::set_indexer_start_block(conn, &index_id, height, &source).await?;
+
+    info!(
+        "Indexer({}) resolved '{source}' to block {height}",
+        manifest.uid()
+    );
+
+    Ok(height)
+}
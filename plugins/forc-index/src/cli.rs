@@ -2,9 +2,11 @@
 pub(crate) use crate::commands::{
     auth::Command as AuthCommand, build::Command as BuildCommand,
     check::Command as CheckCommand, deploy::Command as DeployCommand,
-    init::Command as InitCommand, kill::Command as KillCommand,
+    export::Command as ExportCommand, init::Command as InitCommand,
+    kill::Command as KillCommand, logs::Command as LogsCommand,
     new::Command as NewCommand, pull_abi::Command as PullAbiCommand,
-    remove::Command as RemoveCommand, start::Command as StartCommand,
+    remove::Command as RemoveCommand, restore::Command as RestoreCommand,
+    snapshot::Command as SnapshotCommand, start::Command as StartCommand,
     status::Command as StatusCommand, welcome::Command as WelcomeCommand,
 };
 use clap::{Parser, Subcommand};
@@ -36,6 +38,10 @@ pub enum ForcIndex {
     PullAbi(PullAbiCommand),
     Kill(KillCommand),
     Status(StatusCommand),
+    Logs(LogsCommand),
+    Export(ExportCommand),
+    Snapshot(SnapshotCommand),
+    Restore(RestoreCommand),
     //Welcome(WelcomeCommand),
 }
 
@@ -65,5 +71,9 @@ pub async fn run_cli() -> Result<(), anyhow::Error> {
         },
         ForcIndex::Kill(command) => crate::commands::kill::exec(command),
         ForcIndex::Status(command) => crate::commands::status::exec(command).await,
+        ForcIndex::Logs(command) => crate::commands::logs::exec(command).await,
+        ForcIndex::Export(command) => crate::commands::export::exec(command).await,
+        ForcIndex::Snapshot(command) => crate::commands::snapshot::exec(command).await,
+        ForcIndex::Restore(command) => crate::commands::restore::exec(command).await,
     }
 }
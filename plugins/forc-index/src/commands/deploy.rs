@@ -65,6 +65,21 @@ pub struct Command {
     /// Do not build before deploying.
     #[clap(long, help = "Do not build before deploying.")]
     pub skip_build: bool,
+
+    /// Migrate the schema of an existing indexer in place, keeping its data.
+    #[clap(
+        long,
+        help = "Migrate the schema of an existing indexer in place, keeping its data."
+    )]
+    pub migrate: bool,
+
+    /// Print the schema migration that would be applied, without deploying.
+    #[clap(
+        long,
+        requires = "migrate",
+        help = "Print the schema migration that would be applied, without deploying."
+    )]
+    pub dry_run: bool,
 }
 
 impl Default for Command {
@@ -81,6 +96,8 @@ impl Default for Command {
             locked: false,
             native: false,
             skip_build: false,
+            migrate: false,
+            dry_run: false,
             target_dir: Some(std::path::PathBuf::from(".")),
         }
     }
@@ -0,0 +1,73 @@
+use crate::{defaults, ops::forc_index_export};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Export the entities of a registered indexer to CSV, JSON Lines or Parquet files.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Name of the entity to export.
+    #[clap(
+        short,
+        long,
+        help = "Name of the entity to export. Every entity is exported if not specified."
+    )]
+    pub entity: Option<String>,
+
+    /// File format of the export.
+    #[clap(long, default_value = defaults::EXPORT_FORMAT, help = "File format of the export.", value_parser(["csv", "jsonl", "parquet"]))]
+    pub format: String,
+
+    /// Filter that the exported records must match.
+    #[clap(
+        long,
+        help = "Filter that the exported records must match, as JSON in the shape of the `filter` argument of GraphQL queries."
+    )]
+    pub filter: Option<String>,
+
+    /// Only export the versions of historical entities valid at or after this block.
+    #[clap(
+        long,
+        help = "Only export the versions of historical entities valid at or after this block."
+    )]
+    pub from_block: Option<u64>,
+
+    /// Only export the versions of historical entities valid at or before this block.
+    #[clap(
+        long,
+        help = "Only export the versions of historical entities valid at or before this block."
+    )]
+    pub to_block: Option<u64>,
+
+    /// Path of the exported file, or of the directory of the exported files when
+    /// every entity is exported to CSV or Parquet.
+    #[clap(
+        short,
+        long,
+        help = "Path of the exported file, or of the directory of the exported files when every entity is exported to CSV or Parquet."
+    )]
+    pub output: Option<PathBuf>,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> anyhow::Result<()> {
+    forc_index_export::init(command).await
+}
@@ -0,0 +1,39 @@
+use crate::{defaults, ops::forc_index_logs};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Print the lines logged by a registered indexer.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Least severe level of the lines to print.
+    #[clap(long, help = "Least severe level of the lines to print.", value_parser(["error", "warn", "info", "debug", "trace"]))]
+    pub level: Option<String>,
+
+    /// Keep printing lines as they're logged.
+    #[clap(short, long, help = "Keep printing lines as they're logged.")]
+    pub follow: bool,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> anyhow::Result<()> {
+    forc_index_logs::init(command).await
+}
@@ -2,11 +2,15 @@ pub mod auth;
 pub mod build;
 pub mod check;
 pub mod deploy;
+pub mod export;
 pub mod init;
 pub mod kill;
+pub mod logs;
 pub mod new;
 pub mod pull_abi;
 pub mod remove;
+pub mod restore;
+pub mod snapshot;
 pub mod start;
 pub mod status;
 pub mod welcome;
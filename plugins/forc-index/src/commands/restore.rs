@@ -0,0 +1,27 @@
+use crate::{defaults, ops::forc_index_restore};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Restore an indexer from a snapshot, resuming indexing from the snapshot height.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path of the snapshot file.
+    #[clap(short, long, help = "Path of the snapshot file.")]
+    pub file: PathBuf,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> anyhow::Result<()> {
+    forc_index_restore::init(command).await
+}
@@ -0,0 +1,39 @@
+use crate::{defaults, ops::forc_index_snapshot};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Take a snapshot of a registered indexer's data, to be restored elsewhere.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Path of the snapshot file.
+    #[clap(
+        short,
+        long,
+        help = "Path of the snapshot file. Defaults to <namespace>_<identifier>.snapshot.json."
+    )]
+    pub output: Option<PathBuf>,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> anyhow::Result<()> {
+    forc_index_snapshot::init(command).await
+}
@@ -8,6 +8,8 @@ pub const INDEXER_BINARY_FILENAME: &str = "main.rs";
 pub const CARGO_CONFIG_DIR_NAME: &str = ".cargo";
 pub const CARGO_CONFIG_FILENAME: &str = "config";
 pub const INDEXER_SERVICE_HOST: &str = "http://127.0.0.1:29987";
+pub const LOGS_POLL_INTERVAL_SECS: u64 = 1;
+pub const EXPORT_FORMAT: &str = "jsonl";
 pub const GRAPHQL_API_HOST: &str = defaults::GRAPHQL_API_HOST;
 pub const GRAPHQL_API_PORT: &str = defaults::GRAPHQL_API_PORT;
 pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
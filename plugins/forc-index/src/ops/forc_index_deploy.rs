@@ -31,6 +31,8 @@ pub fn init(command: DeployCommand) -> anyhow::Result<()> {
         target_dir,
         verbose,
         skip_build,
+        migrate,
+        dry_run,
     } = command;
 
     if !skip_build {
@@ -74,12 +76,67 @@ pub fn init(command: DeployCommand) -> anyhow::Result<()> {
         }
     }
 
+    let mut headers = HeaderMap::new();
+    headers.insert(CONNECTION, "keep-alive".parse()?);
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    let client = Client::builder()
+        .tcp_keepalive(Duration::from_secs(TCP_TIMEOUT))
+        .connection_verbose(verbose)
+        .build()?;
+
+    if dry_run {
+        let target = format!("{url}/api/index/{namespace}/{identifier}/migration");
+        let schema = std::fs::read_to_string(&graphql_schema)?;
+
+        let res = client
+            .post(&target)
+            .body(schema)
+            .headers(headers)
+            .send()
+            .expect("Failed to plan schema migration.");
+
+        let status = res.status();
+        let res_json = res
+            .json::<Map<String, Value>>()
+            .expect("Failed to read JSON response.");
+
+        if status != StatusCode::OK {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+            info!("\n{}", to_string_pretty(&res_json)?);
+            return Ok(());
+        }
+
+        match res_json.get("report").and_then(|r| r.as_str()) {
+            Some(report) => info!("\n{report}"),
+            None => info!("\n{}", to_string_pretty(&res_json)?),
+        }
+
+        if let Some(statements) = res_json
+            .get("migration")
+            .and_then(|m| m.get("statements"))
+            .and_then(|s| s.as_array())
+        {
+            for stmnt in statements.iter().filter_map(|s| s.as_str()) {
+                info!("{stmnt}");
+            }
+        }
+
+        return Ok(());
+    }
+
     let form = Form::new()
         .file("manifest", &manifest_path)?
         .file("schema", graphql_schema)?
         .file("wasm", module.to_string())?;
 
-    let target = format!("{url}/api/index/{namespace}/{identifier}");
+    let target = if migrate {
+        format!("{url}/api/index/{namespace}/{identifier}?migrate=true")
+    } else {
+        format!("{url}/api/index/{namespace}/{identifier}")
+    };
 
     if verbose {
         info!(
@@ -91,12 +148,6 @@ pub fn init(command: DeployCommand) -> anyhow::Result<()> {
         info!("Deploying indexer...");
     }
 
-    let mut headers = HeaderMap::new();
-    headers.insert(CONNECTION, "keep-alive".parse()?);
-    if let Some(auth) = auth {
-        headers.insert(AUTHORIZATION, auth.parse()?);
-    }
-
     let pb = ProgressBar::new_spinner();
     pb.enable_steady_tick(Duration::from_millis(STEADY_TICK_INTERVAL));
     pb.set_style(
@@ -114,11 +165,6 @@ pub fn init(command: DeployCommand) -> anyhow::Result<()> {
     );
     pb.set_message("🚀 Deploying...");
 
-    let client = Client::builder()
-        .tcp_keepalive(Duration::from_secs(TCP_TIMEOUT))
-        .connection_verbose(verbose)
-        .build()?;
-
     let res = client
         .post(&target)
         .multipart(form)
@@ -0,0 +1,153 @@
+use crate::{cli::ExportCommand, utils::project_dir_info};
+use fuel_indexer_lib::{
+    graphql::{GraphQLSchema, ParsedGraphQLSchema},
+    manifest::Manifest,
+};
+use reqwest::header::{HeaderMap, AUTHORIZATION, CONNECTION};
+use serde_json::{to_string_pretty, value::Value, Map};
+use std::{fs::File, io::Write, path::PathBuf};
+use tracing::{error, info};
+
+pub async fn init(command: ExportCommand) -> anyhow::Result<()> {
+    let ExportCommand {
+        url,
+        manifest,
+        path,
+        entity,
+        format,
+        filter,
+        from_block,
+        to_block,
+        output,
+        auth,
+        verbose,
+    } = command;
+
+    let (_root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/index/{}/{}/export",
+        &manifest.namespace, &manifest.identifier
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONNECTION, "keep-alive".parse()?);
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    let mut query = vec![("format", format.clone())];
+    if let Some(filter) = filter {
+        query.push(("filter", filter));
+    }
+    if let Some(from_block) = from_block {
+        query.push(("from_block", from_block.to_string()));
+    }
+    if let Some(to_block) = to_block {
+        query.push(("to_block", to_block.to_string()));
+    }
+
+    // A JSON Lines export can hold every entity, whereas CSV and Parquet exports
+    // hold a single one, so every entity of the project's schema is exported to a
+    // file of its own.
+    let exports = match entity {
+        Some(entity) => {
+            let file = output.unwrap_or(PathBuf::from(format!("{entity}.{format}")));
+            vec![(Some(entity), file)]
+        }
+        None if format == "jsonl" => {
+            let file = output.unwrap_or(PathBuf::from(format!(
+                "{}_{}.jsonl",
+                manifest.namespace, manifest.identifier
+            )));
+            vec![(None, file)]
+        }
+        None => {
+            let dir = output.unwrap_or(PathBuf::from("."));
+            std::fs::create_dir_all(&dir)?;
+            schema_entities(&manifest)?
+                .into_iter()
+                .map(|entity| {
+                    let file = dir.join(format!("{entity}.{format}"));
+                    (Some(entity), file)
+                })
+                .collect()
+        }
+    };
+
+    let client = reqwest::Client::new();
+
+    for (entity, file) in exports {
+        let mut query = query.clone();
+        if let Some(entity) = &entity {
+            query.push(("entity", entity.clone()));
+        }
+
+        let mut res = match client
+            .get(&target)
+            .headers(headers.clone())
+            .query(&query)
+            .send()
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                if verbose {
+                    error!("\n❌ Failed to export indexer. Could not connect to indexer service:\n'{e}'");
+                } else {
+                    error!("\n❌ Failed to export indexer.");
+                }
+                return Ok(());
+            }
+        };
+
+        let status = res.status();
+        if status != reqwest::StatusCode::OK {
+            if verbose {
+                error!("\n❌ {target} returned a non-200 response code: {status:?}");
+            }
+
+            let result = res
+                .json::<Map<String, Value>>()
+                .await
+                .expect("Failed to read JSON response.");
+
+            info!("\n{}", to_string_pretty(&result)?);
+            return Ok(());
+        }
+
+        // Exports are written as they're received, rather than being held in memory.
+        let mut out = File::create(&file)?;
+        while let Some(chunk) = res.chunk().await? {
+            out.write_all(&chunk)?;
+        }
+
+        info!("✅ Exported to {}.", file.display());
+    }
+
+    Ok(())
+}
+
+/// Return the names of the entities of the indexer project's schema.
+fn schema_entities(manifest: &Manifest) -> anyhow::Result<Vec<String>> {
+    let schema = GraphQLSchema::new(manifest.graphql_schema()?);
+    let parsed = ParsedGraphQLSchema::new(
+        &manifest.namespace,
+        &manifest.identifier,
+        manifest.execution_source(),
+        Some(&schema),
+    )?;
+
+    let mut entities = parsed
+        .non_enum_typdefs()
+        .into_iter()
+        .map(|(name, _)| name.to_string())
+        .filter(|name| !parsed.is_virtual_typedef(name))
+        .collect::<Vec<String>>();
+    entities.sort();
+
+    Ok(entities)
+}
@@ -0,0 +1,115 @@
+use crate::{cli::LogsCommand, defaults, utils::project_dir_info};
+use fuel_indexer_database_types::IndexerLog;
+use fuel_indexer_lib::manifest::Manifest;
+use reqwest::header::{HeaderMap, AUTHORIZATION, CONNECTION};
+use serde_json::{to_string_pretty, value::Value, Map};
+use std::time::Duration;
+use tracing::{error, info};
+
+pub async fn init(command: LogsCommand) -> anyhow::Result<()> {
+    let LogsCommand {
+        url,
+        manifest,
+        path,
+        level,
+        follow,
+        auth,
+        verbose,
+    } = command;
+
+    let (_root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/index/{}/{}/logs",
+        &manifest.namespace, &manifest.identifier
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONNECTION, "keep-alive".parse()?);
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    let client = reqwest::Client::new();
+    let mut after: Option<i64> = None;
+
+    loop {
+        let mut query = Vec::new();
+        if let Some(level) = &level {
+            query.push(("level", level.clone()));
+        }
+        if let Some(after) = after {
+            query.push(("after", after.to_string()));
+        }
+
+        let res = match client
+            .get(&target)
+            .headers(headers.clone())
+            .query(&query)
+            .send()
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                if verbose {
+                    error!("\n❌ Failed to fetch logs. Could not connect to indexer service:\n'{e}'");
+                } else {
+                    error!("\n❌ Failed to fetch logs.");
+                }
+                return Ok(());
+            }
+        };
+
+        let status = res.status();
+        if status != reqwest::StatusCode::OK {
+            if verbose {
+                error!("\n❌ {target} returned a non-200 response code: {status:?}");
+            }
+
+            let result = res
+                .json::<Map<String, Value>>()
+                .await
+                .expect("Failed to read JSON response.");
+
+            info!("\n{}", to_string_pretty(&result)?);
+            return Ok(());
+        }
+
+        let logs = res
+            .json::<Vec<IndexerLog>>()
+            .await
+            .expect("Failed to read JSON response.");
+
+        let is_caught_up = logs.is_empty();
+        for log in logs {
+            print_log(&log);
+            after = Some(log.id);
+        }
+
+        if !follow {
+            return Ok(());
+        }
+
+        if is_caught_up {
+            tokio::time::sleep(Duration::from_secs(defaults::LOGS_POLL_INTERVAL_SECS))
+                .await;
+        }
+    }
+}
+
+fn print_log(log: &IndexerLog) {
+    let block = log
+        .block_height
+        .map(|height| format!(" [block {height}]"))
+        .unwrap_or_default();
+
+    println!(
+        "{} {:>5}{block} {}",
+        log.created_at.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+        log.level.to_uppercase(),
+        log.message
+    );
+}
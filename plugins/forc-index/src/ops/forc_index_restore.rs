@@ -0,0 +1,81 @@
+use crate::cli::RestoreCommand;
+use reqwest::header::{HeaderMap, AUTHORIZATION, CONNECTION, CONTENT_TYPE};
+use serde_json::{to_string_pretty, value::Value, Map};
+use tracing::{error, info};
+
+pub async fn init(command: RestoreCommand) -> anyhow::Result<()> {
+    let RestoreCommand {
+        url,
+        file,
+        auth,
+        verbose,
+    } = command;
+
+    let snapshot = std::fs::read(&file)?;
+
+    // The indexer is restored under the namespace and identifier of the snapshot, so
+    // that no project is needed to restore it.
+    let (namespace, identifier) = {
+        let archive: Map<String, Value> = serde_json::from_slice(&snapshot)?;
+        let field = |name: &str| {
+            archive
+                .get(name)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("❌ Snapshot {} has no {name}.", file.display())
+                })
+        };
+        (field("namespace")?, field("identifier")?)
+    };
+
+    let target = format!("{url}/api/index/{namespace}/{identifier}/restore");
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONNECTION, "keep-alive".parse()?);
+    headers.insert(CONTENT_TYPE, "application/json".parse()?);
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    let res = match reqwest::Client::new()
+        .post(&target)
+        .headers(headers)
+        .body(snapshot)
+        .send()
+        .await
+    {
+        Ok(res) => res,
+        Err(e) => {
+            if verbose {
+                error!("\n❌ Failed to restore indexer. Could not connect to indexer service:\n'{e}'");
+            } else {
+                error!("\n❌ Failed to restore indexer.");
+            }
+            return Ok(());
+        }
+    };
+
+    let status = res.status();
+    let result = res
+        .json::<Map<String, Value>>()
+        .await
+        .expect("Failed to read JSON response.");
+
+    if status != reqwest::StatusCode::OK {
+        if verbose {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}");
+        }
+
+        info!("\n{}", to_string_pretty(&result)?);
+        return Ok(());
+    }
+
+    if verbose {
+        info!("\n{}", to_string_pretty(&result)?);
+    }
+
+    info!("✅ Restored Indexer({namespace}.{identifier}).");
+
+    Ok(())
+}
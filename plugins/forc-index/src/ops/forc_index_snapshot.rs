@@ -0,0 +1,79 @@
+use crate::{cli::SnapshotCommand, utils::project_dir_info};
+use fuel_indexer_lib::manifest::Manifest;
+use reqwest::header::{HeaderMap, AUTHORIZATION, CONNECTION};
+use serde_json::{to_string_pretty, value::Value, Map};
+use std::{fs::File, io::Write, path::PathBuf};
+use tracing::{error, info};
+
+pub async fn init(command: SnapshotCommand) -> anyhow::Result<()> {
+    let SnapshotCommand {
+        url,
+        manifest,
+        path,
+        output,
+        auth,
+        verbose,
+    } = command;
+
+    let (_root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/index/{}/{}/snapshot",
+        &manifest.namespace, &manifest.identifier
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONNECTION, "keep-alive".parse()?);
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    let file = output.unwrap_or(PathBuf::from(format!(
+        "{}_{}.snapshot.json",
+        manifest.namespace, manifest.identifier
+    )));
+
+    let mut res = match reqwest::Client::new()
+        .get(&target)
+        .headers(headers)
+        .send()
+        .await
+    {
+        Ok(res) => res,
+        Err(e) => {
+            if verbose {
+                error!("\n❌ Failed to take snapshot of indexer. Could not connect to indexer service:\n'{e}'");
+            } else {
+                error!("\n❌ Failed to take snapshot of indexer.");
+            }
+            return Ok(());
+        }
+    };
+
+    let status = res.status();
+    if status != reqwest::StatusCode::OK {
+        if verbose {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}");
+        }
+
+        let result = res
+            .json::<Map<String, Value>>()
+            .await
+            .expect("Failed to read JSON response.");
+
+        info!("\n{}", to_string_pretty(&result)?);
+        return Ok(());
+    }
+
+    let mut out = File::create(&file)?;
+    while let Some(chunk) = res.chunk().await? {
+        out.write_all(&chunk)?;
+    }
+
+    info!("✅ Snapshot written to {}.", file.display());
+
+    Ok(())
+}
@@ -16,6 +16,7 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         postgres_database,
         postgres_host,
         postgres_port,
+        sqlite_path,
         local_fuel_node,
         run_migrations,
         metrics,
@@ -95,8 +96,15 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
                     }
                 }
             }
+            "sqlite" => {
+                cmd.arg("--database").arg("sqlite");
+
+                if let Some(path) = sqlite_path {
+                    cmd.arg("--sqlite-path").arg(path);
+                }
+            }
             _ => unreachable!(
-                "'postgres' is currently the only supported database option."
+                "'postgres' and 'sqlite' are the only supported database options."
             ),
         }
     }
@@ -2,11 +2,15 @@ pub mod forc_index_auth;
 pub mod forc_index_build;
 pub mod forc_index_check;
 pub mod forc_index_deploy;
+pub mod forc_index_export;
 pub mod forc_index_init;
 pub mod forc_index_kill;
+pub mod forc_index_logs;
 pub mod forc_index_new;
 pub mod forc_index_pull_abi;
 pub mod forc_index_remove;
+pub mod forc_index_restore;
+pub mod forc_index_snapshot;
 pub mod forc_index_start;
 pub mod forc_index_status;
 pub mod forc_index_welcome;